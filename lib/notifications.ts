@@ -9,10 +9,16 @@ import {
 import { type WatchedJob, useStore } from '../store/memory'
 import { usePersistedStore } from '../store/persisted'
 import type {
+    FilterPreviewEvent,
     NotificationCatalog,
     NotificationEventId,
     NotificationProvider,
+    NotificationSettings,
     NotificationTarget,
+    PayloadTemplate,
+    PendingDelivery,
+    TargetAuth,
+    TargetFilters,
 } from '../types/notifications'
 import rclone from './rclone/client'
 
@@ -52,6 +58,7 @@ export async function notify({ title, body }: { title: string; body: string }) {
 // Webhook engine wrappers
 // ---------------------------------------------------------------------------
 
+/** Secrets (`signingSecret`, `auth.secret`), if any, in plain text — Rust seals them. */
 export type NewNotificationTarget = Omit<
     NotificationTarget,
     'id' | 'createdAt' | 'lastSentAt' | 'lastError'
@@ -72,9 +79,22 @@ export async function addNotificationTarget(
     return await invoke<NotificationTarget>('notifications_add_target', { target })
 }
 
+/**
+ * A `null` template, signing secret or auth removes it; leaving the field out keeps the stored
+ * one. New secrets go in plain text — Rust seals them.
+ */
 export async function updateNotificationTarget(
     id: string,
-    patch: Partial<Omit<NotificationTarget, 'id' | 'createdAt' | 'lastSentAt' | 'lastError'>>
+    patch: Partial<
+        Omit<
+            NotificationTarget,
+            'id' | 'createdAt' | 'lastSentAt' | 'lastError' | 'template' | 'signingSecret' | 'auth'
+        >
+    > & {
+        template?: PayloadTemplate | null
+        signingSecret?: string | null
+        auth?: TargetAuth | null
+    }
 ): Promise<void> {
     await invoke('notifications_update_target', { id, patch })
 }
@@ -107,19 +127,90 @@ export async function dispatchNotification(
 /**
  * Sends a test payload directly to the given target (which may be unsaved drawer values).
  * Throws on failure so the UI can surface the error; Rust records the outcome when the target
- * already exists (`id` set).
+ * already exists (`id` set). Secrets missing or redacted here fall back to the saved target's
+ * stored ones.
  */
 export async function sendTestNotification(
-    target: Pick<NotificationTarget, 'provider' | 'url'> & { id?: string; name?: string }
+    target: Pick<
+        NotificationTarget,
+        'provider' | 'url' | 'template' | 'signingSecret' | 'headers' | 'auth'
+    > & {
+        id?: string
+        name?: string
+    }
 ): Promise<void> {
     await invoke('notifications_send_test', {
-        provider: target.provider,
-        url: target.url,
-        targetId: target.id,
-        name: target.name,
+        target: {
+            provider: target.provider,
+            url: target.url,
+            id: target.id,
+            name: target.name,
+            template: target.template,
+            signingSecret: target.signingSecret,
+            headers: target.headers,
+            auth: target.auth,
+        },
     })
 }
 
+/**
+ * The recently dispatched events, newest first, each marked with whether a target subscribed to
+ * `events` with these filters would have received it. Throws on invalid filters.
+ */
+export async function previewTargetFilters(
+    events: NotificationEventId[],
+    filters: TargetFilters
+): Promise<FilterPreviewEvent[]> {
+    return await invoke<FilterPreviewEvent[]>('notifications_preview_filters', { events, filters })
+}
+
+/** Deliveries waiting in the outbox for a retry, oldest first. */
+export async function listPendingDeliveries(): Promise<PendingDelivery[]> {
+    return await invoke<PendingDelivery[]>('notifications_list_outbox')
+}
+
+/** Gives up on one pending delivery, or on all of them without an `id`. */
+export async function purgePendingDeliveries(id?: string): Promise<void> {
+    await invoke('notifications_purge_outbox', { id })
+}
+
+/** Bounds of `dispatchBudgetSeconds` — the same ones Rust enforces on save. */
+export const MIN_DISPATCH_BUDGET_SECONDS = 5
+export const MAX_DISPATCH_BUDGET_SECONDS = 300
+
+export async function getNotificationSettings(): Promise<NotificationSettings> {
+    return await invoke<NotificationSettings>('notifications_get_settings')
+}
+
+/** Throws the validation error when a value is out of bounds. */
+export async function setNotificationSettings(settings: NotificationSettings): Promise<void> {
+    await invoke('notifications_set_settings', { settings })
+}
+
+/** A random `whsec_` secret for request signing — 32 bytes from the webview's CSPRNG. */
+export function generateSigningSecret(): string {
+    const bytes = crypto.getRandomValues(new Uint8Array(32))
+    return `whsec_${Array.from(bytes, (b) => b.toString(16).padStart(2, '0')).join('')}`
+}
+
+/** The body a payload template renders for the test event. Throws the same errors save would. */
+export async function previewPayloadTemplate(template: PayloadTemplate): Promise<string> {
+    return await invoke<string>('notifications_preview_template', { template })
+}
+
+export const DEFAULT_PAYLOAD_TEMPLATE: PayloadTemplate = {
+    method: 'POST',
+    contentType: 'application/json',
+    body: `{
+    "summary": "{{title}}",
+    "details": "{{body}}",
+    "severity": "{{severity}}",
+    "event": "{{id}}",
+    "timestamp": "{{timestamp}}",
+    "durationMs": {{data.durationMs}}
+}`,
+}
+
 export function useNotificationTargets() {
     return useQuery({
         queryKey: ['notifications', 'targets'],
@@ -132,6 +223,23 @@ export function useNotificationTargets() {
     })
 }
 
+export function usePendingDeliveries() {
+    return useQuery({
+        queryKey: ['notifications', 'outbox'],
+        queryFn: listPendingDeliveries,
+        // Retries happen in the main window's worker thread and in the runner — poll, as above.
+        refetchInterval: 10_000,
+        refetchOnWindowFocus: true,
+    })
+}
+
+export function useNotificationSettings() {
+    return useQuery({
+        queryKey: ['notifications', 'settings'],
+        queryFn: getNotificationSettings,
+    })
+}
+
 export function useNotificationsCatalog() {
     return useQuery({
         queryKey: ['notifications', 'catalog'],
@@ -218,6 +326,52 @@ export const NOTIFICATION_PROVIDERS: Record<
         helpUrl: 'https://core.telegram.org/bots#how-do-i-create-a-bot',
         helpLabel: 'How to create a bot & get a token',
     },
+    teams: {
+        label: 'Microsoft Teams',
+        titleLabel: 'Teams Workflow',
+        description: 'Post cards to a Teams channel',
+        urlPlaceholder: 'https://prod-00.westus.logic.azure.com:443/workflows/…',
+        accentClass: 'text-indigo-500',
+        helpUrl:
+            'https://support.microsoft.com/en-us/office/create-incoming-webhooks-with-workflows-for-microsoft-teams-8ae491c7-0394-4861-ba59-055e33f75498',
+        helpLabel: 'How to create a Workflows webhook',
+    },
+    googlechat: {
+        label: 'Google Chat',
+        titleLabel: 'Google Chat Webhook',
+        description: 'Post cards to a Chat space',
+        urlPlaceholder: 'https://chat.googleapis.com/v1/spaces/AAAA/messages?key=…&token=…',
+        accentClass: 'text-green-600',
+        helpUrl: 'https://developers.google.com/workspace/chat/quickstart/webhooks',
+        helpLabel: 'How to create a webhook',
+    },
+    matrix: {
+        label: 'Matrix',
+        titleLabel: 'Matrix Room',
+        description: 'Post to a Matrix room',
+        urlPlaceholder: 'https://matrix.example.org',
+        accentClass: 'text-foreground',
+        helpUrl: 'https://spec.matrix.org/latest/client-server-api/#login',
+        helpLabel: 'How to get an access token',
+    },
+    ntfy: {
+        label: 'ntfy',
+        titleLabel: 'ntfy Topic',
+        description: 'Push to phones via an ntfy topic',
+        urlPlaceholder: 'https://ntfy.sh/my-backups',
+        accentClass: 'text-teal-500',
+        helpUrl: 'https://docs.ntfy.sh/subscribe/phone/',
+        helpLabel: 'How to subscribe to a topic',
+    },
+    gotify: {
+        label: 'Gotify',
+        titleLabel: 'Gotify App',
+        description: 'Push to your Gotify server',
+        urlPlaceholder: 'https://gotify.example.com',
+        accentClass: 'text-sky-600',
+        helpUrl: 'https://gotify.net/docs/pushmsg',
+        helpLabel: 'How to create an app token',
+    },
     webhook: {
         label: 'Webhook',
         titleLabel: 'Webhook',
@@ -225,11 +379,21 @@ export const NOTIFICATION_PROVIDERS: Record<
         urlPlaceholder: 'https://example.com/hooks/rclone',
         accentClass: 'text-primary',
     },
+    email: {
+        label: 'Email',
+        titleLabel: 'Email Alert',
+        description: 'Send mail through your SMTP server',
+        urlPlaceholder: 'smtp.example.com',
+        accentClass: 'text-amber-500',
+    },
 }
 
 // Accepts discord.com, legacy discordapp.com, and the ptb./canary. test clients.
 const RE_DISCORD_WEBHOOK =
     /^https:\/\/(?:(?:ptb|canary)\.)?discord(?:app)?\.com\/api\/webhooks\/\d+\/[\w-]+$/
+// Google Chat incoming webhooks carry the key and token in the query.
+const RE_GOOGLE_CHAT_WEBHOOK =
+    /^https:\/\/chat\.googleapis\.com\/v1\/spaces\/[\w-]+\/messages\?(?=.*\bkey=)(?=.*\btoken=).+$/
 const RE_SLACK_WEBHOOK = /^https:\/\/hooks\.slack\.com\/services\/T[A-Z0-9]+\/B[A-Z0-9]+\/\w+$/
 // Standard Bot API endpoint: the path carries "bot<botid>:<token>"; chat_id rides the query.
 // This is the STORED shape — the form collects only the pure bot URL (below) and the
@@ -262,6 +426,38 @@ export function validateWebhookUrl(provider: NotificationProvider, url: string):
         return null
     }
 
+    if (provider === 'email') {
+        if (!trimmed.startsWith('smtp://')) {
+            return 'Email targets are stored as an smtp:// URL'
+        }
+        return validateEmailSettings(splitEmailUrl(trimmed))
+    }
+
+    if (provider === 'googlechat') {
+        if (!RE_GOOGLE_CHAT_WEBHOOK.test(trimmed)) {
+            return "This doesn't look like a Google Chat webhook URL — expected https://chat.googleapis.com/v1/spaces/…/messages?key=…&token=…"
+        }
+        return null
+    }
+
+    if (provider === 'teams' && !trimmed.startsWith('https://')) {
+        return 'Teams Workflows webhook URLs start with https://'
+    }
+
+    if (provider === 'matrix') {
+        const { homeserver, roomId, token } = splitMatrixUrl(trimmed)
+        return (
+            validatePushServerUrl(provider, homeserver) ||
+            validateMatrixRoomId(roomId) ||
+            validatePushToken(provider, token)
+        )
+    }
+
+    if (provider === 'ntfy' || provider === 'gotify') {
+        const { baseUrl, token } = splitTokenUrl(provider, trimmed)
+        return validatePushServerUrl(provider, baseUrl) || validatePushToken(provider, token)
+    }
+
     if (provider === 'telegram') {
         if (!RE_TELEGRAM_SEND_MESSAGE.test(trimmed)) {
             return "This doesn't look like a Telegram Bot API URL — expected https://api.telegram.org/bot<token>/sendMessage"
@@ -351,6 +547,242 @@ export function validateTelegramChatId(chatId: string): string | null {
     return null
 }
 
+/** The providers whose form pairs a server URL with an access token. */
+export type PushProvider = 'ntfy' | 'gotify' | 'matrix'
+
+// ntfy topic names: letters, digits, - and _ (max 64).
+const RE_NTFY_TOPIC = /^[\w-]{1,64}$/
+const RE_GOTIFY_MESSAGE_SUFFIX = /\/message$/
+const RE_WHITESPACE = /\s/
+
+/**
+ * ntfy and Gotify keep their access token in a `token` query param of the stored URL (Rust
+ * moves it into a header when sending). The form edits URL and token separately; for Gotify
+ * the form takes the server URL and the /message endpoint is ours to add.
+ */
+export function buildTokenUrl(provider: 'ntfy' | 'gotify', baseUrl: string, token: string) {
+    const parsed = new URL(baseUrl.trim())
+    parsed.search = ''
+    if (provider === 'gotify') {
+        const path = parsed.pathname
+            .replace(RE_TRAILING_SLASHES, '')
+            .replace(RE_GOTIFY_MESSAGE_SUFFIX, '')
+        parsed.pathname = `${path}/message`
+    }
+    if (token.trim()) {
+        parsed.searchParams.set('token', token.trim())
+    }
+    return parsed.toString()
+}
+
+/** Inverse of buildTokenUrl, for seeding the edit form from a stored URL. */
+export function splitTokenUrl(
+    provider: 'ntfy' | 'gotify',
+    url: string
+): { baseUrl: string; token: string } {
+    try {
+        const parsed = new URL(url)
+        const token = parsed.searchParams.get('token') ?? ''
+        parsed.search = ''
+        let baseUrl = parsed.toString()
+        if (provider === 'gotify') {
+            baseUrl = baseUrl.replace(RE_GOTIFY_MESSAGE_SUFFIX, '')
+        }
+        return { baseUrl, token }
+    } catch {
+        return { baseUrl: url, token: '' }
+    }
+}
+
+/** Validates the drawer's ntfy topic URL / Gotify server URL / Matrix homeserver field. */
+export function validatePushServerUrl(provider: PushProvider, url: string): string | null {
+    const trimmed = url.trim()
+    if (!trimmed) {
+        return provider === 'ntfy' ? 'A topic URL is required' : 'A server URL is required'
+    }
+    let parsed: URL
+    try {
+        parsed = new URL(trimmed)
+    } catch {
+        return 'This is not a valid URL'
+    }
+    if (parsed.protocol !== 'http:' && parsed.protocol !== 'https:') {
+        return 'The URL must use http:// or https://'
+    }
+    if (parsed.search) {
+        return "Don't include query parameters — enter the token in its own field below"
+    }
+    if (provider === 'ntfy') {
+        const topic = parsed.pathname.split('/').filter(Boolean).pop() ?? ''
+        if (!RE_NTFY_TOPIC.test(topic)) {
+            return 'Expected a topic URL like https://ntfy.sh/my-backups'
+        }
+    }
+    return null
+}
+
+/** ntfy topics may be public (no token); Gotify and Matrix always need a token. */
+export function validatePushToken(provider: PushProvider, token: string): string | null {
+    if (provider === 'gotify' && !token.trim()) {
+        return 'An app token is required'
+    }
+    if (provider === 'matrix' && !token.trim()) {
+        return 'An access token is required'
+    }
+    if (RE_WHITESPACE.test(token.trim())) {
+        return 'The token must not contain spaces'
+    }
+    return null
+}
+
+// Room ids look like !opaque:server.name — aliases (#room:server) can't be posted to.
+const RE_MATRIX_ROOM_ID = /^![^\s:]+:[^\s]+$/
+
+/**
+ * Matrix keeps room id and access token in the stored URL's query
+ * (https://homeserver?room=!id:server&token=…); Rust builds the client-server API path and
+ * moves the token into the Authorization header.
+ */
+export function buildMatrixUrl(homeserver: string, roomId: string, token: string): string {
+    const parsed = new URL(homeserver.trim())
+    parsed.search = ''
+    parsed.searchParams.set('room', roomId.trim())
+    parsed.searchParams.set('token', token.trim())
+    return parsed.toString()
+}
+
+/** Inverse of buildMatrixUrl, for seeding the edit form from a stored URL. */
+export function splitMatrixUrl(url: string): {
+    homeserver: string
+    roomId: string
+    token: string
+} {
+    try {
+        const parsed = new URL(url)
+        const roomId = parsed.searchParams.get('room') ?? ''
+        const token = parsed.searchParams.get('token') ?? ''
+        parsed.search = ''
+        return { homeserver: parsed.toString(), roomId, token }
+    } catch {
+        return { homeserver: url, roomId: '', token: '' }
+    }
+}
+
+export function validateMatrixRoomId(roomId: string): string | null {
+    const trimmed = roomId.trim()
+    if (!trimmed) {
+        return 'A room ID is required'
+    }
+    if (!RE_MATRIX_ROOM_ID.test(trimmed)) {
+        return 'Enter the internal room ID (!abc123:example.org) from the room settings'
+    }
+    return null
+}
+
+export type EmailSecurity = 'starttls' | 'tls' | 'none'
+
+export interface EmailSettings {
+    host: string
+    // Empty for the security mode's standard port (587, 465 or 25).
+    port: string
+    security: EmailSecurity
+    username: string
+    password: string
+    from: string
+    // Comma-separated recipients.
+    to: string
+}
+
+export const EMPTY_EMAIL_SETTINGS: EmailSettings = {
+    host: '',
+    port: '',
+    security: 'starttls',
+    username: '',
+    password: '',
+    from: '',
+    to: '',
+}
+
+const RE_SMTP_HOST = /^[\w.-]+$/
+// A bare address or `Display Name <address>`.
+const RE_MAILBOX = /^(?:[^<>]*<[^\s@<>]+@[^\s@<>]+>|[^\s@<>]+@[^\s@<>]+)$/
+
+/**
+ * Like Telegram's chat id, the SMTP settings are merged into the one stored URL
+ * (smtp://host:port?security=…&from=…&to=…) — parsed back by
+ * src-tauri/src/notifications/email.rs, which does the sending. The login goes in the
+ * target's auth instead (emailAuth), where Rust seals the password.
+ */
+export function buildEmailUrl(settings: EmailSettings): string {
+    const port = settings.port.trim() ? `:${settings.port.trim()}` : ''
+    const query = new URLSearchParams({
+        security: settings.security,
+        from: settings.from.trim(),
+        to: splitRecipients(settings.to).join(','),
+    })
+    return `smtp://${settings.host.trim()}${port}?${query}`
+}
+
+/** The login as the target's basic auth; an empty password keeps the stored one. */
+export function emailAuth(settings: EmailSettings): TargetAuth | undefined {
+    const username = settings.username.trim()
+    if (!username) {
+        return undefined
+    }
+    return { mode: 'basic', username, secret: settings.password || undefined }
+}
+
+/**
+ * Inverse of buildEmailUrl (and emailAuth), for seeding the edit form from a stored target. The
+ * stored password stays in Rust.
+ */
+export function splitEmailUrl(url: string, auth?: TargetAuth): EmailSettings {
+    try {
+        const parsed = new URL(url)
+        const security = parsed.searchParams.get('security')
+        return {
+            host: parsed.hostname,
+            port: parsed.port,
+            security: security === 'tls' || security === 'none' ? security : 'starttls',
+            username: auth?.username ?? '',
+            password: '',
+            from: parsed.searchParams.get('from') ?? '',
+            to: splitRecipients(parsed.searchParams.get('to') ?? '').join(', '),
+        }
+    } catch {
+        return EMPTY_EMAIL_SETTINGS
+    }
+}
+
+function splitRecipients(to: string): string[] {
+    return to
+        .split(',')
+        .map((recipient) => recipient.trim())
+        .filter(Boolean)
+}
+
+export function validateEmailSettings(settings: EmailSettings): string | null {
+    if (!RE_SMTP_HOST.test(settings.host.trim())) {
+        return 'Enter the SMTP server host name, e.g. smtp.example.com'
+    }
+    const port = settings.port.trim()
+    if (port && !(Number.isInteger(Number(port)) && Number(port) >= 1 && Number(port) <= 65535)) {
+        return 'The port must be a number between 1 and 65535'
+    }
+    if (!RE_MAILBOX.test(settings.from.trim())) {
+        return 'Enter the sender address, e.g. alerts@example.com'
+    }
+    const recipients = splitRecipients(settings.to)
+    if (recipients.length === 0) {
+        return 'Enter at least one recipient'
+    }
+    const invalid = recipients.find((recipient) => !RE_MAILBOX.test(recipient))
+    if (invalid) {
+        return `"${invalid}" is not an email address`
+    }
+    return null
+}
+
 const MAX_MASK_SEGMENT_LENGTH = 10
 
 // Webhook URLs are credentials (Telegram's first path segment IS the bot token) — the list view
@@ -358,6 +790,10 @@ const MAX_MASK_SEGMENT_LENGTH = 10
 export function maskWebhookUrl(url: string): string {
     try {
         const parsed = new URL(url)
+        // Email targets: the recipients say more than the server.
+        if (parsed.protocol === 'smtp:') {
+            return parsed.searchParams.get('to') ?? parsed.hostname
+        }
         const segments = parsed.pathname.split('/').filter(Boolean)
         if (segments.length === 0) {
             return parsed.host
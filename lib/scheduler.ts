@@ -30,14 +30,42 @@ export interface SchedulerJobSpec {
     maxRunSeconds: number
     verboseLogging: boolean
     runMode: 'system' | 'user'
+    bisyncResync: 'never' | 'first-run' | 'always'
+    env: Record<string, string>
+    secretEnv: string[]
+    daemonFlags: string[]
+    maxParallel: number
+    continueOnError: boolean
+    logRetention: number
     requests: RcRequest[]
 }
 
+export type SchedulerFailureKind =
+    | 'permissionDenied'
+    | 'quotaExceeded'
+    | 'rateLimited'
+    | 'authExpired'
+    | 'unauthorized'
+    | 'notFound'
+    | 'other'
+
+/** Mirrors scheduler/daemonlog.rs — the failed run's daemon-log ERROR lines, classified. */
+export interface SchedulerFailureSummary {
+    total: number
+    categories: {
+        kind: SchedulerFailureKind
+        count: number
+        examples: string[]
+    }[]
+}
+
 export interface SchedulerTaskStatus {
     taskId: string
     installed: boolean
     enabled: boolean
     running: boolean
+    /** The state last chosen here or with the headless enable/disable (the job file's). */
+    intendedEnabled?: boolean
     lastFinished?: {
         runId: string
         ts: string
@@ -45,7 +73,11 @@ export interface SchedulerTaskStatus {
         error?: string
         durationMs: number
         jobids?: number[]
-        stats?: { bytes?: number; transfers?: number; errors?: number }
+        stats?: { bytes?: number; transfers?: number; errors?: number; conflicts?: number }
+        failureSummary?: SchedulerFailureSummary
+        /** The run failed on an expired/revoked OAuth token — offer a reconnect. */
+        needsReauth?: boolean
+        reauthRemote?: string
         /** Synthesized: the run left a started event but no finished one (crash/power loss). */
         interrupted?: boolean
     }
@@ -64,7 +96,10 @@ export type SchedulerHistoryLine =
           error?: string
           durationMs: number
           jobids?: number[]
-          stats?: { bytes?: number; transfers?: number; errors?: number }
+          stats?: { bytes?: number; transfers?: number; errors?: number; conflicts?: number }
+          failureSummary?: SchedulerFailureSummary
+          needsReauth?: boolean
+          reauthRemote?: string
       }
     | { event: 'skipped'; ts: string; reason: string }
 
@@ -113,10 +148,15 @@ export interface CronValidation {
     /** Next local fire times (RFC3339), computed by the same Rust matcher the runner uses —
      * the only preview source that can't disagree with what the OS schedule will do. */
     nextRuns: string[]
+    /** When the schedule fires, in words, in the system language (English when cronconv has no
+     * wording for it). */
+    description?: string
 }
 
 export async function schedulerValidateCron(cron: string) {
-    return invoke<CronValidation>('scheduler_validate_cron', { cron })
+    // The schedule is what non-technical users must read, so it follows their language even
+    // where the rest of the UI is English.
+    return invoke<CronValidation>('scheduler_validate_cron', { cron, language: navigator.language })
 }
 
 export async function schedulerStatus(hostId: string) {
@@ -138,6 +178,35 @@ export async function schedulerReadLog(taskId: string, which: 'runner' | 'daemon
     })
 }
 
+/** Mirrors history::LogRecord — one line of the runner log, tagged with the run it belongs to. */
+export interface SchedulerLogRecord {
+    ts: string
+    runId?: string
+    level: 'info' | 'warn' | 'error'
+    phase: 'setup' | 'daemon' | 'request' | 'webhook'
+    message: string
+}
+
+/** The runner-log records of one run, gathered from the live log and its gzipped archives. */
+export async function schedulerReadRunLog(taskId: string, runId: string) {
+    return invoke<SchedulerLogRecord[]>('scheduler_read_run_log', { taskId, runId })
+}
+
+/** Parses `KEY=value` lines (blank lines skipped) — the drawer's environment fields. */
+export function parseEnvLines(text: string): Record<string, string> {
+    const env: Record<string, string> = {}
+    for (const line of text.split('\n')) {
+        const trimmed = line.trim()
+        if (!trimmed) continue
+        const separator = trimmed.indexOf('=')
+        if (separator < 1) {
+            throw new Error(`"${trimmed}" is not a KEY=value line`)
+        }
+        env[trimmed.slice(0, separator).trim()] = trimmed.slice(separator + 1)
+    }
+    return env
+}
+
 function buildJobSpec(task: ScheduledTask): SchedulerJobSpec {
     return {
         schemaVersion: 1,
@@ -151,18 +220,56 @@ function buildJobSpec(task: ScheduledTask): SchedulerJobSpec {
         maxRunSeconds: clampMaxRunHours(task.maxRunHours) * 3600,
         verboseLogging: task.verboseLogging ?? false,
         runMode: task.runMode ?? 'user',
+        bisyncResync: task.bisyncResync ?? 'never',
+        env: task.env ?? {},
+        secretEnv: task.secretEnvKeys ?? [],
+        daemonFlags: task.daemonFlags ?? [],
+        maxParallel: task.maxParallel ?? 1,
+        continueOnError: task.continueOnError ?? false,
+        logRetention: task.logRetention ?? 5,
         // Pre-serialized here, at save time, by the exact same builders the live start* path
         // uses — the runner just POSTs them. Throws when the args can't serialize.
         requests: buildTaskRequests(task),
     }
 }
 
-async function registerTask(task: ScheduledTask): Promise<void> {
+// When this window last wrote each task's enabled state to its job file — a status read that
+// started earlier predates the write and must not be adopted over it.
+const enabledWrittenAt = new Map<string, number>()
+
+/**
+ * Adopts the enabled state a task's job file keeps when it differs from the store's — it was
+ * chosen with the headless `enable`/`disable` — so the next registration doesn't re-arm a
+ * headless disable. `requestedAt`: when the status read began.
+ */
+export function adoptIntendedEnabled(statuses: SchedulerTaskStatus[], requestedAt: number) {
+    const { scheduledTasks, updateScheduledTask } = useHostStore.getState()
+    for (const status of statuses) {
+        const task = scheduledTasks.find((t) => t.id === status.taskId)
+        if (
+            !task ||
+            status.intendedEnabled === undefined ||
+            status.intendedEnabled === task.isEnabled ||
+            (enabledWrittenAt.get(task.id) ?? 0) >= requestedAt
+        ) {
+            continue
+        }
+        console.log('[scheduler] adopting enabled state from the job file', task.id)
+        updateScheduledTask(task.id, { isEnabled: status.intendedEnabled })
+    }
+}
+
+/** `secrets`: new values for the task's secret env vars — the ones left out keep theirs. */
+async function registerTask(
+    task: ScheduledTask,
+    secrets?: Record<string, string>
+): Promise<void> {
     const spec = buildJobSpec(task)
     // One command: the artifact is installed directly in the target enabled state. A separate
     // set_enabled step used to leave disabled tasks briefly armed (and, when it failed, running
     // against the user's intent — or flagged as unregistered although active).
-    await invoke('scheduler_register', { spec, enabled: task.isEnabled })
+    enabledWrittenAt.set(task.id, Date.now())
+    await invoke('scheduler_register', { spec, enabled: task.isEnabled, secrets })
 }
 
 function isCurrentHostLocal() {
@@ -198,6 +305,7 @@ export async function createScheduledTask(input: {
     binaryPath?: string
     /** Defaults to 'user' (only runs while logged in) when omitted. */
     runMode?: 'system' | 'user'
+    env?: Record<string, string>
 }): Promise<string> {
     assertLocalHost()
     await assertSupported()
@@ -225,6 +333,7 @@ export async function createScheduledTask(input: {
         configId,
         binaryPath: input.binaryPath ?? 'app-default',
         runMode: input.runMode ?? 'user',
+        ...(input.env && Object.keys(input.env).length > 0 ? { env: input.env } : {}),
     } as Omit<ScheduledTask, 'id'>
 
     // Serialization must succeed before anything persists. (Callers guarantee the operation/args
@@ -257,7 +366,8 @@ export async function createScheduledTask(input: {
  */
 export async function updateScheduledTask(
     id: string,
-    patch: Partial<ScheduledTask>
+    patch: Partial<ScheduledTask>,
+    secrets?: Record<string, string>
 ): Promise<void> {
     if (!isCurrentHostLocal()) {
         useHostStore.getState().updateScheduledTask(id, patch)
@@ -281,7 +391,7 @@ export async function updateScheduledTask(
     }
 
     try {
-        await registerTask(merged)
+        await registerTask(merged, secrets)
     } catch (error) {
         const registrationError = error instanceof Error ? error.message : String(error)
         useHostStore.getState().updateScheduledTask(id, { registrationError })
@@ -332,6 +442,7 @@ export async function setScheduledTaskEnabled(id: string, enabled: boolean): Pro
     // success, so always ask it — a real disable failure must surface rather than leave the
     // task firing while the UI says paused.
     if (!enabled && task.registrationError) {
+        enabledWrittenAt.set(id, Date.now())
         await invoke('scheduler_set_enabled', { taskId: id, enabled: false })
         useHostStore.getState().updateScheduledTask(id, { isEnabled: false })
         return
@@ -339,10 +450,134 @@ export async function setScheduledTaskEnabled(id: string, enabled: boolean): Pro
 
     // OS first, store second — a failed OS call must not leave the UI claiming a state the
     // scheduler doesn't have.
+    enabledWrittenAt.set(id, Date.now())
     await invoke('scheduler_set_enabled', { taskId: id, enabled })
     useHostStore.getState().updateScheduledTask(id, { isEnabled: enabled })
 }
 
+export interface SchedulerExportReport {
+    tasks: number
+    notificationTargets: number
+    /** Tasks left out because they were never registered on this machine. */
+    skipped: string[]
+}
+
+export interface SchedulerImportReport {
+    tasks: {
+        taskId: string
+        name: string
+        imported: boolean
+        /** What was changed to fit this machine. */
+        adaptations: string[]
+        error?: string
+        definition?: ScheduledTask
+    }[]
+    notificationTargets: { name: string; imported: boolean; error?: string }[]
+}
+
+/** Writes the local tasks (and the webhooks listening to schedule events) to a bundle file. */
+export async function exportScheduledTasks(path: string): Promise<SchedulerExportReport> {
+    assertLocalHost()
+    const definitions = useHostStore.getState().scheduledTasks
+    return invoke<SchedulerExportReport>('scheduler_export', { path, definitions })
+}
+
+/**
+ * Registers the tasks of a bundle written by exportScheduledTasks — adapted to this machine's
+ * binaries and configs by the Rust side — and adds the imported ones to the store.
+ */
+export async function importScheduledTasks(path: string): Promise<SchedulerImportReport> {
+    assertLocalHost()
+    await assertSupported()
+
+    const hostState = useHostStore.getState()
+    const report = await invoke<SchedulerImportReport>('scheduler_import', {
+        path,
+        fallbackConfigId: hostState.activeConfigId,
+        knownTaskIds: hostState.scheduledTasks.map((task) => task.id),
+    })
+    for (const task of report.tasks) {
+        if (task.imported && task.definition) {
+            const { id, ...definition } = task.definition
+            useHostStore.getState().addScheduledTask(definition, id)
+        }
+    }
+    return report
+}
+
+/**
+ * Writes a standalone equivalent of a registered task into `dir`: a shell script running the
+ * rclone CLI, or a systemd service/timer pair with its env file. Returns the written paths.
+ */
+export async function exportTaskArtifact(
+    taskId: string,
+    kind: 'script' | 'systemd',
+    dir: string
+): Promise<string[]> {
+    assertLocalHost()
+    return invoke<string[]>('scheduler_export_artifact', { taskId, kind, dir })
+}
+
+/** Mirrors scheduler/adopt.rs — rclone lines found in the user's crontab. */
+export interface CrontabScan {
+    candidates: {
+        line: string
+        cron: string
+        operation: 'sync' | 'copy' | 'move' | 'bisync'
+        name: string
+        args: ScheduledTask['args']
+        env: Record<string, string>
+        /** What was not carried over. */
+        notes: string[]
+    }[]
+    rejected: { line: string; reason: string }[]
+}
+
+export async function scanCrontab() {
+    return invoke<CrontabScan>('scheduler_scan_crontab')
+}
+
+/**
+ * Creates a task for each candidate, then comments out the crontab lines of the ones that were
+ * registered. The tasks run in 'system' mode: cron ran the originals whether or not anyone was
+ * logged in. Returns the per-candidate errors (empty when all were adopted).
+ */
+export async function adoptCrontabEntries(
+    candidates: CrontabScan['candidates']
+): Promise<{ line: string; error: string }[]> {
+    const adopted: string[] = []
+    const failed: { line: string; error: string }[] = []
+    for (const candidate of candidates) {
+        const existing = new Set(useHostStore.getState().scheduledTasks.map((task) => task.id))
+        try {
+            await createScheduledTask({
+                name: candidate.name,
+                operation: candidate.operation,
+                cron: candidate.cron,
+                args: candidate.args,
+                runMode: 'system',
+                env: candidate.env,
+            })
+            adopted.push(candidate.line)
+        } catch (error) {
+            // A task kept after a failed registration would duplicate the still-active line.
+            for (const task of useHostStore.getState().scheduledTasks) {
+                if (!existing.has(task.id)) {
+                    await removeScheduledTask(task.id)
+                }
+            }
+            failed.push({
+                line: candidate.line,
+                error: error instanceof Error ? error.message : String(error),
+            })
+        }
+    }
+    if (adopted.length > 0) {
+        await invoke<number>('scheduler_retire_crontab_lines', { lines: adopted })
+    }
+    return failed
+}
+
 /**
  * Startup/host-switch reconciliation — idempotent, runs on EVERY start. Re-registers every
  * local task (heals exe-path drift, deleted OS artifacts, and performs the one-time migration
@@ -362,6 +597,15 @@ export async function reconcile(): Promise<void> {
         return
     }
 
+    // A headless enable/disable since the last start: adopt it before re-registering, which
+    // would otherwise restore the store's state.
+    try {
+        const requestedAt = Date.now()
+        adoptIntendedEnabled(await schedulerStatus(LOCAL_HOST_ID), requestedAt)
+    } catch (error) {
+        console.error('[scheduler] reading the enabled states failed', error)
+    }
+
     const taskIds = useHostStore.getState().scheduledTasks.map((task) => task.id)
 
     for (const id of taskIds) {
@@ -420,6 +664,60 @@ export async function reconcile(): Promise<void> {
     }
 }
 
+/** Mirrors scheduler/drift.rs — job files whose OS registration drifted from their definition. */
+export interface SchedulerDriftReport {
+    checked: number
+    drifted: { taskId: string; name: string; problems: string[] }[]
+}
+
+export interface SchedulerRepairReport {
+    repaired: string[]
+    failed: { taskId: string; error: string }[]
+}
+
+/** The store's intended enabled state per task — what the OS registrations should match. */
+function intendedEnabled() {
+    return Object.fromEntries(
+        useHostStore.getState().scheduledTasks.map((task) => [task.id, task.isEnabled])
+    )
+}
+
+/** Compares every registered task's OS artifact with what registering it would write today. */
+export async function schedulerReconcileReport(): Promise<SchedulerDriftReport> {
+    assertLocalHost()
+    return invoke<SchedulerDriftReport>('scheduler_reconcile_report', {
+        enabled: intendedEnabled(),
+    })
+}
+
+/** Re-registers every task the drift report lists. */
+export async function schedulerReconcileApply(): Promise<SchedulerRepairReport> {
+    assertLocalHost()
+    return invoke<SchedulerRepairReport>('scheduler_reconcile_apply', {
+        enabled: intendedEnabled(),
+    })
+}
+
+/** Mirrors scheduler/doctor.rs — a setup checklist per task, nothing transferred. */
+export interface SchedulerDoctorReport {
+    tasks: {
+        taskId: string
+        name: string
+        checks: {
+            id: string
+            label: string
+            status: 'pass' | 'warn' | 'fail' | 'skip'
+            detail: string
+        }[]
+    }[]
+}
+
+/** Dry-runs every task's setup: binary, config, secrets, session, daemon, remotes, targets. */
+export async function schedulerDoctor(): Promise<SchedulerDoctorReport> {
+    assertLocalHost()
+    return invoke<SchedulerDoctorReport>('scheduler_doctor')
+}
+
 let initialized = false
 
 /**
@@ -28,6 +28,17 @@ pub fn run_scheduled_task(
     scheduler::runner::run(task_id, host_id, forced, data_dir, local_data_dir)
 }
 
+/// Whether `name` is one of the headless task subcommands (`list-tasks`, `task-status`, …).
+pub fn is_cli_command(name: &str) -> bool {
+    scheduler::cli::COMMANDS.contains(&name)
+}
+
+/// Entry point for the headless task subcommands (see main.rs); `args` excludes the program
+/// name. Returns the process exit code.
+pub fn run_cli(args: &[String]) -> i32 {
+    scheduler::cli::run(args)
+}
+
 use shortcut::{
     ensure_toolbar_window, set_toolbar_shortcut, show_toolbar_window, DEFAULT_TOOLBAR_SHORTCUT,
 };
@@ -910,16 +921,31 @@ pub fn run() {
             scheduler::scheduler_run_now,
             scheduler::scheduler_status,
             scheduler::scheduler_read_history,
+            scheduler::scheduler_export,
+            scheduler::scheduler_import,
+            scheduler::scheduler_export_artifact,
+            scheduler::scheduler_scan_crontab,
+            scheduler::scheduler_retire_crontab_lines,
             scheduler::scheduler_read_log,
+            scheduler::scheduler_read_run_log,
             scheduler::scheduler_unregister_all,
             scheduler::scheduler_sweep_orphans,
+            scheduler::scheduler_reconcile_report,
+            scheduler::scheduler_reconcile_apply,
+            scheduler::scheduler_doctor,
             notifications::notifications_catalog,
             notifications::notifications_list_targets,
             notifications::notifications_add_target,
             notifications::notifications_update_target,
             notifications::notifications_remove_target,
             notifications::notifications_dispatch,
-            notifications::notifications_send_test
+            notifications::notifications_send_test,
+            notifications::notifications_preview_template,
+            notifications::notifications_preview_filters,
+            notifications::notifications_list_outbox,
+            notifications::notifications_purge_outbox,
+            notifications::notifications_get_settings,
+            notifications::notifications_set_settings
         ])
         .setup(|app| {
             #[cfg(target_os = "linux")]
@@ -956,6 +982,8 @@ pub fn run() {
             // once here (before any webview) so it can never race a live download.
             zookeeper::sweep_versions_tmp(app.handle());
 
+            notifications::start_outbox_worker(app.handle());
+
             if let Err(err) = ensure_toolbar_window(&app.handle()) {
                 log::warn!("failed to prepare toolbar window: {}", err);
             }
@@ -35,6 +35,13 @@ fn main() {
         ));
     }
 
+    // Headless inspection/control (`list-tasks`, `task-status <id>`, `run-now <id>`, …) for
+    // machines reached over SSH. Same rule as run-task: never start the GUI.
+    if args.len() >= 2 && app_lib::is_cli_command(&args[1]) {
+        let _ = fix_path_env::fix();
+        std::process::exit(app_lib::run_cli(&args[1..]));
+    }
+
     #[cfg(target_os = "linux")]
     {
         let is_dri_present = std::path::Path::new("/dev/dri").exists();
@@ -37,7 +37,7 @@ pub const CATEGORIES: [CategoryMeta; 3] = [
     },
 ];
 
-pub const EVENTS: [EventMeta; 10] = [
+pub const EVENTS: [EventMeta; 11] = [
     EventMeta {
         id: "job.started",
         label: "Transfer started",
@@ -80,6 +80,13 @@ pub const EVENTS: [EventMeta; 10] = [
         category: "schedules",
         severity: "error",
     },
+    EventMeta {
+        id: "schedule.reauth-required",
+        label: "Scheduled task needs sign-in",
+        description: "A scheduled task failed because a remote's sign-in expired or was revoked",
+        category: "schedules",
+        severity: "error",
+    },
     EventMeta {
         id: "mount.failed",
         label: "Mount failed",
@@ -129,7 +136,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn catalog_has_the_ten_wire_stable_ids() {
+    fn catalog_has_the_wire_stable_ids() {
         let ids: Vec<&str> = EVENTS.iter().map(|e| e.id).collect();
         assert_eq!(
             ids,
@@ -140,6 +147,7 @@ mod tests {
                 "schedule.started",
                 "schedule.completed",
                 "schedule.failed",
+                "schedule.reauth-required",
                 "mount.failed",
                 "rclone.crashed",
                 "rclone.update-available",
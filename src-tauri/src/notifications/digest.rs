@@ -0,0 +1,602 @@
+//! Per-target delivery modes. `immediate` (the default) sends every event as it fires.
+//! `deduplicate` sends the first of a run of the same event for the same task, holds the repeats
+//! back for a window, then sends one "(x12)" follow-up for them — a task failing every 15
+//! minutes posts once an hour instead of four times. `digest` sends nothing as events fire and
+//! batches them into one summary message every hour (on the hour) or every day (local midnight).
+//!
+//! A task's success closes its open failure windows, so failing again after a recovery is news
+//! again rather than a repeat.
+//!
+//! What's held back lives in `<app_data>/notifications/digest.json`, under the targets store
+//! lock, so the GUI and the headless runner hold back against the same windows. Both release
+//! what's due right before they flush the outbox — the GUI every minute, the runner after each
+//! run — by moving it into the outbox under the same lock: nothing released is lost to a crash,
+//! and the outbox's claims, retries and backoff apply to it like to any other delivery.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use super::catalog;
+use super::filters::{self, RecentEvent};
+use super::outbox;
+use super::targets::{self, NotificationTarget};
+use super::webhooks::Delivery;
+use crate::scheduler::history;
+use crate::scheduler::storeread::AppDirs;
+
+const DEFAULT_WINDOW_MINUTES: u32 = 60;
+const MAX_WINDOW_MINUTES: u32 = 24 * 60;
+/// A digest lists only the newest this many; its count still covers them all.
+const DIGEST_LIST_LIMIT: usize = 50;
+const HOUR_MS: u64 = 60 * 60_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryMode {
+    #[default]
+    Immediate,
+    Deduplicate,
+    Digest,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestInterval {
+    #[default]
+    Hourly,
+    Daily,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetDelivery {
+    #[serde(default)]
+    pub mode: DeliveryMode,
+    /// Deduplicate only: how long repeats are held back. `DEFAULT_WINDOW_MINUTES` when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_minutes: Option<u32>,
+    /// Digest only.
+    #[serde(default)]
+    pub interval: DigestInterval,
+}
+
+impl TargetDelivery {
+    pub fn is_immediate(&self) -> bool {
+        self.mode == DeliveryMode::Immediate
+    }
+
+    fn window_ms(&self) -> u64 {
+        self.window_minutes.unwrap_or(DEFAULT_WINDOW_MINUTES) as u64 * 60_000
+    }
+}
+
+/// Save-time check.
+pub fn validate(delivery: &TargetDelivery) -> Result<(), String> {
+    if delivery
+        .window_minutes
+        .is_some_and(|minutes| minutes == 0 || minutes > MAX_WINDOW_MINUTES)
+    {
+        return Err("The deduplication window must be between 1 minute and 24 hours.".to_string());
+    }
+    Ok(())
+}
+
+/// A deduplicate target's window for one event and task: repeats until `ends_at` are counted,
+/// not sent.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DedupeWindow {
+    target_id: String,
+    event_id: String,
+    /// `task_key`.
+    task: String,
+    ends_at: u64,
+    repeats: u32,
+    /// The newest repeat — the follow-up renders it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    latest: Option<RecentEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingDigest {
+    target_id: String,
+    due_at: u64,
+    /// Oldest first, at most `DIGEST_LIST_LIMIT`.
+    events: Vec<RecentEvent>,
+    /// Events dropped from `events` to keep it within the limit.
+    #[serde(default)]
+    omitted: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DigestFile {
+    version: u32,
+    #[serde(default)]
+    windows: Vec<DedupeWindow>,
+    #[serde(default)]
+    digests: Vec<PendingDigest>,
+}
+
+fn digest_path(dirs: &AppDirs) -> PathBuf {
+    dirs.app_data.join("notifications").join("digest.json")
+}
+
+fn load_locked(dirs: &AppDirs) -> Result<DigestFile, String> {
+    let path = digest_path(dirs);
+    match std::fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map_err(|e| format!("invalid digest file {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DigestFile {
+            version: 1,
+            windows: Vec::new(),
+            digests: Vec::new(),
+        }),
+        Err(e) => Err(format!("failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Nothing held back is no file, like the outbox.
+fn write_locked(dirs: &AppDirs, state: &DigestFile) -> Result<(), String> {
+    let path = digest_path(dirs);
+    if state.windows.is_empty() && state.digests.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("failed to clear the digest file: {}", e))
+            }
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create notifications dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("failed to serialize the digest file: {}", e))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("failed to write the digest file: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("failed to save the digest file: {}", e))
+}
+
+/// Which task an event is about: a schedule by its id, a manual job or mount by what it touches.
+fn task_key(data: &Value) -> String {
+    match data.get("scheduleId").and_then(Value::as_str) {
+        Some(id) => id.to_string(),
+        None => ["operation", "sources", "destination", "source", "remote"]
+            .iter()
+            .map(|key| data.get(*key).map(Value::to_string).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("|"),
+    }
+}
+
+/// The delivery id of a released message, derived from what it releases — a window by its end,
+/// a digest by its due time — so the same release always goes out under the same id.
+fn release_id(target_id: &str, released: &str, at: u64) -> String {
+    let hash = Sha256::digest(format!("{}|{}|{}", target_id, released, at));
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    uuid::Builder::from_random_bytes(bytes)
+        .into_uuid()
+        .to_string()
+}
+
+/// The next hour, or the next local midnight.
+fn next_boundary(interval: DigestInterval, now: u64) -> u64 {
+    match interval {
+        DigestInterval::Hourly => (now / HOUR_MS + 1) * HOUR_MS,
+        DigestInterval::Daily => chrono::DateTime::from_timestamp_millis(now as i64)
+            .map(|utc| utc.with_timezone(&chrono::Local).date_naive())
+            .and_then(|today| today.succ_opt())
+            .and_then(|tomorrow| tomorrow.and_hms_opt(0, 0, 0))
+            .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+            .map(|midnight| midnight.timestamp_millis() as u64)
+            .unwrap_or(now + 24 * HOUR_MS),
+    }
+}
+
+fn held(delivery: &Delivery) -> RecentEvent {
+    RecentEvent {
+        event_id: delivery.event.id.to_string(),
+        title: delivery.title.clone(),
+        body: delivery.body.clone(),
+        timestamp: delivery.timestamp.clone(),
+        data: delivery.data.clone(),
+    }
+}
+
+/// Makes `delivery` stand for `count` occurrences of its event.
+fn mark_repeated(delivery: &mut Delivery, count: u32) {
+    delivery.title = format!("{} (x{})", delivery.title, count);
+    delivery.body = format!(
+        "{}\n\nStill happening: {} times since the last notification.",
+        delivery.body, count
+    );
+    if let Some(data) = delivery.data.as_object_mut() {
+        data.insert("repeatCount".to_string(), json!(count));
+    }
+}
+
+/// The "(x12)" message for the repeats a window held back, if it held any.
+fn follow_up(target: &NotificationTarget, window: &mut DedupeWindow) -> Option<Delivery> {
+    let latest = window.latest.take().filter(|_| window.repeats > 0)?;
+    let event = catalog::find(&latest.event_id)?;
+    let released = format!("{}|{}", window.event_id, window.task);
+    let mut delivery = Delivery {
+        target: target.clone(),
+        event,
+        title: latest.title,
+        body: latest.body,
+        data: latest.data,
+        timestamp: latest.timestamp,
+        id: release_id(&target.id, &released, window.ends_at),
+    };
+    mark_repeated(&mut delivery, window.repeats);
+    Some(delivery)
+}
+
+/// Splits a dispatch's deliveries: what goes out now is returned, the rest is held back. If
+/// the held-back state can't be read or saved everything goes out — better a repeat than a
+/// lost alert.
+pub(super) fn hold(dirs: &AppDirs, deliveries: Vec<Delivery>) -> Vec<Delivery> {
+    hold_at(dirs, deliveries, outbox::now_ms())
+}
+
+fn hold_at(dirs: &AppDirs, deliveries: Vec<Delivery>, now: u64) -> Vec<Delivery> {
+    if deliveries.iter().all(|d| d.target.delivery.is_immediate()) {
+        return deliveries;
+    }
+    let Ok(_lock) = targets::acquire_store_lock(dirs) else {
+        return deliveries;
+    };
+    let Ok(mut state) = load_locked(dirs) else {
+        return deliveries;
+    };
+    let mut send = Vec::new();
+    for delivery in &deliveries {
+        let target = &delivery.target;
+        match target.delivery.mode {
+            DeliveryMode::Immediate => send.push(delivery.clone()),
+            DeliveryMode::Deduplicate => {
+                let task = task_key(&delivery.data);
+                if delivery.event.severity == "success" {
+                    // Recovered: the task's failure windows close, their repeats going out first.
+                    let (closed, open): (Vec<_>, Vec<_>) = std::mem::take(&mut state.windows)
+                        .into_iter()
+                        .partition(|w| {
+                            w.target_id == target.id
+                                && w.task == task
+                                && catalog::find(&w.event_id)
+                                    .is_some_and(|e| matches!(e.severity, "warning" | "error"))
+                        });
+                    state.windows = open;
+                    send.extend(
+                        closed
+                            .into_iter()
+                            .filter_map(|mut window| follow_up(target, &mut window)),
+                    );
+                }
+                let existing = state.windows.iter().position(|w| {
+                    w.target_id == target.id && w.event_id == delivery.event.id && w.task == task
+                });
+                if let Some(window) = existing
+                    .map(|i| &mut state.windows[i])
+                    .filter(|w| w.ends_at > now)
+                {
+                    window.repeats += 1;
+                    window.latest = Some(held(delivery));
+                    continue;
+                }
+                let mut delivery = delivery.clone();
+                // A window that ran out before a flush released it: its repeats ride along.
+                if let Some(expired) = existing.map(|i| state.windows.remove(i)) {
+                    if expired.repeats > 0 {
+                        mark_repeated(&mut delivery, expired.repeats + 1);
+                    }
+                }
+                state.windows.push(DedupeWindow {
+                    target_id: target.id.clone(),
+                    event_id: delivery.event.id.to_string(),
+                    task,
+                    ends_at: now + target.delivery.window_ms(),
+                    repeats: 0,
+                    latest: None,
+                });
+                send.push(delivery);
+            }
+            DeliveryMode::Digest => {
+                let event = held(delivery);
+                match state.digests.iter_mut().find(|d| d.target_id == target.id) {
+                    Some(digest) => {
+                        digest.events.push(event);
+                        if digest.events.len() > DIGEST_LIST_LIMIT {
+                            digest.events.remove(0);
+                            digest.omitted += 1;
+                        }
+                    }
+                    None => state.digests.push(PendingDigest {
+                        target_id: target.id.clone(),
+                        due_at: next_boundary(target.delivery.interval, now),
+                        events: vec![event],
+                        omitted: 0,
+                    }),
+                }
+            }
+        }
+    }
+    match write_locked(dirs, &state) {
+        Ok(()) => send,
+        Err(_) => deliveries,
+    }
+}
+
+/// One message for a due digest, carrying its most severe event's colors.
+fn summary(target: &NotificationTarget, digest: PendingDigest) -> Option<Delivery> {
+    let event = digest
+        .events
+        .iter()
+        .filter_map(|e| catalog::find(&e.event_id))
+        .max_by_key(|meta| filters::severity_rank(meta.severity))?;
+    let total = digest.events.len() + digest.omitted as usize;
+    let period = match target.delivery.interval {
+        DigestInterval::Hourly => "Hourly",
+        DigestInterval::Daily => "Daily",
+    };
+    let mut lines: Vec<String> = digest
+        .events
+        .iter()
+        .map(|e| format!("• {}: {}", e.title, e.body))
+        .collect();
+    if digest.omitted > 0 {
+        lines.push(format!("…and {} earlier", digest.omitted));
+    }
+    let listed: Vec<Value> = digest
+        .events
+        .iter()
+        .map(|e| {
+            json!({
+                "eventId": e.event_id,
+                "title": e.title,
+                "body": e.body,
+                "timestamp": e.timestamp,
+            })
+        })
+        .collect();
+    Some(Delivery {
+        target: target.clone(),
+        event,
+        title: format!(
+            "{} digest: {} notification{}",
+            period,
+            total,
+            if total == 1 { "" } else { "s" }
+        ),
+        body: lines.join("\n"),
+        data: json!({ "digest": { "count": total, "events": listed } }),
+        timestamp: history::now_iso(),
+        id: release_id(&target.id, "digest", digest.due_at),
+    })
+}
+
+/// Moves what's due from the held-back state into the outbox, due now: follow-ups for windows
+/// that ran out with repeats (a new window opens, in case it keeps happening) and digests. Both
+/// files change under the one lock — the outbox first, so a failure there releases nothing. A
+/// missing or disabled target's state is dropped. Returns what was released.
+fn release_at(
+    dirs: &AppDirs,
+    all_targets: &[NotificationTarget],
+    now: u64,
+) -> Result<Vec<Delivery>, String> {
+    let _lock = targets::acquire_store_lock(dirs)?;
+    let mut state = load_locked(dirs)?;
+    let target = |id: &str| all_targets.iter().find(|t| t.id == id && t.is_enabled);
+    let mut released = Vec::new();
+    state.windows.retain_mut(|window| {
+        if window.ends_at > now {
+            return true;
+        }
+        let Some(target) = target(&window.target_id) else {
+            return false;
+        };
+        let Some(delivery) = follow_up(target, window) else {
+            return false;
+        };
+        released.push(delivery);
+        window.ends_at = now + target.delivery.window_ms();
+        window.repeats = 0;
+        true
+    });
+    let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut state.digests)
+        .into_iter()
+        .partition(|d| d.due_at <= now);
+    state.digests = waiting;
+    for digest in due {
+        if let Some(delivery) = target(&digest.target_id).and_then(|t| summary(t, digest)) {
+            released.push(delivery);
+        }
+    }
+    let queued = released
+        .iter()
+        .map(|delivery| outbox::PendingDelivery {
+            last_error: "held back until now".to_string(),
+            ..outbox::pending(delivery, now)
+        })
+        .collect();
+    outbox::enqueue_locked(dirs, queued)?;
+    write_locked(dirs, &state)?;
+    Ok(released)
+}
+
+/// Releases the follow-ups and digests that are due into the outbox, for the `outbox::flush`
+/// that follows to send. Never fails the caller — what went wrong comes back as log lines.
+pub fn flush(dirs: &AppDirs) -> Vec<String> {
+    if !digest_path(dirs).exists() {
+        return Vec::new();
+    }
+    let all_targets = match targets::load(dirs) {
+        Ok(t) => t,
+        Err(e) => return vec![format!("failed to load notification targets: {}", e)],
+    };
+    match release_at(dirs, &all_targets, outbox::now_ms()) {
+        Ok(_) => Vec::new(),
+        Err(e) => vec![format!("failed to release held-back notifications: {}", e)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dirs(tag: &str) -> AppDirs {
+        let root = std::env::temp_dir().join(format!("rcloneui-digest-test-{}", tag));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        AppDirs {
+            app_data: root.clone(),
+            app_local_data: root,
+        }
+    }
+
+    fn target(id: &str, mode: DeliveryMode) -> NotificationTarget {
+        NotificationTarget {
+            id: id.into(),
+            provider: "webhook".into(),
+            name: id.into(),
+            url: format!("https://example.com/{}", id),
+            is_enabled: true,
+            events: vec!["schedule.failed".into(), "schedule.completed".into()],
+            created_at: 0,
+            last_sent_at: None,
+            last_error: None,
+            template: None,
+            signing_secret: None,
+            headers: Vec::new(),
+            auth: None,
+            filters: Default::default(),
+            delivery: TargetDelivery {
+                mode,
+                window_minutes: Some(60),
+                interval: DigestInterval::Hourly,
+            },
+        }
+    }
+
+    fn delivery(target: &NotificationTarget, event_id: &str, schedule_id: &str) -> Delivery {
+        Delivery {
+            target: target.clone(),
+            event: catalog::find(event_id).unwrap(),
+            title: "Nightly failed".into(),
+            body: "exit status 1".into(),
+            data: json!({ "scheduleId": schedule_id }),
+            timestamp: "2026-01-01T00:00:00.000Z".into(),
+            id: "d".into(),
+        }
+    }
+
+    #[test]
+    fn repeats_are_held_back_until_the_window_ends() {
+        let dirs = test_dirs("dedupe");
+        let dedupe = target("dedupe", DeliveryMode::Deduplicate);
+        let now = 10 * HOUR_MS;
+        let first = hold_at(&dirs, vec![delivery(&dedupe, "schedule.failed", "s1")], now);
+        assert_eq!(first.len(), 1);
+        for minute in 1..=3 {
+            let repeat = delivery(&dedupe, "schedule.failed", "s1");
+            assert!(hold_at(&dirs, vec![repeat], now + minute * 15 * 60_000).is_empty());
+        }
+        let other_task = delivery(&dedupe, "schedule.failed", "s2");
+        assert_eq!(hold_at(&dirs, vec![other_task], now + 60_000).len(), 1);
+
+        let targets = [dedupe.clone()];
+        assert!(release_at(&dirs, &targets, now + 30 * 60_000)
+            .unwrap()
+            .is_empty());
+        let released = release_at(&dirs, &targets, now + HOUR_MS).unwrap();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].title, "Nightly failed (x3)");
+        assert_eq!(released[0].data["repeatCount"], 3);
+        // Handed to the outbox, due now, under an id the same release would get again.
+        let queued = outbox::list(&dirs).unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].id, released[0].id);
+        assert_eq!(queued[0].next_attempt_at, now + HOUR_MS);
+        assert_eq!(
+            released[0].id,
+            release_id("dedupe", "schedule.failed|s1", now + HOUR_MS)
+        );
+        // Nothing more happened in the next window: the state empties and the file goes.
+        assert!(release_at(&dirs, &targets, now + 2 * HOUR_MS)
+            .unwrap()
+            .is_empty());
+        assert!(release_at(&dirs, &targets, now + 3 * HOUR_MS)
+            .unwrap()
+            .is_empty());
+        assert!(!digest_path(&dirs).exists());
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+
+    #[test]
+    fn digests_batch_until_the_hour_alongside_immediate_targets() {
+        let dirs = test_dirs("digest");
+        let digest = target("digest", DeliveryMode::Digest);
+        let immediate = target("immediate", DeliveryMode::Immediate);
+        let now = 10 * HOUR_MS + 5 * 60_000;
+        let sent = hold_at(
+            &dirs,
+            vec![
+                delivery(&digest, "schedule.completed", "s1"),
+                delivery(&immediate, "schedule.completed", "s1"),
+            ],
+            now,
+        );
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].target.id, "immediate");
+        let failed = delivery(&digest, "schedule.failed", "s1");
+        assert!(hold_at(&dirs, vec![failed], now + 60_000).is_empty());
+
+        let targets = [digest.clone()];
+        assert!(release_at(&dirs, &targets, 11 * HOUR_MS - 1)
+            .unwrap()
+            .is_empty());
+        let released = release_at(&dirs, &targets, 11 * HOUR_MS).unwrap();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].title, "Hourly digest: 2 notifications");
+        assert_eq!(released[0].event.id, "schedule.failed");
+        assert_eq!(released[0].data["digest"]["count"], 2);
+        assert_eq!(released[0].id, release_id("digest", "digest", 11 * HOUR_MS));
+        assert_eq!(outbox::list(&dirs).unwrap()[0].id, released[0].id);
+        assert!(!digest_path(&dirs).exists());
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+
+    /// Failing again after a recovery is news: the success closes the failure window (sending
+    /// what it held back), so the next failure goes out at once.
+    #[test]
+    fn a_success_closes_the_failure_window() {
+        let dirs = test_dirs("recovery");
+        let dedupe = target("dedupe", DeliveryMode::Deduplicate);
+        let now = 10 * HOUR_MS;
+        let failed = || vec![delivery(&dedupe, "schedule.failed", "s1")];
+        assert_eq!(hold_at(&dirs, failed(), now).len(), 1);
+        assert!(hold_at(&dirs, failed(), now + 60_000).is_empty());
+
+        // Another task's success leaves the window alone.
+        let other = vec![delivery(&dedupe, "schedule.completed", "s2")];
+        assert_eq!(hold_at(&dirs, other, now + 2 * 60_000).len(), 1);
+        assert!(hold_at(&dirs, failed(), now + 3 * 60_000).is_empty());
+
+        let completed = vec![delivery(&dedupe, "schedule.completed", "s1")];
+        let sent = hold_at(&dirs, completed, now + 4 * 60_000);
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].title, "Nightly failed (x2)");
+        assert_eq!(sent[1].event.id, "schedule.completed");
+
+        let again = hold_at(&dirs, failed(), now + 5 * 60_000);
+        assert_eq!(again.len(), 1);
+        assert_eq!(again[0].title, "Nightly failed");
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+}
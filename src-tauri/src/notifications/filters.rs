@@ -0,0 +1,359 @@
+//! Per-target routing filters, on top of the event subscription: a target can take only some
+//! schedules, operations or remotes, or only events of at least a given severity — so a
+//! #backups-critical channel hears about the production backups failing, not every personal
+//! test task. Every criterion that is set must match; within one, any value may.
+//!
+//! Filters are evaluated against the event's `data`: `scheduleId`, `operation`, and the remote
+//! names found in `remotes` (scheduled runs), `remote` (reauth), `sources`/`destination`
+//! (transfers) and `source` (mounts). An event without the field a criterion needs doesn't
+//! match it. For the drawer's preview, the last events dispatched are kept in `recent.json`.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::catalog::{self, EventMeta};
+use super::targets;
+use crate::scheduler::jobfile;
+use crate::scheduler::storeread::AppDirs;
+
+/// Lowest first. `success` ranks above `info` so "at least success" drops the chatter.
+const SEVERITIES: [&str; 4] = ["info", "success", "warning", "error"];
+
+/// How many dispatched events `recent.json` keeps.
+const RECENT_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetFilters {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedule_ids: Vec<String>,
+    /// `sync`, `copy`, `move`, `bisync`, …
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operations: Vec<String>,
+    /// Remote name patterns: `*` matches any run of characters, `?` any one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remotes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<String>,
+}
+
+impl TargetFilters {
+    pub fn is_empty(&self) -> bool {
+        *self == TargetFilters::default()
+    }
+}
+
+pub(super) fn severity_rank(severity: &str) -> Option<usize> {
+    SEVERITIES.iter().position(|s| *s == severity)
+}
+
+/// Glob match over chars, with `*` backtracking to its latest position.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<char>, Vec<char>) =
+        (pattern.chars().collect(), text.chars().collect());
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// The configured remotes an event's data names.
+fn event_remotes(data: &Value) -> Vec<String> {
+    let mut remotes: Vec<String> = Vec::new();
+    let strings = |key: &str| -> Vec<String> {
+        match data.get(key) {
+            Some(Value::String(s)) => vec![s.clone()],
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+    // Already remote names.
+    remotes.extend(strings("remotes"));
+    remotes.extend(strings("remote"));
+    // fs strings (`gdrive:backup`, `/home/me`).
+    for key in ["sources", "destination", "source"] {
+        remotes.extend(
+            strings(key)
+                .iter()
+                .filter_map(|fs| jobfile::remote_name(fs)),
+        );
+    }
+    remotes
+}
+
+/// Whether a target with these filters takes this event (its subscription checked apart).
+pub fn matches(filters: &TargetFilters, event: &EventMeta, data: &Value) -> bool {
+    if let Some(min) = filters.min_severity.as_deref() {
+        if severity_rank(event.severity) < severity_rank(min) {
+            return false;
+        }
+    }
+    let text = |key: &str| data.get(key).and_then(|v| v.as_str());
+    if !filters.schedule_ids.is_empty()
+        && !text("scheduleId").is_some_and(|id| filters.schedule_ids.iter().any(|s| s == id))
+    {
+        return false;
+    }
+    if !filters.operations.is_empty()
+        && !text("operation").is_some_and(|op| filters.operations.iter().any(|o| o == op))
+    {
+        return false;
+    }
+    if !filters.remotes.is_empty() {
+        let remotes = event_remotes(data);
+        let any = filters
+            .remotes
+            .iter()
+            .any(|pattern| remotes.iter().any(|remote| glob_matches(pattern, remote)));
+        if !any {
+            return false;
+        }
+    }
+    true
+}
+
+/// Save-time check.
+pub fn validate(filters: &TargetFilters) -> Result<(), String> {
+    if let Some(min) = &filters.min_severity {
+        if severity_rank(min).is_none() {
+            return Err(format!("unknown severity '{}'", min));
+        }
+    }
+    let blank = |values: &[String]| values.iter().any(|v| v.trim().is_empty());
+    if blank(&filters.schedule_ids) || blank(&filters.operations) || blank(&filters.remotes) {
+        return Err("Filter values can't be empty.".to_string());
+    }
+    Ok(())
+}
+
+/// A dispatched event, as the filter preview replays it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentEvent {
+    pub event_id: String,
+    pub title: String,
+    pub body: String,
+    pub timestamp: String,
+    #[serde(default)]
+    pub data: Value,
+}
+
+/// A recent event and whether a target with the previewed events and filters would have taken
+/// it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewedEvent {
+    #[serde(flatten)]
+    pub event: RecentEvent,
+    pub matched: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecentFile {
+    version: u32,
+    events: Vec<RecentEvent>,
+}
+
+fn recent_path(dirs: &AppDirs) -> PathBuf {
+    dirs.app_data.join("notifications").join("recent.json")
+}
+
+fn load_recent_locked(dirs: &AppDirs) -> Result<Vec<RecentEvent>, String> {
+    let path = recent_path(dirs);
+    match std::fs::read_to_string(&path) {
+        Ok(raw) => {
+            let file: RecentFile = serde_json::from_str(&raw)
+                .map_err(|e| format!("invalid recent events file {}: {}", path.display(), e))?;
+            Ok(file.events)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Appends a dispatched event, keeping the newest `RECENT_LIMIT`. Best-effort — the preview
+/// is a convenience, never a reason for a dispatch to fail.
+pub fn record_recent(dirs: &AppDirs, event: RecentEvent) {
+    let Ok(_lock) = targets::acquire_store_lock(dirs) else {
+        return;
+    };
+    let Ok(mut events) = load_recent_locked(dirs) else {
+        return;
+    };
+    events.push(event);
+    let excess = events.len().saturating_sub(RECENT_LIMIT);
+    events.drain(..excess);
+    let path = recent_path(dirs);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let file = RecentFile { version: 1, events };
+    let Ok(json) = serde_json::to_string_pretty(&file) else {
+        return;
+    };
+    let tmp = path.with_extension("json.tmp");
+    if std::fs::write(&tmp, json).is_ok() {
+        let _ = std::fs::rename(&tmp, &path);
+    }
+}
+
+/// The recent events, newest first, each with whether a target subscribed to `events` with
+/// these filters would have taken it.
+pub fn preview(
+    dirs: &AppDirs,
+    events: &[String],
+    filters: &TargetFilters,
+) -> Result<Vec<PreviewedEvent>, String> {
+    validate(filters)?;
+    let recent = {
+        let _lock = targets::acquire_store_lock(dirs)?;
+        load_recent_locked(dirs)?
+    };
+    Ok(recent
+        .into_iter()
+        .rev()
+        .map(|event| {
+            let matched = events.contains(&event.event_id)
+                && catalog::find(&event.event_id)
+                    .is_some_and(|meta| matches(filters, meta, &event.data));
+            PreviewedEvent { event, matched }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_dirs(tag: &str) -> AppDirs {
+        let root = std::env::temp_dir().join(format!("rcloneui-filters-test-{}", tag));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        AppDirs {
+            app_data: root.clone(),
+            app_local_data: root,
+        }
+    }
+
+    #[test]
+    fn remote_patterns_match_names_from_every_event_shape() {
+        assert!(glob_matches("gdrive", "gdrive"));
+        assert!(glob_matches("prod-*", "prod-s3"));
+        assert!(glob_matches("*-backup-?", "b2-backup-1"));
+        assert!(!glob_matches("prod-*", "test-prod-s3"));
+        assert!(!glob_matches("gdrive", "gdrive2"));
+
+        let prod = TargetFilters {
+            remotes: vec!["prod-*".into()],
+            ..Default::default()
+        };
+        let event = catalog::find("job.failed").unwrap();
+        let transfer = json!({ "sources": ["/home/me"], "destination": "prod-b2:photos" });
+        assert!(matches(&prod, event, &transfer));
+        let mount = json!({ "source": "test-gdrive:", "destination": "/mnt/g" });
+        assert!(!matches(&prod, event, &mount));
+        let scheduled = json!({ "scheduleId": "s1", "remotes": ["prod-s3"] });
+        assert!(matches(&prod, event, &scheduled));
+        assert!(!matches(&prod, event, &Value::Null));
+    }
+
+    #[test]
+    fn every_set_criterion_must_match() {
+        let filters = TargetFilters {
+            schedule_ids: vec!["nightly".into(), "weekly".into()],
+            operations: vec!["sync".into()],
+            remotes: Vec::new(),
+            min_severity: Some("warning".into()),
+        };
+        let failed = catalog::find("schedule.failed").unwrap();
+        let completed = catalog::find("schedule.completed").unwrap();
+        let data = json!({ "scheduleId": "weekly", "operation": "sync" });
+        assert!(matches(&filters, failed, &data));
+        assert!(
+            !matches(&filters, completed, &data),
+            "below the minimum severity"
+        );
+        let other = json!({ "scheduleId": "scratch", "operation": "sync" });
+        assert!(!matches(&filters, failed, &other));
+        let copy = json!({ "scheduleId": "weekly", "operation": "copy" });
+        assert!(!matches(&filters, failed, &copy));
+        assert!(matches(&TargetFilters::default(), completed, &Value::Null));
+
+        assert!(validate(&filters).is_ok());
+        let unknown = TargetFilters {
+            min_severity: Some("fatal".into()),
+            ..Default::default()
+        };
+        assert!(validate(&unknown).is_err());
+    }
+
+    #[test]
+    fn recent_events_keep_the_newest_and_preview_what_a_target_would_take() {
+        let dirs = test_dirs("recent");
+        for i in 0..RECENT_LIMIT + 5 {
+            let (event_id, operation) = if i % 2 == 0 {
+                ("schedule.failed", "sync")
+            } else {
+                ("schedule.completed", "copy")
+            };
+            record_recent(
+                &dirs,
+                RecentEvent {
+                    event_id: event_id.into(),
+                    title: format!("event {}", i),
+                    body: String::new(),
+                    timestamp: String::new(),
+                    data: json!({ "scheduleId": "nightly", "operation": operation }),
+                },
+            );
+        }
+
+        let syncs = TargetFilters {
+            operations: vec!["sync".into()],
+            ..Default::default()
+        };
+        let subscribed = vec!["schedule.failed".to_string(), "schedule.completed".into()];
+        let previewed = preview(&dirs, &subscribed, &syncs).unwrap();
+        assert_eq!(previewed.len(), RECENT_LIMIT);
+        assert_eq!(
+            previewed[0].event.title,
+            format!("event {}", RECENT_LIMIT + 4)
+        );
+        assert_eq!(previewed[RECENT_LIMIT - 1].event.title, "event 5");
+        // Even events are failed syncs; odd ones completed copies.
+        assert!(previewed[0].matched);
+        assert!(!previewed[1].matched);
+
+        // Unsubscribed events never match, whatever the filters.
+        let completions_only = vec!["schedule.completed".to_string()];
+        let previewed = preview(&dirs, &completions_only, &syncs).unwrap();
+        assert!(previewed.iter().all(|p| !p.matched));
+
+        let invalid = TargetFilters {
+            min_severity: Some("fatal".into()),
+            ..Default::default()
+        };
+        assert!(preview(&dirs, &subscribed, &invalid).is_err());
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+}
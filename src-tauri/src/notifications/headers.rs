@@ -0,0 +1,204 @@
+//! Custom request headers and authentication for generic webhook targets — an alert gateway
+//! behind a bearer token, or one that wants basic auth plus a tenant header. Header values are
+//! stored as typed; the auth secret (token, password or header value) is sealed in
+//! targets.json like the signing secret (signing::seal) and redacted from listings. Email targets
+//! keep their SMTP login here too, as basic auth.
+
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+
+use super::email;
+
+/// Headers the engine sets itself; auth goes through `TargetAuth`, not a raw Authorization.
+const RESERVED: [&str; 5] = [
+    "authorization",
+    "content-length",
+    "content-type",
+    "host",
+    "transfer-encoding",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMode {
+    Bearer,
+    Basic,
+    Header,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetAuth {
+    pub mode: AuthMode,
+    /// Basic auth only.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub username: String,
+    /// Custom-header auth only: the header the secret goes in.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub header: String,
+    /// The token, password or header value. Sealed in targets.json, redacted in listings, in
+    /// plain text from the GUI — where `None` keeps the stored one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+fn check_header_name(name: &str) -> Result<(), String> {
+    if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+        return Err(format!("'{}' is not a valid header name.", name));
+    }
+    let lower = name.to_ascii_lowercase();
+    if RESERVED.contains(&lower.as_str()) || lower.starts_with("x-rcloneui-") {
+        return Err(format!("The {} header is set by Rclone UI itself.", name));
+    }
+    Ok(())
+}
+
+/// Save-time check; a plain-text secret in `auth` is checked too (a kept stored one was
+/// checked when it was entered).
+pub fn validate(
+    provider: &str,
+    headers: &[TargetHeader],
+    auth: Option<&TargetAuth>,
+) -> Result<(), String> {
+    if provider == email::PROVIDER {
+        if !headers.is_empty() {
+            return Err("Custom headers are only supported for generic webhooks.".to_string());
+        }
+        if auth.is_some_and(|auth| auth.mode != AuthMode::Basic) {
+            return Err("Email targets log in with a username and password.".to_string());
+        }
+    } else if provider != "webhook" && (!headers.is_empty() || auth.is_some()) {
+        return Err(
+            "Custom headers and authentication are only supported for generic webhooks."
+                .to_string(),
+        );
+    }
+    for header in headers {
+        check_header_name(&header.name)?;
+        if reqwest::header::HeaderValue::from_str(&header.value).is_err() {
+            return Err(format!(
+                "The value of the {} header is invalid.",
+                header.name
+            ));
+        }
+    }
+    let Some(auth) = auth else {
+        return Ok(());
+    };
+    match auth.mode {
+        AuthMode::Basic if auth.username.is_empty() || auth.username.contains(':') => {
+            return Err("Basic auth needs a username without ':'.".to_string());
+        }
+        AuthMode::Header => check_header_name(&auth.header)?,
+        _ => {}
+    }
+    if let Some(secret) = &auth.secret {
+        if auth.mode != AuthMode::Basic && secret.is_empty() {
+            return Err("The authentication secret is empty.".to_string());
+        }
+        if secret.contains(['\r', '\n']) {
+            return Err("The authentication secret contains a line break.".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// The headers a delivery carries, with the auth secret already opened.
+pub fn request_headers(
+    headers: &[TargetHeader],
+    auth: Option<(&TargetAuth, &str)>,
+) -> Vec<(String, String)> {
+    let mut out: Vec<(String, String)> = headers
+        .iter()
+        .map(|h| (h.name.clone(), h.value.clone()))
+        .collect();
+    if let Some((auth, secret)) = auth {
+        out.push(match auth.mode {
+            AuthMode::Bearer => ("Authorization".to_string(), format!("Bearer {}", secret)),
+            AuthMode::Basic => {
+                let credentials = format!("{}:{}", auth.username, secret);
+                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                ("Authorization".to_string(), format!("Basic {}", encoded))
+            }
+            AuthMode::Header => (auth.header.clone(), secret.to_string()),
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth(mode: AuthMode, username: &str, header: &str, secret: &str) -> TargetAuth {
+        TargetAuth {
+            mode,
+            username: username.into(),
+            header: header.into(),
+            secret: Some(secret.into()),
+        }
+    }
+
+    #[test]
+    fn auth_modes_become_request_headers() {
+        let tenant = [TargetHeader {
+            name: "X-Tenant".into(),
+            value: "ops".into(),
+        }];
+        let basic = auth(AuthMode::Basic, "alerts", "", "s3cret");
+        assert_eq!(
+            request_headers(&tenant, Some((&basic, "s3cret"))),
+            vec![
+                ("X-Tenant".to_string(), "ops".to_string()),
+                // printf 'alerts:s3cret' | base64
+                (
+                    "Authorization".to_string(),
+                    "Basic YWxlcnRzOnMzY3JldA==".to_string()
+                ),
+            ]
+        );
+        let bearer = auth(AuthMode::Bearer, "", "", "tok");
+        assert_eq!(
+            request_headers(&[], Some((&bearer, "tok"))),
+            vec![("Authorization".to_string(), "Bearer tok".to_string())]
+        );
+        let custom = auth(AuthMode::Header, "", "X-Api-Key", "k");
+        assert_eq!(
+            request_headers(&[], Some((&custom, "k"))),
+            vec![("X-Api-Key".to_string(), "k".to_string())]
+        );
+    }
+
+    #[test]
+    fn validation_rejects_reserved_and_malformed_headers() {
+        let header = |name: &str| TargetHeader {
+            name: name.into(),
+            value: "v".into(),
+        };
+        assert!(validate("webhook", &[header("X-Tenant")], None).is_ok());
+        assert!(validate("webhook", &[header("Content-Type")], None).is_err());
+        assert!(validate("webhook", &[header("X-RcloneUI-Event")], None).is_err());
+        assert!(validate("webhook", &[header("bad header")], None).is_err());
+        assert!(validate("slack", &[header("X-Tenant")], None).is_err());
+        assert!(validate("email", &[header("X-Tenant")], None).is_err());
+
+        let no_user = auth(AuthMode::Basic, "", "", "pw");
+        assert!(validate("webhook", &[], Some(&no_user)).is_err());
+        let reserved = auth(AuthMode::Header, "", "Authorization", "k");
+        assert!(validate("webhook", &[], Some(&reserved)).is_err());
+        let empty = auth(AuthMode::Bearer, "", "", "");
+        assert!(validate("webhook", &[], Some(&empty)).is_err());
+
+        let login = auth(AuthMode::Basic, "alerts@example.com", "", "pw");
+        assert!(validate("email", &[], Some(&login)).is_ok());
+        assert!(validate("slack", &[], Some(&login)).is_err());
+        let bearer = auth(AuthMode::Bearer, "", "", "t");
+        assert!(validate("email", &[], Some(&bearer)).is_err());
+    }
+}
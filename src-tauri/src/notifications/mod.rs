@@ -1,11 +1,20 @@
-//! The notification engine: event catalog, webhook targets + dispatch, and the headless
-//! runner's OS toast. The GUI drives it through the commands below (lib/notifications.ts);
-//! the scheduler runner calls webhooks::dispatch / os::notify_headless directly. GUI OS toasts
-//! are NOT here — JS uses @tauri-apps/plugin-notification for those.
+//! The notification engine: event catalog, webhook/email targets + dispatch, the outbox of
+//! failed deliveries, and the headless runner's OS toast. The GUI drives it through the commands
+//! below (lib/notifications.ts) and retries the outbox on a background worker; the scheduler
+//! runner calls webhooks::dispatch / digest::flush / outbox::flush / os::notify_headless
+//! directly. GUI OS toasts are NOT here — JS uses @tauri-apps/plugin-notification for those.
 
 pub mod catalog;
+pub mod digest;
+pub mod email;
+pub mod filters;
+pub mod headers;
 pub mod os;
+pub mod outbox;
+pub mod settings;
+pub mod signing;
 pub mod targets;
+pub mod template;
 pub mod webhooks;
 
 use serde::Serialize;
@@ -32,9 +41,11 @@ pub async fn notifications_list_targets(
     app: AppHandle,
 ) -> Result<Vec<targets::NotificationTarget>, String> {
     // spawn_blocking: the cross-process store lock can wait up to ~10s under contention.
+    // Stored secrets never reach the webview.
     tauri::async_runtime::spawn_blocking(move || {
         let dirs = storeread::app_dirs_from(&app)?;
-        targets::load(&dirs)
+        let targets = targets::load(&dirs)?;
+        Ok(targets.into_iter().map(targets::redacted).collect())
     })
     .await
     .map_err(|e| format!("task failed: {}", e))?
@@ -47,7 +58,7 @@ pub async fn notifications_add_target(
 ) -> Result<targets::NotificationTarget, String> {
     tauri::async_runtime::spawn_blocking(move || {
         let dirs = storeread::app_dirs_from(&app)?;
-        targets::add(&dirs, target)
+        targets::add(&dirs, target).map(targets::redacted)
     })
     .await
     .map_err(|e| format!("task failed: {}", e))?
@@ -97,6 +108,7 @@ pub async fn notifications_dispatch(
             &title,
             &body,
             data.unwrap_or(serde_json::Value::Null),
+            settings::load(&dirs).dispatch_budget(),
         ) {
             log::warn!("[notifications] {}", line);
         }
@@ -106,19 +118,113 @@ pub async fn notifications_dispatch(
     .map_err(|e| format!("task failed: {}", e))?
 }
 
+/// Deliveries waiting in the outbox for a retry, oldest first.
+#[tauri::command]
+pub async fn notifications_list_outbox(
+    app: AppHandle,
+) -> Result<Vec<outbox::PendingDelivery>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        outbox::list(&dirs)
+    })
+    .await
+    .map_err(|e| format!("task failed: {}", e))?
+}
+
+/// Gives up on one pending delivery, or on all of them without an `id`.
+#[tauri::command]
+pub async fn notifications_purge_outbox(app: AppHandle, id: Option<String>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        outbox::purge(&dirs, id.as_deref()).map(|_| ())
+    })
+    .await
+    .map_err(|e| format!("task failed: {}", e))?
+}
+
+/// The notification-wide settings — the defaults until some are saved.
+#[tauri::command]
+pub async fn notifications_get_settings(
+    app: AppHandle,
+) -> Result<settings::NotificationSettings, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        Ok(settings::load(&dirs))
+    })
+    .await
+    .map_err(|e| format!("task failed: {}", e))?
+}
+
+/// Errors are validation messages, shown as is.
+#[tauri::command]
+pub async fn notifications_set_settings(
+    app: AppHandle,
+    settings: settings::NotificationSettings,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        settings::save(&dirs, settings)
+    })
+    .await
+    .map_err(|e| format!("task failed: {}", e))?
+}
+
+/// Sends due digests and follow-ups and retries due outbox deliveries every minute for as long
+/// as the GUI runs — the runner only flushes when a schedule fires.
+pub fn start_outbox_worker(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let client = webhooks::http_client();
+        loop {
+            std::thread::sleep(std::time::Duration::from_secs(60));
+            let Ok(dirs) = storeread::app_dirs_from(&app) else {
+                continue;
+            };
+            let budget = settings::load(&dirs).dispatch_budget();
+            let mut log_lines = digest::flush(&dirs);
+            log_lines.extend(outbox::flush(&dirs, &client, budget));
+            for line in log_lines {
+                log::warn!("[notifications] {}", line);
+            }
+        }
+    });
+}
+
 /// Errors propagate — the UI shows them in the "Test failed" dialog.
 #[tauri::command]
 pub async fn notifications_send_test(
     app: AppHandle,
-    provider: String,
-    url: String,
-    target_id: Option<String>,
-    name: Option<String>,
+    target: webhooks::TestTarget,
 ) -> Result<(), String> {
     tauri::async_runtime::spawn_blocking(move || {
         let dirs = storeread::app_dirs_from(&app)?;
-        webhooks::send_test(&dirs, &provider, &url, target_id.as_deref(), name.as_deref())
+        webhooks::send_test(&dirs, target)
     })
     .await
     .map_err(|e| format!("task failed: {}", e))?
 }
+
+/// The recent events a target subscribed to `events` with these filters would have taken —
+/// the drawer's filter preview.
+#[tauri::command]
+pub async fn notifications_preview_filters(
+    app: AppHandle,
+    events: Vec<String>,
+    filters: filters::TargetFilters,
+) -> Result<Vec<filters::PreviewedEvent>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        filters::preview(&dirs, &events, &filters)
+    })
+    .await
+    .map_err(|e| format!("task failed: {}", e))?
+}
+
+/// The payload a template renders for the test event — the drawer's preview. Errors are the
+/// same ones saving would report.
+#[tauri::command]
+pub fn notifications_preview_template(
+    template: template::PayloadTemplate,
+) -> Result<String, String> {
+    template::preview(&template)
+}
@@ -0,0 +1,519 @@
+//! The notification outbox: `<app_data>/notifications/outbox.json`. A delivery that fails for a
+//! reason that may pass — the endpoint is down, 5xx, still rate limited, a transient SMTP error —
+//! is kept here instead of being lost, and retried with exponential backoff (1 minute doubling
+//! to an hour) until it goes through or is a day old. Both the GUI (every minute, while it runs)
+//! and every runner invocation (after its own notifications) flush it, so a failure is retried
+//! even if the app is never opened.
+//!
+//! Entries hold the notification, not the built request: a retry is rebuilt from the target as
+//! it is then (an edited URL or secret applies, a deleted or disabled target drops its entries)
+//! and no opened secret is ever written to disk. The generic webhook's delivery id is kept, so a
+//! receiver sees the same `X-RcloneUI-Delivery` on every retry. The file shares the targets
+//! store lock; like targets.json it is only locked for the read-modify-write, never a send.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::catalog;
+use super::targets;
+use super::webhooks;
+use crate::scheduler::storeread::AppDirs;
+
+const BASE_BACKOFF_MS: u64 = 60_000;
+const MAX_BACKOFF_MS: u64 = 60 * 60_000;
+/// Past this a notification is stale news; it's dropped rather than delivered.
+const MAX_AGE_MS: u64 = 24 * 60 * 60_000;
+/// A flush claims the due entries for this long, so a GUI and a runner flushing at the same
+/// time never both send one; a delivery still in flight when its budget ran out is claimed the
+/// same way. Well above a flush's worst case (settings::MAX_DISPATCH_BUDGET_SECONDS).
+const CLAIM_MS: u64 = 10 * 60_000;
+/// Oldest entries go first beyond this — a target down for a day can't grow the file unbounded.
+const MAX_PENDING: usize = 500;
+/// Sent in parallel, one thread each — the rest wait for the next flush.
+const MAX_PER_FLUSH: usize = 50;
+
+/// Why a delivery failed, and whether a later retry could help: network errors, 5xx, 408,
+/// exhausted 429 waits and transient SMTP errors can; other 4xx, an unopenable secret or a
+/// permanent SMTP rejection can't.
+#[derive(Debug, Clone)]
+pub struct DeliveryError {
+    pub message: String,
+    pub retryable: bool,
+}
+
+impl DeliveryError {
+    pub fn permanent(message: String) -> Self {
+        DeliveryError {
+            message,
+            retryable: false,
+        }
+    }
+
+    pub fn transient(message: String) -> Self {
+        DeliveryError {
+            message,
+            retryable: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingDelivery {
+    /// Also the generic webhook's delivery id.
+    pub id: String,
+    pub target_id: String,
+    pub target_name: String,
+    pub event_id: String,
+    pub title: String,
+    pub body: String,
+    #[serde(default)]
+    pub data: Value,
+    /// When the notification fired — retries render it, not the retry time.
+    pub timestamp: String,
+    pub created_at: u64,
+    pub attempts: u32,
+    pub next_attempt_at: u64,
+    pub last_error: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OutboxFile {
+    version: u32,
+    deliveries: Vec<PendingDelivery>,
+}
+
+fn outbox_path(dirs: &AppDirs) -> PathBuf {
+    dirs.app_data.join("notifications").join("outbox.json")
+}
+
+pub(super) fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// The wait after the `attempts`-th failed attempt: 1, 2, 4 … 32 minutes, then hourly.
+pub(super) fn backoff_ms(attempts: u32) -> u64 {
+    (BASE_BACKOFF_MS << attempts.saturating_sub(1).min(6)).min(MAX_BACKOFF_MS)
+}
+
+fn load_locked(dirs: &AppDirs) -> Result<Vec<PendingDelivery>, String> {
+    let path = outbox_path(dirs);
+    match std::fs::read_to_string(&path) {
+        Ok(raw) => {
+            let file: OutboxFile = serde_json::from_str(&raw)
+                .map_err(|e| format!("invalid outbox file {}: {}", path.display(), e))?;
+            Ok(file.deliveries)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// An empty outbox is no file, so the GUI's periodic flush is a cheap existence check.
+fn write_locked(dirs: &AppDirs, deliveries: &[PendingDelivery]) -> Result<(), String> {
+    let path = outbox_path(dirs);
+    if deliveries.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("failed to clear the outbox: {}", e))
+            }
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create notifications dir: {}", e))?;
+    }
+    let file = OutboxFile {
+        version: 1,
+        deliveries: deliveries.to_vec(),
+    };
+    let json = serde_json::to_string_pretty(&file)
+        .map_err(|e| format!("failed to serialize the outbox: {}", e))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("failed to write the outbox: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("failed to save the outbox: {}", e))
+}
+
+pub fn list(dirs: &AppDirs) -> Result<Vec<PendingDelivery>, String> {
+    let _lock = targets::acquire_store_lock(dirs)?;
+    load_locked(dirs)
+}
+
+/// An entry for `delivery`, not attempted yet and due `now`.
+pub(super) fn pending(delivery: &webhooks::Delivery, now: u64) -> PendingDelivery {
+    PendingDelivery {
+        id: delivery.id.clone(),
+        target_id: delivery.target.id.clone(),
+        target_name: delivery.target.name.clone(),
+        event_id: delivery.event.id.to_string(),
+        title: delivery.title.clone(),
+        body: delivery.body.clone(),
+        data: delivery.data.clone(),
+        timestamp: delivery.timestamp.clone(),
+        created_at: now,
+        attempts: 0,
+        next_attempt_at: now,
+        last_error: String::new(),
+    }
+}
+
+/// Queues failed deliveries (their first attempt already made) for their first retry.
+pub fn enqueue(dirs: &AppDirs, failed: Vec<PendingDelivery>) -> Result<(), String> {
+    if failed.is_empty() {
+        return Ok(());
+    }
+    let _lock = targets::acquire_store_lock(dirs)?;
+    enqueue_locked(dirs, failed)
+}
+
+/// `enqueue` for a caller already holding the store lock. Entries already queued under the
+/// same id are kept as they are.
+pub(super) fn enqueue_locked(dirs: &AppDirs, queued: Vec<PendingDelivery>) -> Result<(), String> {
+    let mut deliveries = load_locked(dirs)?;
+    for entry in queued {
+        if !deliveries.iter().any(|d| d.id == entry.id) {
+            deliveries.push(entry);
+        }
+    }
+    let excess = deliveries.len().saturating_sub(MAX_PENDING);
+    deliveries.drain(..excess);
+    write_locked(dirs, &deliveries)
+}
+
+/// Drops one pending delivery, or all of them without an id. Returns how many were dropped.
+pub fn purge(dirs: &AppDirs, id: Option<&str>) -> Result<usize, String> {
+    let _lock = targets::acquire_store_lock(dirs)?;
+    let mut deliveries = load_locked(dirs)?;
+    let before = deliveries.len();
+    deliveries.retain(|d| id.is_some_and(|id| d.id != id));
+    write_locked(dirs, &deliveries)?;
+    Ok(before - deliveries.len())
+}
+
+/// Queues deliveries still in flight when their budget ran out, claimed as if a flush were
+/// sending them: should the process exit before they finish, the claim runs out and they are
+/// retried under the same delivery id; otherwise their thread settles them (`settle_late`).
+/// Ones already queued — outbox retries — are left as they are.
+pub(super) fn hold_in_flight(
+    dirs: &AppDirs,
+    in_flight: &[&webhooks::Delivery],
+) -> Result<(), String> {
+    if in_flight.is_empty() {
+        return Ok(());
+    }
+    let now = now_ms();
+    let claimed = in_flight
+        .iter()
+        .map(|delivery| PendingDelivery {
+            next_attempt_at: now + CLAIM_MS,
+            last_error: "still sending when the time budget ran out".to_string(),
+            ..pending(delivery, now)
+        })
+        .collect();
+    let _lock = targets::acquire_store_lock(dirs)?;
+    enqueue_locked(dirs, claimed)
+}
+
+/// Settles a delivery whose send finished after its budget ran out, the way a flush settles a
+/// retry: its queued entry goes on success or a permanent failure and backs off on a retryable
+/// one, and the outcome is recorded on its target.
+pub(super) fn settle_late(
+    dirs: &AppDirs,
+    delivery: &webhooks::Delivery,
+    result: Result<(), DeliveryError>,
+) {
+    let error = result.err();
+    let _ = settle_retries(dirs, &[(delivery.id.clone(), error.clone())], now_ms());
+    let outcome = (delivery.target.id.clone(), error.map(|e| e.message));
+    targets::record_outcomes(dirs, &[outcome]);
+}
+
+/// (id, error) per sent entry — no error, or a permanent one, removes it.
+fn settle_retries(
+    dirs: &AppDirs,
+    results: &[(String, Option<DeliveryError>)],
+    now: u64,
+) -> Result<(), String> {
+    let _lock = targets::acquire_store_lock(dirs)?;
+    let mut deliveries = load_locked(dirs)?;
+    deliveries.retain_mut(|d| {
+        let Some((_, error)) = results.iter().find(|(id, _)| *id == d.id) else {
+            return true;
+        };
+        match error {
+            Some(e) if e.retryable => {
+                d.attempts += 1;
+                d.next_attempt_at = now + backoff_ms(d.attempts);
+                d.last_error = e.message.clone();
+                true
+            }
+            _ => false,
+        }
+    });
+    write_locked(dirs, &deliveries)
+}
+
+/// Retries every due delivery, waiting at most `budget` for them. Never fails the caller —
+/// what went wrong comes back as log lines, and outcomes are recorded on the targets like a
+/// dispatch's.
+pub fn flush(dirs: &AppDirs, client: &reqwest::Client, budget: Duration) -> Vec<String> {
+    flush_at(dirs, client, now_ms(), budget)
+}
+
+fn flush_at(dirs: &AppDirs, client: &reqwest::Client, now: u64, budget: Duration) -> Vec<String> {
+    if !outbox_path(dirs).exists() {
+        return Vec::new();
+    }
+    let mut log_lines = Vec::new();
+
+    // Claim what's due and drop what expired, under the lock; send without it.
+    let claimed = {
+        let Ok(_lock) = targets::acquire_store_lock(dirs) else {
+            return log_lines;
+        };
+        let mut deliveries = match load_locked(dirs) {
+            Ok(d) => d,
+            Err(e) => return vec![e],
+        };
+        deliveries.retain(|d| {
+            let expired = now.saturating_sub(d.created_at) > MAX_AGE_MS;
+            if expired {
+                log_lines.push(format!(
+                    "gave up on {} to {} after {} attempts: {}",
+                    d.event_id, d.target_name, d.attempts, d.last_error
+                ));
+            }
+            !expired
+        });
+        let mut claimed = Vec::new();
+        let due = deliveries.iter_mut().filter(|d| d.next_attempt_at <= now);
+        for delivery in due.take(MAX_PER_FLUSH) {
+            delivery.next_attempt_at = now + CLAIM_MS;
+            claimed.push(delivery.clone());
+        }
+        if let Err(e) = write_locked(dirs, &deliveries) {
+            log_lines.push(e);
+            return log_lines;
+        }
+        claimed
+    };
+    if claimed.is_empty() {
+        return log_lines;
+    }
+
+    let all_targets = match targets::load(dirs) {
+        Ok(t) => t,
+        Err(e) => {
+            log_lines.push(format!("failed to load notification targets: {}", e));
+            return log_lines;
+        }
+    };
+    let mut results: Vec<(String, Option<DeliveryError>)> = Vec::new();
+    let mut deliveries: Vec<webhooks::Delivery> = Vec::new();
+    let mut attempts: Vec<u32> = Vec::new();
+    for pending in claimed {
+        let target = all_targets
+            .iter()
+            .find(|t| t.id == pending.target_id && t.is_enabled);
+        let (Some(target), Some(event)) = (target, catalog::find(&pending.event_id)) else {
+            results.push((pending.id, None));
+            continue;
+        };
+        attempts.push(pending.attempts);
+        deliveries.push(webhooks::Delivery {
+            target: target.clone(),
+            event,
+            title: pending.title,
+            body: pending.body,
+            data: pending.data,
+            timestamp: pending.timestamp,
+            id: pending.id,
+        });
+    }
+    let sent = webhooks::deliver_all(dirs, client, &deliveries, budget);
+    let mut outcomes: Vec<(String, Option<String>)> = Vec::new();
+    for ((delivery, attempts), result) in deliveries.into_iter().zip(attempts).zip(sent) {
+        // Released digests and follow-ups (digest.rs) go out here for the first time.
+        let attempt = match attempts {
+            0 => "sending".to_string(),
+            n => format!("retry {} of", n),
+        };
+        // Still claimed: its thread settles it when the send finishes.
+        let Some(result) = result else {
+            log_lines.push(format!(
+                "{} {} to {} still going when the time budget ran out",
+                attempt, delivery.event.id, delivery.target.name
+            ));
+            continue;
+        };
+        if let Err(e) = &result {
+            log_lines.push(format!(
+                "{} {} to {} failed: {}",
+                attempt, delivery.event.id, delivery.target.name, e.message
+            ));
+        }
+        outcomes.push((
+            delivery.target.id.clone(),
+            result.clone().err().map(|e| e.message),
+        ));
+        results.push((delivery.id, result.err()));
+    }
+
+    // On failure the claim runs out, so the entries are retried later (and possibly resent).
+    if let Err(e) = settle_retries(dirs, &results, now) {
+        log_lines.push(format!("failed to update the outbox: {}", e));
+    }
+    targets::record_outcomes(dirs, &outcomes);
+    log_lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_dirs(tag: &str) -> AppDirs {
+        let root = std::env::temp_dir().join(format!("rcloneui-outbox-test-{}", tag));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        AppDirs {
+            app_data: root.clone(),
+            app_local_data: root,
+        }
+    }
+
+    fn pending(id: &str, created_at: u64) -> PendingDelivery {
+        PendingDelivery {
+            id: id.into(),
+            target_id: "gone".into(),
+            target_name: "Gone".into(),
+            event_id: "schedule.failed".into(),
+            title: "T".into(),
+            body: "B".into(),
+            data: Value::Null,
+            timestamp: "2026-01-01T00:00:00.000Z".into(),
+            created_at,
+            attempts: 1,
+            next_attempt_at: created_at + backoff_ms(1),
+            last_error: "connection refused".into(),
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_to_an_hour_and_old_or_orphaned_entries_are_dropped() {
+        assert_eq!(backoff_ms(1), 60_000);
+        assert_eq!(backoff_ms(2), 120_000);
+        assert_eq!(backoff_ms(6), 32 * 60_000);
+        assert_eq!(backoff_ms(7), MAX_BACKOFF_MS);
+        assert_eq!(backoff_ms(40), MAX_BACKOFF_MS);
+
+        let dirs = test_dirs("expiry");
+        let now = now_ms();
+        enqueue(
+            &dirs,
+            vec![
+                pending("old", now - MAX_AGE_MS - 1),
+                pending("orphan", now - 5 * 60_000),
+                pending("later", now),
+                pending("discard", now),
+            ],
+        )
+        .unwrap();
+        assert_eq!(purge(&dirs, Some("discard")).unwrap(), 1);
+
+        let lines = flush_at(
+            &dirs,
+            &webhooks::http_client(),
+            now,
+            Duration::from_secs(30),
+        );
+        assert_eq!(lines.len(), 1, "{:?}", lines);
+        assert!(lines[0].contains("gave up on schedule.failed to Gone after 1 attempts"));
+        // "orphan" was due but its target no longer exists; "later" isn't due yet.
+        let left = list(&dirs).unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].id, "later");
+
+        assert_eq!(purge(&dirs, None).unwrap(), 1);
+        assert!(!outbox_path(&dirs).exists());
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+
+    /// A dispatch to a dead endpoint is queued; once the endpoint is back, the flush delivers it
+    /// with the original delivery id and clears the recorded error.
+    #[test]
+    fn failed_dispatches_are_queued_and_delivered_once_the_endpoint_is_back() {
+        let dirs = test_dirs("retry");
+        let dead_url = {
+            let l = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}/hook", l.local_addr().unwrap())
+        };
+        let added = targets::add(
+            &dirs,
+            targets::NewTarget {
+                provider: "webhook".into(),
+                name: "Flaky".into(),
+                url: dead_url,
+                is_enabled: true,
+                events: vec!["schedule.failed".into()],
+                template: None,
+                signing_secret: None,
+                headers: Vec::new(),
+                auth: None,
+                filters: Default::default(),
+                delivery: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let client = webhooks::http_client();
+        let data = json!({ "scheduleId": "s1" });
+        let budget = Duration::from_secs(30);
+        let lines = webhooks::dispatch(&dirs, &client, "schedule.failed", "T", "B", data, budget);
+        assert!(lines[0].contains("queued for retry"), "{:?}", lines);
+        let queued = list(&dirs).unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].attempts, 1);
+        assert!(queued[0].next_attempt_at > now_ms());
+
+        // Not due yet: nothing is sent.
+        assert!(flush(&dirs, &client, budget).is_empty());
+        assert_eq!(list(&dirs).unwrap().len(), 1);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let patch = targets::TargetPatch {
+            url: Some(url),
+            ..Default::default()
+        };
+        targets::update(&dirs, &added.id, patch).unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 16384];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n");
+            let _ = tx.send(String::from_utf8_lossy(&buf[..n]).into_owned());
+        });
+
+        let lines = flush_at(&dirs, &client, queued[0].next_attempt_at, budget);
+        assert!(lines.is_empty(), "{:?}", lines);
+        let raw = rx
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+            .to_lowercase();
+        assert!(raw.contains(&format!("x-rcloneui-delivery: {}", queued[0].id)));
+        assert!(list(&dirs).unwrap().is_empty());
+        let after = targets::load(&dirs).unwrap();
+        assert_eq!(after[0].last_error, None);
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+}
@@ -0,0 +1,127 @@
+//! Notification-wide settings: `<app_data>/notifications/settings.json`. Like the targets, they
+//! are read at fire time by both the GUI and the headless runner, so a change applies to the
+//! next scheduled run without re-registering anything.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::targets;
+use crate::scheduler::storeread::AppDirs;
+
+/// The dispatch budget bounds. The floor keeps a run that is almost out of time from giving
+/// its deliveries none at all; the ceiling stays well below the outbox's claim on a delivery
+/// still in flight (outbox::CLAIM_MS).
+pub const MIN_DISPATCH_BUDGET_SECONDS: u64 = 5;
+pub const MAX_DISPATCH_BUDGET_SECONDS: u64 = 300;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    /// How long a dispatch, digest or outbox flush waits for its deliveries — all targets
+    /// together, since they're sent at the same time.
+    pub dispatch_budget_seconds: u64,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            dispatch_budget_seconds: 30,
+        }
+    }
+}
+
+impl NotificationSettings {
+    pub fn dispatch_budget(&self) -> Duration {
+        Duration::from_secs(
+            self.dispatch_budget_seconds
+                .clamp(MIN_DISPATCH_BUDGET_SECONDS, MAX_DISPATCH_BUDGET_SECONDS),
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SettingsFile {
+    version: u32,
+    settings: NotificationSettings,
+}
+
+fn settings_path(dirs: &AppDirs) -> PathBuf {
+    dirs.app_data.join("notifications").join("settings.json")
+}
+
+/// The saved settings, or the defaults when there are none (or they can't be read — a
+/// notification is never held up over a settings file).
+pub fn load(dirs: &AppDirs) -> NotificationSettings {
+    std::fs::read_to_string(settings_path(dirs))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<SettingsFile>(&raw).ok())
+        .map(|file| file.settings)
+        .unwrap_or_default()
+}
+
+pub fn save(dirs: &AppDirs, settings: NotificationSettings) -> Result<(), String> {
+    let budget = settings.dispatch_budget_seconds;
+    if !(MIN_DISPATCH_BUDGET_SECONDS..=MAX_DISPATCH_BUDGET_SECONDS).contains(&budget) {
+        return Err(format!(
+            "The delivery time budget must be between {} and {} seconds.",
+            MIN_DISPATCH_BUDGET_SECONDS, MAX_DISPATCH_BUDGET_SECONDS
+        ));
+    }
+    let _lock = targets::acquire_store_lock(dirs)?;
+    let path = settings_path(dirs);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create notifications dir: {}", e))?;
+    }
+    let file = SettingsFile {
+        version: 1,
+        settings,
+    };
+    let json = serde_json::to_string_pretty(&file)
+        .map_err(|e| format!("failed to serialize notification settings: {}", e))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)
+        .map_err(|e| format!("failed to write notification settings: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("failed to save notification settings: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dirs(tag: &str) -> AppDirs {
+        let root = std::env::temp_dir().join(format!("rcloneui-settings-test-{}", tag));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        AppDirs {
+            app_data: root.clone(),
+            app_local_data: root,
+        }
+    }
+
+    #[test]
+    fn dispatch_budget_defaults_round_trips_and_stays_in_bounds() {
+        let dirs = test_dirs("budget");
+        assert_eq!(load(&dirs).dispatch_budget(), Duration::from_secs(30));
+
+        let settings = NotificationSettings {
+            dispatch_budget_seconds: 90,
+        };
+        save(&dirs, settings.clone()).unwrap();
+        assert_eq!(load(&dirs), settings);
+
+        // Refused when saved; clamped when hand-edited in — never a zero budget.
+        let zero = NotificationSettings {
+            dispatch_budget_seconds: 0,
+        };
+        assert!(save(&dirs, zero.clone()).is_err());
+        assert_eq!(load(&dirs), settings);
+        assert_eq!(
+            zero.dispatch_budget(),
+            Duration::from_secs(MIN_DISPATCH_BUDGET_SECONDS)
+        );
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+}
@@ -0,0 +1,187 @@
+//! Signed generic webhooks. A target may carry a signing secret; each delivery then carries
+//! `X-RcloneUI-Timestamp` (unix seconds) and `X-RcloneUI-Signature: sha256=<hex>`, the
+//! HMAC-SHA256 of `<timestamp>.<body>` under the secret (Stripe's scheme in GitHub's header
+//! format). Receivers recompute it over the raw body and reject stale timestamps to stop
+//! replays. Every generic delivery also gets `X-RcloneUI-Delivery`, an id that stays the same
+//! across the retries of one delivery, for deduplication.
+//!
+//! The secret — like the auth secrets of headers.rs — is sealed in targets.json
+//! (ChaCha20-Poly1305) under a per-install key kept owner-only next to it: targets.json can be
+//! backed up, synced or attached to a bug report without leaking the secret, and a sealed value
+//! is useless on any other machine.
+
+use std::path::PathBuf;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::scheduler::storeread::AppDirs;
+
+pub const TIMESTAMP_HEADER: &str = "X-RcloneUI-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-RcloneUI-Signature";
+pub const DELIVERY_HEADER: &str = "X-RcloneUI-Delivery";
+
+/// Below this an HMAC key is guessable offline from a single signed request.
+pub const MIN_SECRET_LEN: usize = 16;
+
+const SEALED_PREFIX: &str = "v1:";
+const NONCE_LEN: usize = 12;
+
+fn key_path(dirs: &AppDirs) -> PathBuf {
+    dirs.app_data.join("notifications").join("signing.key")
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn read_key(dirs: &AppDirs) -> Result<Option<Key>, String> {
+    let path = key_path(dirs);
+    match std::fs::read_to_string(&path) {
+        Ok(raw) => match from_hex(raw.trim()) {
+            Some(bytes) if bytes.len() == 32 => Ok(Some(*Key::from_slice(&bytes))),
+            _ => Err(format!("invalid signing key file {}", path.display())),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Only called while the targets store lock is held (sealing happens on add/update), so two
+/// processes can never both create a key.
+fn load_or_create_key(dirs: &AppDirs) -> Result<Key, String> {
+    if let Some(key) = read_key(dirs)? {
+        return Ok(key);
+    }
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    let path = key_path(dirs);
+    let dir = path.parent().ok_or("invalid signing key path")?;
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("failed to create notifications dir: {}", e))?;
+    let tmp = path.with_extension("key.tmp");
+    let _ = std::fs::remove_file(&tmp);
+    {
+        use std::io::Write as _;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt as _;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(&tmp)
+            .map_err(|e| format!("failed to create signing key: {}", e))?;
+        file.write_all(to_hex(&key).as_bytes())
+            .map_err(|e| format!("failed to write signing key: {}", e))?;
+    }
+    std::fs::rename(&tmp, &path).map_err(|e| format!("failed to save signing key: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypts a secret for targets.json, creating this install's key on first use.
+pub fn seal(dirs: &AppDirs, secret: &str) -> Result<String, String> {
+    let cipher = ChaCha20Poly1305::new(&load_or_create_key(dirs)?);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, secret.as_bytes())
+        .map_err(|_| "failed to seal the secret".to_string())?;
+    Ok(format!(
+        "{}{}{}",
+        SEALED_PREFIX,
+        to_hex(&nonce),
+        to_hex(&ciphertext)
+    ))
+}
+
+/// The secret behind a sealed targets.json value. Fails when the value was sealed on another
+/// machine (or the key file was deleted) — the secret then has to be entered again.
+pub fn open(dirs: &AppDirs, sealed: &str) -> Result<String, String> {
+    let bytes = sealed
+        .strip_prefix(SEALED_PREFIX)
+        .and_then(from_hex)
+        .filter(|bytes| bytes.len() > NONCE_LEN)
+        .ok_or("the stored secret is malformed")?;
+    let key = read_key(dirs)?.ok_or("the encryption key is missing; enter the secret again")?;
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let plaintext = ChaCha20Poly1305::new(&key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "the secret can't be decrypted on this machine; enter it again")?;
+    String::from_utf8(plaintext).map_err(|_| "the stored secret is malformed".to_string())
+}
+
+/// Hex HMAC-SHA256 of `<timestamp>.<body>`.
+pub fn signature(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// Save-time check: only generic webhooks are signed, with a secret long enough to matter.
+pub fn validate_secret(provider: &str, secret: &str) -> Result<(), String> {
+    if provider != "webhook" {
+        return Err("Request signing is only supported for generic webhooks.".to_string());
+    }
+    if secret.chars().count() < MIN_SECRET_LEN || secret.trim() != secret {
+        return Err(format!(
+            "The signing secret must be at least {} characters, without surrounding spaces.",
+            MIN_SECRET_LEN
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_secrets_open_only_with_this_installs_key() {
+        let root = std::env::temp_dir().join("rcloneui-signing-test");
+        let _ = std::fs::remove_dir_all(&root);
+        let dirs = AppDirs {
+            app_data: root.clone(),
+            app_local_data: root.clone(),
+        };
+
+        let sealed = seal(&dirs, "whsec_0123456789abcdef").unwrap();
+        assert!(sealed.starts_with("v1:"));
+        assert!(!sealed.contains("0123456789abcdef"));
+        assert_ne!(sealed, seal(&dirs, "whsec_0123456789abcdef").unwrap());
+        assert_eq!(open(&dirs, &sealed).unwrap(), "whsec_0123456789abcdef");
+
+        // A fresh key (another machine, or a deleted key file) can't open it.
+        std::fs::remove_file(key_path(&dirs)).unwrap();
+        assert!(open(&dirs, &sealed).unwrap_err().contains("missing"));
+        seal(&dirs, "another secret value").unwrap();
+        assert!(open(&dirs, &sealed).unwrap_err().contains("decrypted"));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn signature_matches_a_known_vector() {
+        // printf '1700000000.{"a":1}' | openssl dgst -sha256 -hmac 'whsec_test_secret_1'
+        assert_eq!(
+            signature("whsec_test_secret_1", 1_700_000_000, br#"{"a":1}"#),
+            "52cfeb510cf96374ea094bf9623975c0f517e1c3091f80ea7e0941290d2514df"
+        );
+        assert!(validate_secret("slack", "whsec_test_secret_1").is_err());
+        assert!(validate_secret("webhook", "short").is_err());
+        assert!(validate_secret("webhook", "whsec_test_secret_1").is_ok());
+    }
+}
@@ -6,8 +6,14 @@
 
 use std::path::PathBuf;
 
-use serde::{Deserialize, Serialize};
-
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::digest::{self, TargetDelivery};
+use super::email;
+use super::filters::{self, TargetFilters};
+use super::headers::{self, AuthMode, TargetAuth, TargetHeader};
+use super::signing;
+use super::template::{self, PayloadTemplate};
 use crate::scheduler::storeread::AppDirs;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +30,39 @@ pub struct NotificationTarget {
     pub last_sent_at: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_error: Option<String>,
+    /// Generic webhooks only: replaces the fixed payload shape.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<PayloadTemplate>,
+    /// Generic webhooks only: the HMAC signing secret, sealed (signing::seal).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_secret: Option<String>,
+    /// Generic webhooks only: extra request headers, sent as typed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<TargetHeader>,
+    /// Generic webhooks, and email targets' SMTP login (basic): its secret sealed like the
+    /// signing secret.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<TargetAuth>,
+    /// Narrows the subscribed events down to some schedules, operations, remotes or severities.
+    #[serde(default, skip_serializing_if = "TargetFilters::is_empty")]
+    pub filters: TargetFilters,
+    /// Immediate, deduplicated or batched into digests.
+    #[serde(default, skip_serializing_if = "TargetDelivery::is_immediate")]
+    pub delivery: TargetDelivery,
+}
+
+/// What listings show instead of a stored secret: the GUI only needs to know one is set.
+pub const REDACTED: &str = "********";
+
+/// The target as the GUI gets it — sealed secrets replaced by `REDACTED`.
+pub fn redacted(mut target: NotificationTarget) -> NotificationTarget {
+    if target.signing_secret.is_some() {
+        target.signing_secret = Some(REDACTED.to_string());
+    }
+    if let Some(auth) = target.auth.as_mut().filter(|auth| auth.secret.is_some()) {
+        auth.secret = Some(REDACTED.to_string());
+    }
+    target
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,6 +73,20 @@ pub struct NewTarget {
     pub url: String,
     pub is_enabled: bool,
     pub events: Vec<String>,
+    #[serde(default)]
+    pub template: Option<PayloadTemplate>,
+    /// In plain text; sealed before it's stored.
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+    #[serde(default)]
+    pub headers: Vec<TargetHeader>,
+    /// Its secret in plain text.
+    #[serde(default)]
+    pub auth: Option<TargetAuth>,
+    #[serde(default)]
+    pub filters: TargetFilters,
+    #[serde(default)]
+    pub delivery: TargetDelivery,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -43,6 +96,27 @@ pub struct TargetPatch {
     pub url: Option<String>,
     pub events: Option<Vec<String>>,
     pub is_enabled: Option<bool>,
+    /// Absent leaves the template alone; `null` removes it.
+    #[serde(default, deserialize_with = "present")]
+    pub template: Option<Option<PayloadTemplate>>,
+    /// A new secret in plain text; `null` turns signing off.
+    #[serde(default, deserialize_with = "present")]
+    pub signing_secret: Option<Option<String>>,
+    pub headers: Option<Vec<TargetHeader>>,
+    /// `null` removes auth; an auth without a secret keeps the stored one.
+    #[serde(default, deserialize_with = "present")]
+    pub auth: Option<Option<TargetAuth>>,
+    pub filters: Option<TargetFilters>,
+    pub delivery: Option<TargetDelivery>,
+}
+
+/// Tells an explicit `null` (`Some(None)`) apart from a missing field (`None`, via default).
+fn present<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -70,11 +144,11 @@ fn now_ms() -> u64 {
         .as_millis() as u64
 }
 
-/// Cross-process mutual exclusion for targets.json read-modify-write cycles. Held for
-/// milliseconds (never across HTTP sends). Unix: kernel flock — released on crash, valid across
-/// Flatpak sandbox PID namespaces; release truncates but never unlinks (an unlink/recreate race
-/// would let two processes lock two inodes of the same path). Windows: create_new existence
-/// with a stale break well above any real hold time.
+/// Cross-process mutual exclusion for targets.json (and outbox.json) read-modify-write cycles.
+/// Held for milliseconds (never across HTTP sends). Unix: kernel flock — released on crash,
+/// valid across Flatpak sandbox PID namespaces; release truncates but never unlinks (an
+/// unlink/recreate race would let two processes lock two inodes of the same path). Windows:
+/// create_new existence with a stale break well above any real hold time.
 pub struct StoreLock {
     #[cfg(unix)]
     _file: std::fs::File,
@@ -95,7 +169,7 @@ const LOCK_RETRY_MS: u64 = 250;
 const LOCK_STALE_MS: u64 = 30_000;
 
 #[cfg(unix)]
-fn acquire_store_lock(dirs: &AppDirs) -> Result<StoreLock, String> {
+pub(super) fn acquire_store_lock(dirs: &AppDirs) -> Result<StoreLock, String> {
     use std::os::unix::io::AsRawFd;
 
     let path = lock_file_path(dirs);
@@ -126,7 +200,7 @@ fn acquire_store_lock(dirs: &AppDirs) -> Result<StoreLock, String> {
 }
 
 #[cfg(not(unix))]
-fn acquire_store_lock(dirs: &AppDirs) -> Result<StoreLock, String> {
+pub(super) fn acquire_store_lock(dirs: &AppDirs) -> Result<StoreLock, String> {
     let path = lock_file_path(dirs);
     if let Some(parent) = path.parent() {
         let _ = std::fs::create_dir_all(parent);
@@ -200,7 +274,38 @@ pub fn load(dirs: &AppDirs) -> Result<Vec<NotificationTarget>, String> {
     load_locked(dirs)
 }
 
+/// Seals the plain-text secret of an auth setting about to be stored. Without one (or with the
+/// redaction placeholder) the stored secret is kept, as long as the mode didn't change; basic
+/// auth may go without a password.
+fn seal_auth(
+    dirs: &AppDirs,
+    mut auth: TargetAuth,
+    stored: Option<&TargetAuth>,
+) -> Result<TargetAuth, String> {
+    auth.secret = match auth.secret.take().filter(|secret| secret != REDACTED) {
+        Some(secret) => Some(signing::seal(dirs, &secret)?),
+        None => match stored.filter(|stored| stored.mode == auth.mode) {
+            Some(stored) => stored.secret.clone(),
+            None if auth.mode == AuthMode::Basic => None,
+            None => return Err("Enter the authentication secret.".to_string()),
+        },
+    };
+    Ok(auth)
+}
+
 pub fn add(dirs: &AppDirs, new: NewTarget) -> Result<NotificationTarget, String> {
+    if let Some(template) = &new.template {
+        template::validate_for(&new.provider, template)?;
+    }
+    if let Some(secret) = &new.signing_secret {
+        signing::validate_secret(&new.provider, secret)?;
+    }
+    headers::validate(&new.provider, &new.headers, new.auth.as_ref())?;
+    if new.provider == email::PROVIDER {
+        email::settings(&new.url, None)?;
+    }
+    filters::validate(&new.filters)?;
+    digest::validate(&new.delivery)?;
     let _lock = acquire_store_lock(dirs)?;
     let mut targets = load_locked(dirs)?;
     // Re-checked here under the lock: the drawer's duplicate check reads a snapshot that
@@ -219,6 +324,18 @@ pub fn add(dirs: &AppDirs, new: NewTarget) -> Result<NotificationTarget, String>
         created_at: now_ms(),
         last_sent_at: None,
         last_error: None,
+        template: new.template,
+        signing_secret: new
+            .signing_secret
+            .map(|secret| signing::seal(dirs, &secret))
+            .transpose()?,
+        headers: new.headers,
+        auth: new
+            .auth
+            .map(|auth| seal_auth(dirs, auth, None))
+            .transpose()?,
+        filters: new.filters,
+        delivery: new.delivery,
     };
     targets.push(target.clone());
     write_targets(dirs, &targets)?;
@@ -244,6 +361,9 @@ pub fn update(dirs: &AppDirs, id: &str, patch: TargetPatch) -> Result<(), String
         target.name = name;
     }
     if let Some(url) = patch.url {
+        if target.provider == email::PROVIDER {
+            email::settings(&url, None)?;
+        }
         target.url = url;
     }
     if let Some(events) = patch.events {
@@ -252,6 +372,42 @@ pub fn update(dirs: &AppDirs, id: &str, patch: TargetPatch) -> Result<(), String
     if let Some(is_enabled) = patch.is_enabled {
         target.is_enabled = is_enabled;
     }
+    if let Some(template) = patch.template {
+        if let Some(template) = &template {
+            template::validate_for(&target.provider, template)?;
+        }
+        target.template = template;
+    }
+    if let Some(secret) = patch.signing_secret {
+        target.signing_secret = match secret {
+            Some(secret) if secret == REDACTED => target.signing_secret.take(),
+            Some(secret) => {
+                signing::validate_secret(&target.provider, &secret)?;
+                Some(signing::seal(dirs, &secret)?)
+            }
+            None => None,
+        };
+    }
+    if let Some(headers) = patch.headers {
+        headers::validate(&target.provider, &headers, None)?;
+        target.headers = headers;
+    }
+    if let Some(auth) = patch.auth {
+        if let Some(auth) = &auth {
+            headers::validate(&target.provider, &[], Some(auth))?;
+        }
+        target.auth = auth
+            .map(|auth| seal_auth(dirs, auth, target.auth.as_ref()))
+            .transpose()?;
+    }
+    if let Some(new_filters) = patch.filters {
+        filters::validate(&new_filters)?;
+        target.filters = new_filters;
+    }
+    if let Some(delivery) = patch.delivery {
+        digest::validate(&delivery)?;
+        target.delivery = delivery;
+    }
     write_targets(dirs, &targets)
 }
 
@@ -326,6 +482,12 @@ mod tests {
                 url: "https://example.com/hook".into(),
                 is_enabled: true,
                 events: vec!["schedule.completed".into()],
+                template: None,
+                signing_secret: None,
+                headers: Vec::new(),
+                auth: None,
+                filters: Default::default(),
+                delivery: Default::default(),
             },
         )
         .unwrap();
@@ -346,6 +508,12 @@ mod tests {
                 url: "  HTTPS://EXAMPLE.COM/HOOK ".into(),
                 is_enabled: true,
                 events: vec![],
+                template: None,
+                signing_secret: None,
+                headers: Vec::new(),
+                auth: None,
+                filters: Default::default(),
+                delivery: Default::default(),
             },
         );
         assert!(dup.unwrap_err().contains("already configured"));
@@ -374,6 +542,95 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dirs.app_data);
     }
 
+    #[test]
+    fn template_patches_are_validated_and_null_clears_them() {
+        let dirs = test_dirs("template");
+        let added = add(
+            &dirs,
+            NewTarget {
+                provider: "webhook".into(),
+                name: "Incidents".into(),
+                url: "https://example.com/incidents".into(),
+                is_enabled: true,
+                events: vec![],
+                template: Some(PayloadTemplate {
+                    body: r#"{"summary": "{{title}}"}"#.into(),
+                    content_type: "application/json".into(),
+                    method: "POST".into(),
+                }),
+                signing_secret: None,
+                headers: Vec::new(),
+                auth: None,
+                filters: Default::default(),
+                delivery: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let broken: TargetPatch =
+            serde_json::from_str(r#"{"template": {"body": "{\"summary\": {{title}"}}"#).unwrap();
+        assert!(update(&dirs, &added.id, broken).is_err());
+        let unrelated: TargetPatch = serde_json::from_str(r#"{"name": "Renamed"}"#).unwrap();
+        update(&dirs, &added.id, unrelated).unwrap();
+        assert!(load(&dirs).unwrap()[0].template.is_some());
+
+        let cleared: TargetPatch = serde_json::from_str(r#"{"template": null}"#).unwrap();
+        update(&dirs, &added.id, cleared).unwrap();
+        assert_eq!(load(&dirs).unwrap()[0].template, None);
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+
+    #[test]
+    fn email_logins_are_sealed_and_kept_out_of_the_url() {
+        let dirs = test_dirs("email");
+        let url = "smtp://mail.example.com?from=a%40x.com&to=b%40y.com";
+        let new = |url: &str| NewTarget {
+            provider: "email".into(),
+            name: "Ops".into(),
+            url: url.into(),
+            is_enabled: true,
+            events: vec![],
+            template: None,
+            signing_secret: None,
+            headers: Vec::new(),
+            auth: Some(TargetAuth {
+                mode: AuthMode::Basic,
+                username: "alerts@example.com".into(),
+                header: String::new(),
+                secret: Some("smtp-password".into()),
+            }),
+            filters: Default::default(),
+            delivery: Default::default(),
+        };
+        let in_url = "smtp://u:pw@mail.example.com?from=a%40x.com&to=b%40y.com";
+        assert!(add(&dirs, new(in_url)).is_err());
+        let added = add(&dirs, new(url)).unwrap();
+
+        let raw =
+            std::fs::read_to_string(dirs.app_data.join("notifications/targets.json")).unwrap();
+        assert!(!raw.contains("smtp-password"));
+        let stored = load(&dirs).unwrap().remove(0);
+        assert_eq!(
+            email::login(&dirs, stored.auth.as_ref()).unwrap(),
+            Some(("alerts@example.com".into(), "smtp-password".into()))
+        );
+        assert_eq!(
+            redacted(stored).auth.unwrap().secret.as_deref(),
+            Some(REDACTED)
+        );
+
+        // The drawer sends the redaction back when the password wasn't touched.
+        let patch: TargetPatch = serde_json::from_str(
+            r#"{"auth": {"mode": "basic", "username": "alerts@example.com", "secret": "********"}}"#,
+        )
+        .unwrap();
+        update(&dirs, &added.id, patch).unwrap();
+        let stored = load(&dirs).unwrap().remove(0);
+        let (_, password) = email::login(&dirs, stored.auth.as_ref()).unwrap().unwrap();
+        assert_eq!(password, "smtp-password");
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+
     #[test]
     fn record_outcomes_updates_existing_and_skips_deleted() {
         let dirs = test_dirs("outcomes");
@@ -385,6 +642,12 @@ mod tests {
                 url: "https://hooks.slack.com/services/T1/B1/y".into(),
                 is_enabled: true,
                 events: vec![],
+                template: None,
+                signing_secret: None,
+                headers: Vec::new(),
+                auth: None,
+                filters: Default::default(),
+                delivery: Default::default(),
             },
         )
         .unwrap();
@@ -0,0 +1,297 @@
+//! User-defined payload templates for generic webhook targets: a body with `{{variable}}`
+//! placeholders plus the content type and method to send it with, so a target can speak
+//! PagerDuty Events v2, Opsgenie or an in-house incident API without a translation proxy.
+//!
+//! Variables: `id`, `label`, `severity` (the event), `title`, `body`, `timestamp`, and `data` or
+//! `data.<path>` (dot-separated keys, array indexes as numbers). In a JSON template a placeholder
+//! inside a string literal becomes the escaped text, and a bare one a JSON value — both
+//! `"summary": "{{title}}"` and `"ms": {{data.durationMs}}` render valid JSON. Missing values
+//! render as `null` (bare JSON) or nothing. Other content types get the plain text.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::catalog::{self, EventMeta};
+use crate::scheduler::history;
+
+pub const METHODS: [&str; 3] = ["POST", "PUT", "PATCH"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadTemplate {
+    pub body: String,
+    #[serde(default = "default_content_type")]
+    pub content_type: String,
+    #[serde(default = "default_method")]
+    pub method: String,
+}
+
+fn default_content_type() -> String {
+    "application/json".to_string()
+}
+
+fn default_method() -> String {
+    "POST".to_string()
+}
+
+/// What a template is rendered with — one notification.
+pub struct Variables<'a> {
+    pub event: &'a EventMeta,
+    pub title: &'a str,
+    pub body: &'a str,
+    pub timestamp: &'a str,
+    pub data: &'a Value,
+}
+
+impl Variables<'_> {
+    fn lookup(&self, name: &str) -> Option<Value> {
+        match name {
+            "id" => Some(Value::from(self.event.id)),
+            "label" => Some(Value::from(self.event.label)),
+            "severity" => Some(Value::from(self.event.severity)),
+            "title" => Some(Value::from(self.title)),
+            "body" => Some(Value::from(self.body)),
+            "timestamp" => Some(Value::from(self.timestamp)),
+            "data" => Some(self.data.clone()),
+            _ => {
+                let path = name.strip_prefix("data.")?;
+                let mut value = self.data;
+                for key in path.split('.') {
+                    value = match value {
+                        Value::Array(items) => items.get(key.parse::<usize>().ok()?)?,
+                        _ => value.get(key)?,
+                    };
+                }
+                Some(value.clone())
+            }
+        }
+    }
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        segments.push(Segment::Text(&rest[..start]));
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| "unclosed '{{' in the payload template".to_string())?;
+        let name = after[..end].trim();
+        let known = matches!(
+            name,
+            "id" | "label" | "severity" | "title" | "body" | "timestamp" | "data"
+        ) || name
+            .strip_prefix("data.")
+            .is_some_and(|path| path.split('.').all(|key| !key.is_empty()));
+        if !known {
+            return Err(format!("unknown template variable '{}'", name));
+        }
+        segments.push(Segment::Variable(name));
+        rest = &after[end + 2..];
+    }
+    segments.push(Segment::Text(rest));
+    Ok(segments)
+}
+
+fn is_json(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+/// Whether the end of `text` is inside a JSON string literal, given where it started.
+fn track_strings(text: &str, mut in_string: bool) -> bool {
+    let mut escaped = false;
+    for c in text.chars() {
+        if escaped {
+            escaped = false;
+        } else if in_string && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_string = !in_string;
+        }
+    }
+    in_string
+}
+
+fn plain_text(value: Option<Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s,
+        Some(other) => other.to_string(),
+    }
+}
+
+pub fn method(template: &PayloadTemplate) -> Result<reqwest::Method, String> {
+    if !METHODS.contains(&template.method.as_str()) {
+        return Err(format!(
+            "unsupported method '{}' (use POST, PUT or PATCH)",
+            template.method
+        ));
+    }
+    reqwest::Method::from_bytes(template.method.as_bytes()).map_err(|e| e.to_string())
+}
+
+pub fn render(template: &PayloadTemplate, vars: &Variables) -> Result<String, String> {
+    let json = is_json(&template.content_type);
+    let mut out = String::with_capacity(template.body.len());
+    let mut in_string = false;
+    for segment in parse(&template.body)? {
+        match segment {
+            Segment::Text(text) => {
+                out.push_str(text);
+                if json {
+                    in_string = track_strings(text, in_string);
+                }
+            }
+            Segment::Variable(name) => {
+                let value = vars.lookup(name);
+                if json && in_string {
+                    let quoted = Value::String(plain_text(value)).to_string();
+                    out.push_str(&quoted[1..quoted.len() - 1]);
+                } else if json {
+                    out.push_str(&value.unwrap_or(Value::Null).to_string());
+                } else {
+                    out.push_str(&plain_text(value));
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Renders with the test event and representative schedule data — what the drawer previews
+/// and what save-time validation checks.
+pub fn preview(template: &PayloadTemplate) -> Result<String, String> {
+    validate(template)?;
+    render_sample(template)
+}
+
+fn render_sample(template: &PayloadTemplate) -> Result<String, String> {
+    let data = json!({
+        "scheduleId": "sample",
+        "task": "Nightly backup",
+        "operation": "sync",
+        "durationMs": 83_000,
+        "bytes": 1_073_741_824u64,
+    });
+    render(
+        template,
+        &Variables {
+            event: &catalog::TEST_EVENT,
+            title: "Test notification",
+            body: "This is a test notification from Rclone UI.",
+            timestamp: &history::now_iso(),
+            data: &data,
+        },
+    )
+}
+
+pub fn validate(template: &PayloadTemplate) -> Result<(), String> {
+    method(template)?;
+    if template.body.trim().is_empty() {
+        return Err("the payload template is empty".to_string());
+    }
+    let content_type = template.content_type.trim();
+    if content_type.is_empty() || reqwest::header::HeaderValue::from_str(content_type).is_err() {
+        return Err(format!("invalid content type '{}'", template.content_type));
+    }
+    let rendered = render_sample(template)?;
+    if is_json(&template.content_type) {
+        serde_json::from_str::<Value>(&rendered)
+            .map_err(|e| format!("the payload template does not render valid JSON: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Save-time check: templates only apply to the generic webhook provider.
+pub fn validate_for(provider: &str, template: &PayloadTemplate) -> Result<(), String> {
+    if provider != "webhook" {
+        return Err("Payload templates are only supported for generic webhooks.".to_string());
+    }
+    validate(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(body: &str, content_type: &str) -> PayloadTemplate {
+        PayloadTemplate {
+            body: body.into(),
+            content_type: content_type.into(),
+            method: "POST".into(),
+        }
+    }
+
+    #[test]
+    fn json_placeholders_escape_in_strings_and_render_values_outside() {
+        let event = catalog::find("schedule.failed").unwrap();
+        let data = json!({ "durationMs": 4200, "paths": ["a", "b"], "task": "Nightly" });
+        let vars = Variables {
+            event,
+            title: "Backup \"photos\" failed",
+            body: "line 1\nline 2",
+            timestamp: "2026-01-01T00:00:00.000Z",
+            data: &data,
+        };
+
+        let pagerduty = template(
+            r#"{"event_action": "trigger", "payload": {"summary": "{{title}}: {{ body }}",
+                "severity": "{{severity}}", "custom_details": {"ms": {{data.durationMs}},
+                "first": {{data.paths.0}}, "missing": {{data.nope}}, "all": {{data}}}}}"#,
+            "application/json",
+        );
+        let rendered: Value = serde_json::from_str(&render(&pagerduty, &vars).unwrap()).unwrap();
+        assert_eq!(
+            rendered["payload"]["summary"],
+            "Backup \"photos\" failed: line 1\nline 2"
+        );
+        assert_eq!(rendered["payload"]["severity"], "error");
+        assert_eq!(rendered["payload"]["custom_details"]["ms"], 4200);
+        assert_eq!(rendered["payload"]["custom_details"]["first"], "a");
+        assert_eq!(
+            rendered["payload"]["custom_details"]["missing"],
+            Value::Null
+        );
+        assert_eq!(
+            rendered["payload"]["custom_details"]["all"]["task"],
+            "Nightly"
+        );
+
+        let text = template("{{label}} — {{data.task}} ({{data.nope}})", "text/plain");
+        assert_eq!(
+            render(&text, &vars).unwrap(),
+            "Scheduled task failed — Nightly ()"
+        );
+    }
+
+    #[test]
+    fn validation_rejects_bad_templates() {
+        assert!(validate(&template(r#"{"id": "{{id}}"}"#, "application/json")).is_ok());
+        assert!(validate(&template("{{title}}", "text/plain; charset=utf-8")).is_ok());
+
+        let err = validate(&template(r#"{"x": "{{event.id}}"}"#, "application/json"));
+        assert!(err
+            .unwrap_err()
+            .contains("unknown template variable 'event.id'"));
+        let err = validate(&template(r#"{"x": "{{title"}"#, "application/json"));
+        assert!(err.unwrap_err().contains("unclosed"));
+        let err = validate(&template(r#"{"x": {{title}}"#, "application/json"));
+        assert!(err.unwrap_err().contains("valid JSON"));
+        let mut get = template("{{title}}", "text/plain");
+        get.method = "GET".into();
+        assert!(validate(&get).unwrap_err().contains("unsupported method"));
+        assert!(validate_for("slack", &template("{{title}}", "text/plain")).is_err());
+    }
+}
@@ -3,10 +3,20 @@
 //! hand-synced Rust port (scheduler/notify.rs); payload shapes stay byte-compatible with what
 //! the TS dispatcher sent, so existing webhook consumers see no change.
 
+use std::time::Duration;
+
+use serde::Deserialize;
 use serde_json::{json, Value};
 
 use super::catalog::{self, EventMeta};
+use super::digest;
+use super::email;
+use super::filters::{self, RecentEvent};
+use super::headers::{self, TargetAuth, TargetHeader};
+use super::outbox::{self, DeliveryError, PendingDelivery};
+use super::signing;
 use super::targets::{self, NotificationTarget};
+use super::template::{self, PayloadTemplate};
 use crate::scheduler::history;
 use crate::scheduler::storeread::AppDirs;
 
@@ -18,6 +28,136 @@ fn discord_color(severity: &str) -> u32 {
     }
 }
 
+/// Adaptive Card named color for a severity (Teams).
+fn adaptive_card_color(severity: &str) -> &'static str {
+    match severity {
+        "success" => "Good",
+        "error" => "Attention",
+        _ => "Accent",
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn format_duration(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+/// The facts table of the card providers: the event `data` fields worth a row, humanized.
+/// Scheduled runs report `task`/`durationMs`/`bytes`; the GUI's job watcher `durationSeconds`.
+fn card_facts(data: &Value) -> Vec<(&'static str, String)> {
+    let mut facts = Vec::new();
+    if let Some(task) = data.get("task").and_then(Value::as_str) {
+        facts.push(("Task", task.to_string()));
+    }
+    if let Some(operation) = data.get("operation").and_then(Value::as_str) {
+        facts.push(("Operation", operation.to_string()));
+    }
+    let seconds = data
+        .get("durationMs")
+        .and_then(Value::as_u64)
+        .map(|ms| ms / 1000)
+        .or_else(|| data.get("durationSeconds").and_then(Value::as_u64));
+    if let Some(seconds) = seconds {
+        facts.push(("Duration", format_duration(seconds)));
+    }
+    if let Some(bytes) = data.get("bytes").and_then(Value::as_u64) {
+        facts.push(("Transferred", format_bytes(bytes)));
+    }
+    facts
+}
+
+/// ntfy priority (1 min … 5 urgent) and emoji tag for a severity.
+fn ntfy_priority(severity: &str) -> (u8, &'static str) {
+    match severity {
+        "success" => (3, "white_check_mark"),
+        "error" => (5, "rotating_light"),
+        _ => (2, "information_source"),
+    }
+}
+
+/// Gotify priority (0 silent … 10; 8+ pops up on Android) and title emoji for a severity.
+fn gotify_priority(severity: &str) -> (u8, &'static str) {
+    match severity {
+        "success" => (5, "✅"),
+        "error" => (8, "🚨"),
+        _ => (2, "ℹ️"),
+    }
+}
+
+/// Longest Retry-After / `retry_after_ms` a send waits out; and how many times per send.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(30);
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// How long a 429 asks us to wait: the Retry-After header (seconds — Discord, Slack, …), else
+/// Matrix's `retry_after_ms` or Telegram's `parameters.retry_after` in the JSON body. Negative,
+/// NaN or infinite values are ignored rather than trusted.
+fn rate_limit_delay(headers: &reqwest::header::HeaderMap, body: &str) -> Duration {
+    let from_header = headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok());
+    let from_body = || {
+        let parsed: Value = serde_json::from_str(body).ok()?;
+        parsed
+            .get("retry_after_ms")
+            .and_then(Value::as_u64)
+            .map(Duration::from_millis)
+            .or_else(|| {
+                parsed
+                    .pointer("/parameters/retry_after")
+                    .and_then(Value::as_f64)
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            })
+    };
+    from_header
+        .or_else(from_body)
+        .unwrap_or(Duration::from_secs(2))
+        .min(MAX_RATE_LIMIT_WAIT)
+}
+
+/// Splits `key` out of the url's query: the url without it, and its value. Lets credentials
+/// ride the one stored URL but leave in a header, out of server access logs.
+fn take_query_param(url: &reqwest::Url, key: &str) -> (reqwest::Url, Option<String>) {
+    let mut value = None;
+    let rest: Vec<(String, String)> = url
+        .query_pairs()
+        .filter_map(|(k, v)| {
+            if k == key {
+                value = Some(v.into_owned());
+                None
+            } else {
+                Some((k.into_owned(), v.into_owned()))
+            }
+        })
+        .collect();
+    let mut stripped = url.clone();
+    if rest.is_empty() {
+        stripped.set_query(None);
+    } else {
+        stripped.query_pairs_mut().clear().extend_pairs(rest);
+    }
+    (stripped, value.filter(|v| !v.is_empty()))
+}
+
 fn escape_telegram_html(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -25,9 +165,64 @@ fn escape_telegram_html(text: &str) -> String {
 }
 
 struct OutboundRequest {
+    method: reqwest::Method,
     url: String,
     body: Value,
     event_header: bool,
+    headers: Vec<(String, String)>,
+    /// A rendered payload template — (content type, text) — sent instead of the JSON `body`.
+    raw_body: Option<(String, String)>,
+}
+
+/// The generic webhook's per-delivery id. Built once per request, so every retry of a
+/// delivery repeats it and receivers can deduplicate.
+fn delivery_headers() -> Vec<(String, String)> {
+    vec![(
+        signing::DELIVERY_HEADER.to_string(),
+        uuid::Uuid::new_v4().to_string(),
+    )]
+}
+
+/// Signs the exact bytes that will be sent: the JSON body is serialized here, once, and sent
+/// as is. The timestamp is the signing time, so retries (at most ~2 minutes later) carry it.
+fn sign_request(request: &mut OutboundRequest, secret: &str) {
+    let text = match &request.raw_body {
+        Some((_, text)) => text.clone(),
+        None => {
+            let text = request.body.to_string();
+            request.raw_body = Some(("application/json".to_string(), text.clone()));
+            text
+        }
+    };
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let signature = signing::signature(secret, timestamp, text.as_bytes());
+    request.headers.extend([
+        (signing::TIMESTAMP_HEADER.to_string(), timestamp.to_string()),
+        (
+            signing::SIGNATURE_HEADER.to_string(),
+            format!("sha256={}", signature),
+        ),
+    ]);
+}
+
+/// Adds a target's custom headers and auth, the auth secret opened from targets.json.
+fn apply_target_headers(
+    dirs: &AppDirs,
+    target: &NotificationTarget,
+    request: &mut OutboundRequest,
+) -> Result<(), String> {
+    let secret = match target.auth.as_ref().and_then(|auth| auth.secret.as_deref()) {
+        Some(sealed) => signing::open(dirs, sealed)?,
+        None => String::new(),
+    };
+    let auth = target.auth.as_ref().map(|auth| (auth, secret.as_str()));
+    request
+        .headers
+        .extend(headers::request_headers(&target.headers, auth));
+    Ok(())
 }
 
 fn build_request(
@@ -42,7 +237,10 @@ fn build_request(
         "slack" => Ok(OutboundRequest {
             url: target.url.clone(),
             body: json!({ "text": format!("*{}*\n{}", title, body) }),
+            method: reqwest::Method::POST,
             event_header: false,
+            raw_body: None,
+            headers: Vec::new(),
         }),
         "discord" => Ok(OutboundRequest {
             url: target.url.clone(),
@@ -55,7 +253,10 @@ fn build_request(
                     "timestamp": timestamp,
                 }],
             }),
+            method: reqwest::Method::POST,
             event_header: false,
+            raw_body: None,
+            headers: Vec::new(),
         }),
         "telegram" => {
             // Lift every query param the user configured (chat_id, message_thread_id, …) into
@@ -81,24 +282,241 @@ fn build_request(
             Ok(OutboundRequest {
                 url: base.to_string(),
                 body: Value::Object(payload),
+                method: reqwest::Method::POST,
                 event_header: false,
+                raw_body: None,
+                headers: Vec::new(),
             })
         }
-        _ => Ok(OutboundRequest {
-            url: target.url.clone(),
-            body: json!({
-                "source": "rclone-ui",
-                "version": env!("CARGO_PKG_VERSION"),
-                "event": event.id,
-                "label": event.label,
-                "severity": event.severity,
-                "title": title,
-                "body": body,
-                "timestamp": timestamp,
-                "data": data,
+        "teams" => {
+            // Workflows ("Post to a channel when a webhook request is received") only accepts
+            // Adaptive Cards wrapped in a message envelope.
+            let mut card_body = vec![
+                json!({
+                    "type": "TextBlock",
+                    "text": title,
+                    "weight": "Bolder",
+                    "size": "Medium",
+                    "color": adaptive_card_color(event.severity),
+                    "wrap": true,
+                }),
+                json!({ "type": "TextBlock", "text": body, "wrap": true }),
+            ];
+            let facts = card_facts(data);
+            if !facts.is_empty() {
+                card_body.push(json!({
+                    "type": "FactSet",
+                    "facts": facts
+                        .iter()
+                        .map(|(label, value)| json!({ "title": label, "value": value }))
+                        .collect::<Vec<_>>(),
+                }));
+            }
+            card_body.push(json!({
+                "type": "TextBlock",
+                "text": timestamp,
+                "isSubtle": true,
+                "size": "Small",
+                "wrap": true,
+            }));
+            Ok(OutboundRequest {
+                url: target.url.clone(),
+                body: json!({
+                    "type": "message",
+                    "attachments": [{
+                        "contentType": "application/vnd.microsoft.card.adaptive",
+                        "contentUrl": null,
+                        "content": {
+                            "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                            "type": "AdaptiveCard",
+                            "version": "1.4",
+                            "body": card_body,
+                        },
+                    }],
+                }),
+                method: reqwest::Method::POST,
+                event_header: false,
+                raw_body: None,
+                headers: Vec::new(),
+            })
+        }
+        "googlechat" => {
+            // cardsV2 text widgets take a small HTML subset — escape, then color the body.
+            let mut sections = vec![json!({
+                "widgets": [{
+                    "textParagraph": {
+                        "text": format!(
+                            "<font color=\"#{:06x}\">{}</font>",
+                            discord_color(event.severity),
+                            escape_telegram_html(body)
+                        ),
+                    },
+                }],
+            })];
+            let facts = card_facts(data);
+            if !facts.is_empty() {
+                sections.push(json!({
+                    "widgets": facts
+                        .iter()
+                        .map(|(label, value)| {
+                            json!({ "decoratedText": { "topLabel": label, "text": value } })
+                        })
+                        .collect::<Vec<_>>(),
+                }));
+            }
+            sections.push(json!({
+                "widgets": [{ "textParagraph": { "text": format!("<i>{}</i>", timestamp) } }],
+            }));
+            Ok(OutboundRequest {
+                url: target.url.clone(),
+                body: json!({
+                    "cardsV2": [{
+                        "cardId": format!("rclone-ui-{}", event.id),
+                        "card": {
+                            "header": { "title": title, "subtitle": event.label },
+                            "sections": sections,
+                        },
+                    }],
+                }),
+                method: reqwest::Method::POST,
+                event_header: false,
+                raw_body: None,
+                headers: Vec::new(),
+            })
+        }
+        "matrix" => {
+            // Stored as the homeserver URL with `?room=<room id>&token=<access token>`; sent as
+            // a PUT of an m.room.message event under a fresh transaction id.
+            let parsed = reqwest::Url::parse(&target.url)
+                .map_err(|e| format!("invalid matrix url: {}", e))?;
+            let (homeserver, token) = take_query_param(&parsed, "token");
+            let (mut url, room) = take_query_param(&homeserver, "room");
+            let room = room.ok_or("the matrix url has no room id")?;
+            let token = token.ok_or("the matrix url has no access token")?;
+            url.set_query(None);
+            url.path_segments_mut()
+                .map_err(|_| "invalid matrix url".to_string())?
+                .pop_if_empty()
+                .extend(["_matrix", "client", "v3", "rooms", room.as_str()])
+                .extend([
+                    "send",
+                    "m.room.message",
+                    &uuid::Uuid::new_v4().simple().to_string(),
+                ]);
+            Ok(OutboundRequest {
+                method: reqwest::Method::PUT,
+                url: url.to_string(),
+                body: json!({
+                    "msgtype": "m.text",
+                    "body": format!("{}\n{}", title, body),
+                    "format": "org.matrix.custom.html",
+                    "formatted_body": format!(
+                        "<b><font data-mx-color=\"#{:06x}\">{}</font></b><br>{}",
+                        discord_color(event.severity),
+                        escape_telegram_html(title),
+                        escape_telegram_html(body).replace('\n', "<br>")
+                    ),
+                }),
+                event_header: false,
+                raw_body: None,
+                headers: vec![("Authorization".to_string(), format!("Bearer {}", token))],
+            })
+        }
+        "ntfy" => {
+            // The stored URL is the topic URL (…/<topic>, `?token=` optional); ntfy's JSON
+            // publishing wants the server root with the topic in the body.
+            let parsed =
+                reqwest::Url::parse(&target.url).map_err(|e| format!("invalid ntfy url: {}", e))?;
+            let (mut base, token) = take_query_param(&parsed, "token");
+            let topic = parsed
+                .path_segments()
+                .and_then(|mut segments| segments.rfind(|s| !s.is_empty()))
+                .ok_or("the ntfy url has no topic")?
+                .to_string();
+            base.path_segments_mut()
+                .map_err(|_| "invalid ntfy url".to_string())?
+                .pop_if_empty()
+                .pop()
+                .push("");
+            let (priority, tag) = ntfy_priority(event.severity);
+            Ok(OutboundRequest {
+                url: base.to_string(),
+                body: json!({
+                    "topic": topic,
+                    "title": title,
+                    "message": body,
+                    "priority": priority,
+                    "tags": [tag, event.id],
+                }),
+                method: reqwest::Method::POST,
+                event_header: false,
+                raw_body: None,
+                headers: token
+                    .map(|token| ("Authorization".to_string(), format!("Bearer {}", token)))
+                    .into_iter()
+                    .collect(),
+            })
+        }
+        "gotify" => {
+            let parsed = reqwest::Url::parse(&target.url)
+                .map_err(|e| format!("invalid gotify url: {}", e))?;
+            let (url, token) = take_query_param(&parsed, "token");
+            let token = token.ok_or("the gotify url has no app token")?;
+            let (priority, emoji) = gotify_priority(event.severity);
+            Ok(OutboundRequest {
+                url: url.to_string(),
+                body: json!({
+                    "title": format!("{} {}", emoji, title),
+                    "message": body,
+                    "priority": priority,
+                    "extras": { "client::display": { "contentType": "text/plain" } },
+                }),
+                method: reqwest::Method::POST,
+                event_header: false,
+                raw_body: None,
+                headers: vec![("X-Gotify-Key".to_string(), token)],
+            })
+        }
+        _ => match &target.template {
+            Some(template) => Ok(OutboundRequest {
+                url: target.url.clone(),
+                body: Value::Null,
+                method: template::method(template)?,
+                event_header: true,
+                headers: delivery_headers(),
+                raw_body: Some((
+                    template.content_type.trim().to_string(),
+                    template::render(
+                        template,
+                        &template::Variables {
+                            event,
+                            title,
+                            body,
+                            timestamp,
+                            data,
+                        },
+                    )?,
+                )),
             }),
-            event_header: true,
-        }),
+            None => Ok(OutboundRequest {
+                url: target.url.clone(),
+                body: json!({
+                    "source": "rclone-ui",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "event": event.id,
+                    "label": event.label,
+                    "severity": event.severity,
+                    "title": title,
+                    "body": body,
+                    "timestamp": timestamp,
+                    "data": data,
+                }),
+                method: reqwest::Method::POST,
+                event_header: true,
+                raw_body: None,
+                headers: delivery_headers(),
+            }),
+        },
     }
 }
 
@@ -110,48 +528,250 @@ pub fn http_client() -> reqwest::Client {
         .unwrap_or_default()
 }
 
+/// Whether a target could be sent to, without sending anything: an http(s) URL the provider's
+/// request can be built from, or for email targets SMTP settings a message can be built from.
+pub fn validate(target: &NotificationTarget) -> Result<(), String> {
+    if target.provider == email::PROVIDER {
+        let settings = email::settings(&target.url, None)?;
+        return email::message(
+            &settings,
+            &catalog::TEST_EVENT,
+            "Test notification",
+            "",
+            &Value::Null,
+            &history::now_iso(),
+        )
+        .map(|_| ());
+    }
+    let url = reqwest::Url::parse(&target.url).map_err(|e| format!("invalid url: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("unsupported url scheme '{}'", url.scheme()));
+    }
+    build_request(
+        target,
+        &catalog::TEST_EVENT,
+        "Test notification",
+        "",
+        &Value::Null,
+        &history::now_iso(),
+    )
+    .map(|_| ())
+}
+
 /// One retry after 2s, only on network error or 5xx — 4xx means the endpoint rejected the
-/// request (bad URL, revoked webhook) and retrying only hammers it.
+/// request (bad URL, revoked webhook) and retrying only hammers it. The exception is 429: the
+/// endpoint says when to come back, and that wait is honored (up to 30s, 3 times) without
+/// using up the retry. Every attempt resends the identical request — for Matrix that includes
+/// the transaction id, so a retried message is never posted twice. Failures the outbox could
+/// still get through later (network, 5xx, 408, 429) come back retryable.
 fn send_once(
     client: &reqwest::Client,
     request: &OutboundRequest,
     event_id: &str,
-) -> Result<(), String> {
+) -> Result<(), DeliveryError> {
     let mut attempt = 0;
+    let mut rate_limited = 0;
     loop {
         attempt += 1;
         let result = tauri::async_runtime::block_on(async {
-            let mut req = client.post(&request.url).json(&request.body);
+            let mut req = client.request(request.method.clone(), &request.url);
+            req = match &request.raw_body {
+                Some((content_type, text)) => req
+                    .header(reqwest::header::CONTENT_TYPE, content_type)
+                    .body(text.clone()),
+                None => req.json(&request.body),
+            };
             if request.event_header {
                 req = req.header("X-RcloneUI-Event", event_id);
             }
+            for (name, value) in &request.headers {
+                req = req.header(name, value);
+            }
             req.send().await
         });
 
         match result {
             Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response)
+                if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    && rate_limited < MAX_RATE_LIMIT_RETRIES =>
+            {
+                let headers = response.headers().clone();
+                let body = tauri::async_runtime::block_on(response.text()).unwrap_or_default();
+                std::thread::sleep(rate_limit_delay(&headers, &body));
+                rate_limited += 1;
+                attempt -= 1;
+                continue;
+            }
             Ok(response) => {
                 let status = response.status().as_u16();
                 if status >= 500 && attempt < 2 {
-                    std::thread::sleep(std::time::Duration::from_secs(2));
+                    std::thread::sleep(Duration::from_secs(2));
                     continue;
                 }
-                return Err(format!("Webhook responded with status {}", status));
+                let message = format!("Webhook responded with status {}", status);
+                return Err(if status >= 500 || status == 408 || status == 429 {
+                    DeliveryError::transient(message)
+                } else {
+                    DeliveryError::permanent(message)
+                });
             }
             Err(e) => {
                 if attempt < 2 {
-                    std::thread::sleep(std::time::Duration::from_secs(2));
+                    std::thread::sleep(Duration::from_secs(2));
                     continue;
                 }
-                return Err(format!("{}", e));
+                return Err(DeliveryError::transient(format!("{}", e)));
             }
         }
     }
 }
 
-/// Sends `event_id` to every enabled target subscribed to it and records lastSentAt/lastError
-/// per target. Never fails the caller — delivery errors come back as log lines. Targets are
-/// read at fire time; the store lock is NOT held during the sends (up to ~17s each).
+/// One notification to one target. `id` is the delivery id (a generic webhook's
+/// `X-RcloneUI-Delivery`, a Matrix transaction id): a fresh one from `dispatch`, the queued one
+/// from an outbox retry.
+#[derive(Clone)]
+pub(super) struct Delivery {
+    pub target: NotificationTarget,
+    pub event: &'static EventMeta,
+    pub title: String,
+    pub body: String,
+    pub data: Value,
+    pub timestamp: String,
+    pub id: String,
+}
+
+/// Swaps the transaction id (the last path segment) of a Matrix send url for the delivery id:
+/// an outbox retry of a message that did get through is then deduplicated by the homeserver.
+fn with_matrix_txn_id(url: &str, id: &str) -> String {
+    let Ok(mut parsed) = reqwest::Url::parse(url) else {
+        return url.to_string();
+    };
+    if let Ok(mut segments) = parsed.path_segments_mut() {
+        segments.pop().push(id);
+    }
+    parsed.to_string()
+}
+
+fn deliver(
+    dirs: &AppDirs,
+    client: &reqwest::Client,
+    delivery: &Delivery,
+) -> Result<(), DeliveryError> {
+    let Delivery {
+        target,
+        event,
+        title,
+        body,
+        data,
+        timestamp,
+        id,
+    } = delivery;
+    if target.provider == email::PROVIDER {
+        let login = email::login(dirs, target.auth.as_ref()).map_err(DeliveryError::permanent)?;
+        return email::send(&target.url, login, event, title, body, data, timestamp);
+    }
+    let mut request = build_request(target, event, title, body, data, timestamp)
+        .map_err(|e| DeliveryError::permanent(format!("could not build the request: {}", e)))?;
+    for (name, value) in request.headers.iter_mut() {
+        if name == signing::DELIVERY_HEADER {
+            *value = id.clone();
+        }
+    }
+    if target.provider == "matrix" {
+        request.url = with_matrix_txn_id(&request.url, id);
+    }
+    apply_target_headers(dirs, target, &mut request).map_err(DeliveryError::permanent)?;
+    if let Some(sealed) = &target.signing_secret {
+        let secret = signing::open(dirs, sealed).map_err(DeliveryError::permanent)?;
+        sign_request(&mut request, &secret);
+    }
+    send_once(client, &request, event.id)
+}
+
+/// Sends every delivery on its own thread, so one dead endpoint (~17s of timeouts and retry)
+/// can't hold up the rest, and returns their results in order — `None` for one still in flight
+/// when `budget` runs out. Nothing waits for that thread: the delivery is queued in the outbox
+/// under a claim (outbox::hold_in_flight) and the thread settles it when its send finishes, so
+/// it is neither sent twice nor recorded as failed when it got through late.
+pub(super) fn deliver_all(
+    dirs: &AppDirs,
+    client: &reqwest::Client,
+    deliveries: &[Delivery],
+    budget: Duration,
+) -> Vec<Option<Result<(), DeliveryError>>> {
+    let deadline = std::time::Instant::now() + budget;
+    // Whether results still go to the caller; flipped, under the lock, once it stops waiting.
+    let waiting = std::sync::Arc::new(std::sync::Mutex::new(true));
+    let (tx, rx) = std::sync::mpsc::channel();
+    for (index, delivery) in deliveries.iter().enumerate() {
+        let (tx, waiting, dirs, client, delivery) = (
+            tx.clone(),
+            waiting.clone(),
+            dirs.clone(),
+            client.clone(),
+            delivery.clone(),
+        );
+        std::thread::spawn(move || {
+            let result = deliver(&dirs, &client, &delivery);
+            let waiting = waiting.lock().unwrap_or_else(|e| e.into_inner());
+            if *waiting {
+                let _ = tx.send((index, result));
+            } else {
+                drop(waiting);
+                outbox::settle_late(&dirs, &delivery, result);
+            }
+        });
+    }
+    drop(tx);
+
+    let mut results: Vec<Option<Result<(), DeliveryError>>> = vec![None; deliveries.len()];
+    let mut received = 0;
+    while received < deliveries.len() {
+        let left = deadline.saturating_duration_since(std::time::Instant::now());
+        let Ok((index, result)) = rx.recv_timeout(left) else {
+            break;
+        };
+        results[index] = Some(result);
+        received += 1;
+    }
+    if received < deliveries.len() {
+        // Held until the stragglers are queued, so none can settle an entry not yet written.
+        let mut waiting = waiting.lock().unwrap_or_else(|e| e.into_inner());
+        while let Ok((index, result)) = rx.try_recv() {
+            results[index] = Some(result);
+        }
+        let in_flight: Vec<&Delivery> = deliveries
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_none())
+            .map(|(delivery, _)| delivery)
+            .collect();
+        match outbox::hold_in_flight(dirs, &in_flight) {
+            Ok(()) => *waiting = false,
+            // Reported as failed, so `settle` queues them itself; `waiting` stays set, so their
+            // threads' results go to the dropped channel rather than settling entries they
+            // don't own.
+            Err(e) => {
+                for result in results.iter_mut().filter(|result| result.is_none()) {
+                    *result = Some(Err(DeliveryError::transient(format!(
+                        "no response within the {}s time budget, and it could not be queued: {}",
+                        budget.as_secs(),
+                        e
+                    ))));
+                }
+            }
+        }
+    }
+    results
+}
+
+/// Sends `event_id` to every enabled target subscribed to it whose filters (filters.rs) take
+/// the event — all at once, waiting at most `budget` — and records lastSentAt/lastError per
+/// target. Never fails the caller — delivery errors come back as log lines. Targets are read at
+/// fire time; the store lock is NOT held during the sends. Targets that deduplicate or digest
+/// (digest.rs) may hold the event back. Failures that may pass are queued in the outbox for a
+/// later retry.
 pub fn dispatch(
     dirs: &AppDirs,
     client: &reqwest::Client,
@@ -159,6 +779,7 @@ pub fn dispatch(
     title: &str,
     body: &str,
     data: Value,
+    budget: Duration,
 ) -> Vec<String> {
     let Some(event) = catalog::find(event_id) else {
         return vec![format!("unknown notification event '{}'", event_id)];
@@ -170,75 +791,215 @@ pub fn dispatch(
     };
 
     let timestamp = history::now_iso();
+    filters::record_recent(
+        dirs,
+        RecentEvent {
+            event_id: event_id.to_string(),
+            title: title.to_string(),
+            body: body.to_string(),
+            timestamp: timestamp.clone(),
+            data: data.clone(),
+        },
+    );
+    let deliveries: Vec<Delivery> = targets
+        .into_iter()
+        .filter(|t| t.is_enabled && t.events.iter().any(|e| e == event_id))
+        .filter(|t| filters::matches(&t.filters, event, &data))
+        .map(|target| Delivery {
+            target,
+            event,
+            title: title.to_string(),
+            body: body.to_string(),
+            data: data.clone(),
+            timestamp: timestamp.clone(),
+            id: uuid::Uuid::new_v4().to_string(),
+        })
+        .collect();
+    let deliveries = digest::hold(dirs, deliveries);
+    let results = deliver_all(dirs, client, &deliveries, budget);
+    settle(dirs, deliveries, results)
+}
+
+/// Logs and records the results of first attempts, queueing the failures that may pass in the
+/// outbox. Deliveries still in flight are already queued, and settled by their own thread.
+pub(super) fn settle(
+    dirs: &AppDirs,
+    deliveries: Vec<Delivery>,
+    results: Vec<Option<Result<(), DeliveryError>>>,
+) -> Vec<String> {
     let mut log_lines = Vec::new();
     let mut outcomes: Vec<(String, Option<String>)> = Vec::new();
-
-    for target in &targets {
-        if !target.is_enabled || !target.events.iter().any(|e| e == event_id) {
+    let mut failed: Vec<PendingDelivery> = Vec::new();
+    for (delivery, result) in deliveries.into_iter().zip(results) {
+        let target = delivery.target;
+        let (kind, name) = if target.provider == email::PROVIDER {
+            ("email", &target.name)
+        } else {
+            ("webhook", &target.provider)
+        };
+        let Some(result) = result else {
+            log_lines.push(format!(
+                "{} delivery ({}) still sending when the time budget ran out — its outcome is \
+                 recorded when it finishes",
+                kind, name
+            ));
             continue;
-        }
-
-        let request = match build_request(target, event, title, body, &data, &timestamp) {
-            Ok(r) => r,
-            Err(e) => {
-                log_lines.push(format!("webhook build failed ({}): {}", target.provider, e));
-                outcomes.push((target.id.clone(), Some(e)));
-                continue;
-            }
         };
-
-        match send_once(client, &request, event_id) {
-            Ok(()) => outcomes.push((target.id.clone(), None)),
-            Err(e) => {
-                log_lines.push(format!(
-                    "webhook delivery failed ({}): {}",
-                    target.provider, e
-                ));
-                outcomes.push((target.id.clone(), Some(e)));
-            }
+        let Err(error) = result else {
+            outcomes.push((target.id, None));
+            continue;
+        };
+        let queued = if error.retryable {
+            " — queued for retry"
+        } else {
+            ""
+        };
+        log_lines.push(format!(
+            "{} delivery failed ({}): {}{}",
+            kind, name, error.message, queued
+        ));
+        if error.retryable {
+            let now = outbox::now_ms();
+            failed.push(PendingDelivery {
+                id: delivery.id,
+                target_id: target.id.clone(),
+                target_name: target.name.clone(),
+                event_id: delivery.event.id.to_string(),
+                title: delivery.title,
+                body: delivery.body,
+                data: delivery.data,
+                timestamp: delivery.timestamp,
+                created_at: now,
+                attempts: 1,
+                next_attempt_at: now + outbox::backoff_ms(1),
+                last_error: error.message.clone(),
+            });
         }
+        outcomes.push((target.id, Some(error.message)));
     }
 
+    if let Err(e) = outbox::enqueue(dirs, failed) {
+        log_lines.push(format!("failed to queue deliveries for retry: {}", e));
+    }
     targets::record_outcomes(dirs, &outcomes);
     log_lines
 }
 
+/// The drawer's (possibly unsaved) values for `send_test`. Secrets come in plain text; a
+/// missing or redacted one falls back to the saved target's stored secret.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestTarget {
+    pub provider: String,
+    pub url: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub template: Option<PayloadTemplate>,
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+    #[serde(default)]
+    pub headers: Vec<TargetHeader>,
+    #[serde(default)]
+    pub auth: Option<TargetAuth>,
+}
+
 /// Sends the synthetic test payload to one target — which may be unsaved drawer values (no
-/// `target_id`). Propagates the delivery error so the UI can surface it; records the outcome
-/// only when the target already exists.
-pub fn send_test(
-    dirs: &AppDirs,
-    provider: &str,
-    url: &str,
-    target_id: Option<&str>,
-    name: Option<&str>,
-) -> Result<(), String> {
+/// `id`). Propagates the delivery error so the UI can surface it; records the outcome only when
+/// the target already exists.
+pub fn send_test(dirs: &AppDirs, draft: TestTarget) -> Result<(), String> {
     let event = &catalog::TEST_EVENT;
-    let body = match name {
+    let target_id = draft.id.filter(|id| !id.is_empty());
+    let body = match draft.name.as_deref() {
         Some(n) if !n.is_empty() => {
             format!("This is a test notification from Rclone UI for \"{}\".", n)
         }
         _ => "This is a test notification from Rclone UI.".to_string(),
     };
-    // A throwaway shell: build_request only reads provider + url from the target.
-    let probe = NotificationTarget {
-        id: target_id.unwrap_or_default().to_string(),
-        provider: provider.to_string(),
-        name: name.unwrap_or_default().to_string(),
-        url: url.to_string(),
-        is_enabled: true,
-        events: Vec::new(),
-        created_at: 0,
-        last_sent_at: None,
-        last_error: None,
-    };
     let timestamp = history::now_iso();
-    let request = build_request(&probe, event, "Test notification", &body, &Value::Null, &timestamp)?;
+    let stored = match &target_id {
+        Some(id) => targets::load(dirs)?.into_iter().find(|t| &t.id == id),
+        None => None,
+    };
+    let open_stored =
+        |sealed: Option<&String>| sealed.map(|sealed| signing::open(dirs, sealed)).transpose();
+    let fresh = |secret: Option<String>| secret.filter(|s| s != targets::REDACTED);
 
-    let client = http_client();
-    let result = send_once(&client, &request, event.id);
-    if let Some(id) = target_id.filter(|id| !id.is_empty()) {
-        targets::record_outcomes(dirs, &[(id.to_string(), result.clone().err())]);
+    let auth_secret = match &draft.auth {
+        Some(auth) => match fresh(auth.secret.clone()) {
+            Some(secret) => secret,
+            None => open_stored(
+                stored
+                    .as_ref()
+                    .and_then(|t| t.auth.as_ref())
+                    .filter(|stored| stored.mode == auth.mode)
+                    .and_then(|stored| stored.secret.as_ref()),
+            )?
+            .unwrap_or_default(),
+        },
+        None => String::new(),
+    };
+    let result = if draft.provider == email::PROVIDER {
+        let login = draft
+            .auth
+            .as_ref()
+            .map(|auth| (auth.username.clone(), auth_secret));
+        email::send(
+            &draft.url,
+            login,
+            event,
+            "Test notification",
+            &body,
+            &Value::Null,
+            &timestamp,
+        )
+        .map_err(|e| e.message)
+    } else {
+        // A throwaway shell: build_request only reads provider, url and template from it.
+        let probe = NotificationTarget {
+            id: target_id.clone().unwrap_or_default(),
+            provider: draft.provider.clone(),
+            name: draft.name.clone().unwrap_or_default(),
+            url: draft.url.clone(),
+            is_enabled: true,
+            events: Vec::new(),
+            created_at: 0,
+            last_sent_at: None,
+            last_error: None,
+            template: draft.template,
+            signing_secret: None,
+            headers: Vec::new(),
+            auth: None,
+            filters: Default::default(),
+            delivery: Default::default(),
+        };
+        let mut request = build_request(
+            &probe,
+            event,
+            "Test notification",
+            &body,
+            &Value::Null,
+            &timestamp,
+        )?;
+
+        let auth = draft.auth.as_ref().map(|auth| (auth, auth_secret.as_str()));
+        request
+            .headers
+            .extend(headers::request_headers(&draft.headers, auth));
+
+        let signing_secret = match fresh(draft.signing_secret) {
+            Some(secret) => Some(secret),
+            None => open_stored(stored.as_ref().and_then(|t| t.signing_secret.as_ref()))?,
+        };
+        if let Some(secret) = signing_secret {
+            sign_request(&mut request, &secret);
+        }
+        send_once(&http_client(), &request, event.id).map_err(|e| e.message)
+    };
+    if let Some(id) = target_id {
+        targets::record_outcomes(dirs, &[(id, result.clone().err())]);
     }
     result
 }
@@ -259,34 +1020,47 @@ mod tests {
 
     /// Minimal one-shot HTTP receiver: accepts a single request, captures head+body, replies 200.
     fn local_receiver() -> (String, std::sync::mpsc::Receiver<String>) {
+        scripted_receiver(vec!["HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n"])
+    }
+
+    /// Answers one request per scripted reply (each on its own connection), capturing each.
+    fn scripted_receiver(
+        replies: Vec<&'static str>,
+    ) -> (String, std::sync::mpsc::Receiver<String>) {
         use std::io::{Read, Write};
         let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
         let url = format!("http://{}/hook", listener.local_addr().unwrap());
         let (tx, rx) = std::sync::mpsc::channel();
         std::thread::spawn(move || {
-            let (mut stream, _) = listener.accept().unwrap();
-            let mut buf = [0u8; 16384];
-            let mut captured = Vec::new();
-            // Read until the body announced by Content-Length is complete.
-            loop {
-                let n = stream.read(&mut buf).unwrap_or(0);
-                if n == 0 {
-                    break;
-                }
-                captured.extend_from_slice(&buf[..n]);
-                let text = String::from_utf8_lossy(&captured);
-                if let Some(head_end) = text.find("\r\n\r\n") {
-                    let content_length = text
-                        .lines()
-                        .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap_or(0)))
-                        .unwrap_or(0);
-                    if captured.len() >= head_end + 4 + content_length {
+            for reply in replies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 16384];
+                let mut captured = Vec::new();
+                // Read until the body announced by Content-Length is complete.
+                loop {
+                    let n = stream.read(&mut buf).unwrap_or(0);
+                    if n == 0 {
                         break;
                     }
+                    captured.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&captured);
+                    if let Some(head_end) = text.find("\r\n\r\n") {
+                        let content_length = text
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                            })
+                            .unwrap_or(0);
+                        if captured.len() >= head_end + 4 + content_length {
+                            break;
+                        }
+                    }
                 }
+                let _ = stream.write_all(reply.as_bytes());
+                let _ = tx.send(String::from_utf8_lossy(&captured).into_owned());
             }
-            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n");
-            let _ = tx.send(String::from_utf8_lossy(&captured).into_owned());
         });
         (url, rx)
     }
@@ -307,6 +1081,12 @@ mod tests {
                 url,
                 is_enabled: true,
                 events: vec!["schedule.completed".into()],
+                template: None,
+                signing_secret: None,
+                headers: Vec::new(),
+                auth: None,
+                filters: Default::default(),
+                delivery: Default::default(),
             },
         )
         .unwrap();
@@ -319,6 +1099,12 @@ mod tests {
                 url: "http://127.0.0.1:9/never".into(),
                 is_enabled: true,
                 events: vec!["job.failed".into()],
+                template: None,
+                signing_secret: None,
+                headers: Vec::new(),
+                auth: None,
+                filters: Default::default(),
+                delivery: Default::default(),
             },
         )
         .unwrap();
@@ -331,6 +1117,7 @@ mod tests {
             "Scheduled task completed",
             "backup completed successfully",
             json!({ "scheduleId": "s1" }),
+            Duration::from_secs(30),
         );
         assert!(lines.is_empty(), "no delivery errors expected: {:?}", lines);
 
@@ -371,12 +1158,26 @@ mod tests {
                 url: dead_url,
                 is_enabled: true,
                 events: vec!["schedule.failed".into()],
+                template: None,
+                signing_secret: None,
+                headers: Vec::new(),
+                auth: None,
+                filters: Default::default(),
+                delivery: Default::default(),
             },
         )
         .unwrap();
 
         let client = http_client();
-        let lines = dispatch(&dirs, &client, "schedule.failed", "T", "B", Value::Null);
+        let lines = dispatch(
+            &dirs,
+            &client,
+            "schedule.failed",
+            "T",
+            "B",
+            Value::Null,
+            Duration::from_secs(30),
+        );
         assert_eq!(lines.len(), 1);
         assert!(lines[0].contains("webhook delivery failed"));
 
@@ -388,6 +1189,89 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dirs.app_data);
     }
 
+    /// Targets are sent to at the same time: an endpoint that never answers costs the dispatch
+    /// its budget, not ~17s of timeouts, and the others are delivered and recorded meanwhile.
+    /// The straggler is queued under a claim and settled by its own thread once it fails.
+    #[test]
+    fn a_slow_target_cannot_delay_the_others() {
+        let dirs = test_dirs("parallel");
+        // Connections queue in the backlog and are never answered.
+        let silent = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let (first_url, first) = local_receiver();
+        let (second_url, second) = local_receiver();
+        let add = |name: &str, url: String| {
+            targets::add(
+                &dirs,
+                targets::NewTarget {
+                    provider: "webhook".into(),
+                    name: name.into(),
+                    url,
+                    is_enabled: true,
+                    events: vec!["schedule.failed".into()],
+                    template: None,
+                    signing_secret: None,
+                    headers: Vec::new(),
+                    auth: None,
+                    filters: Default::default(),
+                    delivery: Default::default(),
+                },
+            )
+            .unwrap()
+        };
+        let slow = add(
+            "Slow",
+            format!("http://{}/hook", silent.local_addr().unwrap()),
+        );
+        add("First", first_url);
+        add("Second", second_url);
+
+        let started = std::time::Instant::now();
+        let lines = dispatch(
+            &dirs,
+            &http_client(),
+            "schedule.failed",
+            "T",
+            "B",
+            json!({}),
+            Duration::from_secs(2),
+        );
+        let elapsed = started.elapsed();
+        assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+        assert_eq!(lines.len(), 1, "{:?}", lines);
+        assert!(lines[0].contains("still sending when the time budget ran out"));
+        first.recv_timeout(Duration::from_secs(1)).unwrap();
+        second.recv_timeout(Duration::from_secs(1)).unwrap();
+
+        for target in targets::load(&dirs).unwrap() {
+            assert_eq!(target.last_sent_at.is_some(), target.id != slow.id);
+            assert!(target.last_error.is_none());
+        }
+        let queued = outbox::list(&dirs).unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].target_id, slow.id);
+        assert_eq!(queued[0].attempts, 0);
+        assert!(queued[0].next_attempt_at > outbox::now_ms() + 60_000);
+
+        // Refused from now on: the send fails late, and its thread backs the entry off and
+        // records the error — without a second entry.
+        drop(silent);
+        let settled = std::time::Instant::now();
+        while outbox::list(&dirs).unwrap()[0].attempts == 0 {
+            assert!(settled.elapsed() < Duration::from_secs(40));
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        let queued = outbox::list(&dirs).unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].attempts, 1);
+        let slow = targets::load(&dirs)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.id == slow.id)
+            .unwrap();
+        assert!(slow.last_error.is_some());
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+
     fn target(provider: &str, url: &str) -> NotificationTarget {
         NotificationTarget {
             id: "t1".into(),
@@ -399,6 +1283,12 @@ mod tests {
             created_at: 0,
             last_sent_at: None,
             last_error: None,
+            template: None,
+            signing_secret: None,
+            headers: Vec::new(),
+            auth: None,
+            filters: Default::default(),
+            delivery: Default::default(),
         }
     }
 
@@ -468,4 +1358,465 @@ mod tests {
         assert_eq!(generic.body["timestamp"], ts);
         assert_eq!(generic.body["data"]["scheduleId"], "s1");
     }
+
+    /// What a receiver would do: check the credentials, recompute the HMAC over
+    /// `<timestamp>.<raw body>` with the shared secret and compare it to the signature header.
+    #[test]
+    fn signed_authenticated_deliveries_verify_against_a_local_receiver() {
+        let dirs = test_dirs("signed");
+        let (url, rx) = scripted_receiver(vec![
+            "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\n\r\n",
+            "HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n",
+        ]);
+        let secret = "whsec_local_receiver_1";
+        let added = targets::add(
+            &dirs,
+            targets::NewTarget {
+                provider: "webhook".into(),
+                name: "Signed".into(),
+                url,
+                is_enabled: true,
+                events: vec!["schedule.failed".into()],
+                template: None,
+                signing_secret: Some(secret.into()),
+                headers: vec![TargetHeader {
+                    name: "X-Tenant".into(),
+                    value: "ops".into(),
+                }],
+                auth: Some(TargetAuth {
+                    mode: headers::AuthMode::Basic,
+                    username: "alerts".into(),
+                    header: String::new(),
+                    secret: Some("s3cret".into()),
+                }),
+                filters: Default::default(),
+                delivery: Default::default(),
+            },
+        )
+        .unwrap();
+        let stored = std::fs::read_to_string(dirs.app_data.join("notifications/targets.json"));
+        let stored = stored.unwrap();
+        assert!(!stored.contains(secret) && !stored.contains("s3cret"));
+        let listed = targets::redacted(targets::load(&dirs).unwrap().remove(0));
+        assert_eq!(listed.signing_secret.as_deref(), Some(targets::REDACTED));
+        assert_eq!(
+            listed.auth.unwrap().secret.as_deref(),
+            Some(targets::REDACTED)
+        );
+
+        let client = http_client();
+        let lines = dispatch(
+            &dirs,
+            &client,
+            "schedule.failed",
+            "Scheduled task failed",
+            "boom",
+            json!({ "scheduleId": "s1" }),
+            Duration::from_secs(30),
+        );
+        assert!(lines.is_empty(), "{:?}", lines);
+
+        let header = |raw: &str, name: &str| {
+            raw.split("\r\n\r\n")
+                .next()
+                .unwrap()
+                .lines()
+                .find_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    key.eq_ignore_ascii_case(name)
+                        .then(|| value.trim().to_string())
+                })
+        };
+        let first = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        let retried = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        for raw in [&first, &retried] {
+            let body = raw.split("\r\n\r\n").nth(1).unwrap();
+            let timestamp: u64 = header(raw, "x-rcloneui-timestamp")
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert_eq!(
+                header(raw, "x-rcloneui-signature").unwrap(),
+                format!(
+                    "sha256={}",
+                    signing::signature(secret, timestamp, body.as_bytes())
+                )
+            );
+            assert_ne!(
+                header(raw, "x-rcloneui-signature").unwrap(),
+                format!(
+                    "sha256={}",
+                    signing::signature("wrong", timestamp, body.as_bytes())
+                )
+            );
+            assert_eq!(serde_json::from_str::<Value>(body).unwrap()["body"], "boom");
+            assert_eq!(header(raw, "x-tenant").unwrap(), "ops");
+            assert_eq!(
+                header(raw, "authorization").unwrap(),
+                "Basic YWxlcnRzOnMzY3JldA=="
+            );
+        }
+        // The retry is the same delivery: a receiver that saw the first can drop it.
+        let delivery = header(&first, "x-rcloneui-delivery").unwrap();
+        assert_eq!(
+            uuid::Uuid::parse_str(&delivery).unwrap().to_string(),
+            delivery
+        );
+        assert_eq!(header(&retried, "x-rcloneui-delivery").unwrap(), delivery);
+
+        assert_eq!(targets::load(&dirs).unwrap()[0].id, added.id);
+        assert_eq!(targets::load(&dirs).unwrap()[0].last_error, None);
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+
+    #[test]
+    fn templated_webhooks_send_the_rendered_body_with_their_method_and_content_type() {
+        let (url, rx) = local_receiver();
+        let mut templated = target("webhook", &url);
+        templated.template = Some(PayloadTemplate {
+            body: r#"{"routing_key": "k", "payload": {"summary": "{{title}}", "ms": {{data.durationMs}}}}"#
+                .into(),
+            content_type: "application/vnd.incident+json".into(),
+            method: "PUT".into(),
+        });
+        let request = build_request(
+            &templated,
+            catalog::find("schedule.failed").unwrap(),
+            "Backup \"photos\" failed",
+            "",
+            &json!({ "durationMs": 4200 }),
+            "2026-01-01T00:00:00.000Z",
+        )
+        .unwrap();
+        send_once(&http_client(), &request, "schedule.failed").unwrap();
+
+        let captured = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(captured.starts_with("PUT /hook "));
+        assert!(captured
+            .to_lowercase()
+            .contains("content-type: application/vnd.incident+json"));
+        assert!(captured.contains("x-rcloneui-event: schedule.failed"));
+        assert!(captured.ends_with(
+            r#"{"routing_key": "k", "payload": {"summary": "Backup \"photos\" failed", "ms": 4200}}"#
+        ));
+    }
+
+    #[test]
+    fn card_payloads_are_byte_stable() {
+        let event = catalog::find("schedule.failed").unwrap();
+        let ts = "2026-01-01T00:00:00.000Z";
+        let data = json!({
+            "scheduleId": "s1",
+            "task": "Nightly <photos>",
+            "operation": "sync",
+            "durationMs": 83_000,
+            "bytes": 1_610_612_736u64,
+        });
+
+        let teams = build_request(
+            &target(
+                "teams",
+                "https://prod-01.westus.logic.azure.com/workflows/x",
+            ),
+            event,
+            "Scheduled task failed",
+            "job failed: boom",
+            &data,
+            ts,
+        )
+        .unwrap();
+        assert!(!teams.event_header);
+        assert_eq!(
+            teams.body,
+            json!({
+                "type": "message",
+                "attachments": [{
+                    "contentType": "application/vnd.microsoft.card.adaptive",
+                    "contentUrl": null,
+                    "content": {
+                        "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                        "type": "AdaptiveCard",
+                        "version": "1.4",
+                        "body": [
+                            {
+                                "type": "TextBlock",
+                                "text": "Scheduled task failed",
+                                "weight": "Bolder",
+                                "size": "Medium",
+                                "color": "Attention",
+                                "wrap": true,
+                            },
+                            { "type": "TextBlock", "text": "job failed: boom", "wrap": true },
+                            {
+                                "type": "FactSet",
+                                "facts": [
+                                    { "title": "Task", "value": "Nightly <photos>" },
+                                    { "title": "Operation", "value": "sync" },
+                                    { "title": "Duration", "value": "1m 23s" },
+                                    { "title": "Transferred", "value": "1.5 GiB" },
+                                ],
+                            },
+                            {
+                                "type": "TextBlock",
+                                "text": ts,
+                                "isSubtle": true,
+                                "size": "Small",
+                                "wrap": true,
+                            },
+                        ],
+                    },
+                }],
+            })
+        );
+
+        let chat = build_request(
+            &target(
+                "googlechat",
+                "https://chat.googleapis.com/v1/spaces/AAA/messages?key=k&token=t",
+            ),
+            event,
+            "Scheduled task failed",
+            "quota <exceeded> & more",
+            &data,
+            ts,
+        )
+        .unwrap();
+        assert_eq!(
+            chat.url,
+            "https://chat.googleapis.com/v1/spaces/AAA/messages?key=k&token=t"
+        );
+        assert_eq!(
+            chat.body,
+            json!({
+                "cardsV2": [{
+                    "cardId": "rclone-ui-schedule.failed",
+                    "card": {
+                        "header": {
+                            "title": "Scheduled task failed",
+                            "subtitle": "Scheduled task failed",
+                        },
+                        "sections": [
+                            {
+                                "widgets": [{
+                                    "textParagraph": {
+                                        "text": "<font color=\"#e74c3c\">\
+                                                 quota &lt;exceeded&gt; &amp; more</font>",
+                                    },
+                                }],
+                            },
+                            {
+                                "widgets": [
+                                    { "decoratedText": {
+                                        "topLabel": "Task", "text": "Nightly <photos>" } },
+                                    { "decoratedText": {
+                                        "topLabel": "Operation", "text": "sync" } },
+                                    { "decoratedText": {
+                                        "topLabel": "Duration", "text": "1m 23s" } },
+                                    { "decoratedText": {
+                                        "topLabel": "Transferred", "text": "1.5 GiB" } },
+                                ],
+                            },
+                            {
+                                "widgets": [{
+                                    "textParagraph": { "text": format!("<i>{}</i>", ts) },
+                                }],
+                            },
+                        ],
+                    },
+                }],
+            })
+        );
+
+        // Events without run data (e.g. app updates) get no facts section at all.
+        let bare = build_request(
+            &target("teams", "https://example.com/workflows/x"),
+            event,
+            "T",
+            "B",
+            &Value::Null,
+            ts,
+        )
+        .unwrap();
+        assert_eq!(
+            bare.body["attachments"][0]["content"]["body"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    /// Matrix: a PUT under a transaction id, and a 429 waited out for its `retry_after_ms` —
+    /// then resent under the SAME transaction id, so the homeserver can dedupe it.
+    #[test]
+    fn matrix_waits_out_rate_limits_and_retries_idempotently() {
+        let (url, rx) = scripted_receiver(vec![
+            "HTTP/1.1 429 Too Many Requests\r\ncontent-type: application/json\r\n\
+             connection: close\r\ncontent-length: 51\r\n\r\n\
+             {\"errcode\":\"M_LIMIT_EXCEEDED\",\"retry_after_ms\":400}",
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+             connection: close\r\ncontent-length: 17\r\n\r\n{\"event_id\":\"$e\"}",
+        ]);
+        let request = build_request(
+            &target(
+                "matrix",
+                &format!("{}?room=%21ops%3Aexample.org&token=syt_abc", url),
+            ),
+            catalog::find("schedule.failed").unwrap(),
+            "Scheduled task failed",
+            "job <1>\nfailed",
+            &Value::Null,
+            "2026-01-01T00:00:00.000Z",
+        )
+        .unwrap();
+        assert_eq!(request.method, reqwest::Method::PUT);
+        assert_eq!(
+            request.body["body"],
+            "Scheduled task failed\njob <1>\nfailed"
+        );
+        assert_eq!(
+            request.body["formatted_body"],
+            "<b><font data-mx-color=\"#e74c3c\">Scheduled task failed</font></b>\
+             <br>job &lt;1&gt;<br>failed"
+        );
+
+        let started = std::time::Instant::now();
+        send_once(&http_client(), &request, "schedule.failed").unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(400));
+
+        let first = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        let second = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        let request_line = |raw: &str| raw.lines().next().unwrap().to_string();
+        assert!(request_line(&first).starts_with(
+            "PUT /hook/_matrix/client/v3/rooms/!ops:example.org/send/m.room.message/"
+        ));
+        assert_eq!(request_line(&first), request_line(&second));
+        assert!(first
+            .to_lowercase()
+            .contains("authorization: bearer syt_abc"));
+        assert!(!first.contains("token="));
+    }
+
+    #[test]
+    fn matrix_transaction_ids_follow_the_delivery_id() {
+        let dirs = test_dirs("matrix-txn");
+        let ok = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                  connection: close\r\ncontent-length: 17\r\n\r\n{\"event_id\":\"$e\"}";
+        let (url, rx) = scripted_receiver(vec![ok, ok]);
+        let delivery = Delivery {
+            target: target(
+                "matrix",
+                &format!("{}?room=%21ops%3Aexample.org&token=syt", url),
+            ),
+            event: catalog::find("schedule.failed").unwrap(),
+            title: "Scheduled task failed".into(),
+            body: String::new(),
+            data: Value::Null,
+            timestamp: "2026-01-01T00:00:00.000Z".into(),
+            id: "d-42".into(),
+        };
+        deliver(&dirs, &http_client(), &delivery).unwrap();
+        deliver(&dirs, &http_client(), &delivery).unwrap();
+        for _ in 0..2 {
+            let raw = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+            assert!(raw.starts_with(
+                "PUT /hook/_matrix/client/v3/rooms/!ops:example.org/send/m.room.message/d-42 "
+            ));
+        }
+    }
+
+    #[test]
+    fn rate_limit_delay_ignores_unusable_retry_after_values() {
+        let with_header = |value: &str| {
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(reqwest::header::RETRY_AFTER, value.parse().unwrap());
+            headers
+        };
+        let none = reqwest::header::HeaderMap::new();
+        for bad in ["-5", "NaN", "inf"] {
+            assert_eq!(
+                rate_limit_delay(&with_header(bad), ""),
+                Duration::from_secs(2)
+            );
+        }
+        assert_eq!(
+            rate_limit_delay(&with_header("1e9"), ""),
+            MAX_RATE_LIMIT_WAIT
+        );
+        assert_eq!(
+            rate_limit_delay(&with_header("1.5"), ""),
+            Duration::from_millis(1500)
+        );
+        let telegram = r#"{"ok":false,"parameters":{"retry_after":-3}}"#;
+        assert_eq!(rate_limit_delay(&none, telegram), Duration::from_secs(2));
+        let telegram = r#"{"ok":false,"parameters":{"retry_after":7}}"#;
+        assert_eq!(rate_limit_delay(&none, telegram), Duration::from_secs(7));
+    }
+
+    #[test]
+    fn ntfy_and_gotify_map_severity_and_move_tokens_to_headers() {
+        let failed = catalog::find("schedule.failed").unwrap();
+        let ts = "2026-01-01T00:00:00.000Z";
+
+        let ntfy = build_request(
+            &target("ntfy", "https://push.example.com/ntfy/backups?token=tk_abc"),
+            failed,
+            "Scheduled task failed",
+            "job failed: boom",
+            &Value::Null,
+            ts,
+        )
+        .unwrap();
+        assert_eq!(ntfy.url, "https://push.example.com/ntfy/");
+        assert_eq!(ntfy.body["topic"], "backups");
+        assert_eq!(ntfy.body["title"], "Scheduled task failed");
+        assert_eq!(ntfy.body["priority"], 5);
+        assert_eq!(
+            ntfy.body["tags"],
+            json!(["rotating_light", "schedule.failed"])
+        );
+        assert_eq!(
+            ntfy.headers,
+            vec![("Authorization".to_string(), "Bearer tk_abc".to_string())]
+        );
+
+        let public = build_request(
+            &target("ntfy", "https://ntfy.sh/alerts"),
+            catalog::find("job.completed").unwrap(),
+            "T",
+            "B",
+            &Value::Null,
+            ts,
+        )
+        .unwrap();
+        assert_eq!(public.url, "https://ntfy.sh/");
+        assert_eq!(public.body["priority"], 3);
+        assert!(public.headers.is_empty());
+
+        let gotify = build_request(
+            &target("gotify", "https://gotify.example.com/message?token=AbC.123"),
+            failed,
+            "Scheduled task failed",
+            "B",
+            &Value::Null,
+            ts,
+        )
+        .unwrap();
+        assert_eq!(gotify.url, "https://gotify.example.com/message");
+        assert_eq!(gotify.body["title"], "🚨 Scheduled task failed");
+        assert_eq!(gotify.body["priority"], 8);
+        assert_eq!(
+            gotify.headers,
+            vec![("X-Gotify-Key".to_string(), "AbC.123".to_string())]
+        );
+        assert!(build_request(
+            &target("gotify", "https://gotify.example.com/message"),
+            failed,
+            "T",
+            "B",
+            &Value::Null,
+            ts,
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,712 @@
+//! Adoption of hand-written `rclone sync|copy|move|bisync …` crontab lines as managed tasks.
+//!
+//! `scan` reads a crontab (everything except our managed pairs) and turns each line that runs
+//! rclone into a candidate: the schedule, validated by `cronconv`, plus the operation's args in
+//! the GUI's `ScheduledTask` shape. The GUI creates the tasks from those args — the RC request
+//! bodies come out of the same builders (lib/rclone/requests.ts) as every other task's, so an
+//! adopted task is indistinguishable from one made in the app — and then asks the crontab
+//! backend to comment the original lines out.
+//!
+//! Only what maps faithfully is carried over: filter flags, the global flags in `CONFIG_FLAGS`
+//! and env assignments that registration accepts. Everything else is named in the candidate's
+//! notes, and a line whose meaning can't be kept (shell pipelines, extra positionals, unknown
+//! short flags) is reported as rejected instead.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use super::{cronconv, jobfile};
+
+/// What adoption leaves in place of the original line.
+pub const RETIRED_PREFIX: &str = "# adopted by Rclone UI: ";
+
+const OPERATIONS: &[&str] = &["sync", "copy", "move", "bisync"];
+
+/// Filter flags (the `_filter` param). Repeatable ones collect into arrays.
+const FILTER_FLAGS: &[&str] = &[
+    "filter",
+    "filter_from",
+    "exclude",
+    "exclude_from",
+    "include",
+    "include_from",
+    "exclude_if_present",
+    "files_from",
+    "files_from_raw",
+    "delete_excluded",
+    "min_age",
+    "max_age",
+    "min_size",
+    "max_size",
+    "ignore_case",
+    "hash_filter",
+];
+const REPEATABLE_FILTER_FLAGS: &[&str] = &[
+    "filter",
+    "filter_from",
+    "exclude",
+    "exclude_from",
+    "include",
+    "include_from",
+    "exclude_if_present",
+    "files_from",
+    "files_from_raw",
+];
+
+/// Global flags whose `_config` field the GUI's serializer names correctly.
+const CONFIG_FLAGS: &[&str] = &[
+    "backup_dir",
+    "bwlimit",
+    "buffer_size",
+    "check_first",
+    "checkers",
+    "checksum",
+    "compare_dest",
+    "contimeout",
+    "copy_dest",
+    "cutoff_mode",
+    "dry_run",
+    "fast_list",
+    "ignore_checksum",
+    "ignore_errors",
+    "ignore_existing",
+    "ignore_size",
+    "ignore_times",
+    "immutable",
+    "inplace",
+    "low_level_retries",
+    "max_backlog",
+    "max_delete",
+    "max_delete_size",
+    "max_depth",
+    "max_duration",
+    "max_transfer",
+    "metadata",
+    "modify_window",
+    "multi_thread_streams",
+    "no_check_certificate",
+    "no_check_dest",
+    "no_traverse",
+    "no_update_modtime",
+    "order_by",
+    "retries",
+    "retries_sleep",
+    "server_side_across_configs",
+    "size_only",
+    "suffix",
+    "suffix_keep_extension",
+    "timeout",
+    "tpslimit",
+    "tpslimit_burst",
+    "track_renames",
+    "track_renames_strategy",
+    "transfers",
+    "update",
+    "use_server_modtime",
+    "user_agent",
+];
+const NUMERIC_CONFIG_FLAGS: &[&str] = &[
+    "checkers",
+    "low_level_retries",
+    "max_backlog",
+    "max_delete",
+    "max_depth",
+    "multi_thread_streams",
+    "retries",
+    "tpslimit",
+    "tpslimit_burst",
+    "transfers",
+];
+
+/// Flags that take no value (everything else consumes the next word unless written `--x=v`).
+const BOOL_FLAGS: &[&str] = &[
+    "check_access",
+    "check_first",
+    "checksum",
+    "copy_links",
+    "create_empty_src_dirs",
+    "delete_after",
+    "delete_before",
+    "delete_during",
+    "delete_excluded",
+    "dry_run",
+    "fast_list",
+    "force",
+    "human_readable",
+    "ignore_case",
+    "ignore_checksum",
+    "ignore_errors",
+    "ignore_existing",
+    "ignore_listing_checksum",
+    "ignore_size",
+    "ignore_times",
+    "immutable",
+    "inplace",
+    "interactive",
+    "links",
+    "metadata",
+    "no_check_certificate",
+    "no_check_dest",
+    "no_traverse",
+    "no_update_modtime",
+    "one_file_system",
+    "progress",
+    "quiet",
+    "recover",
+    "remove_empty_dirs",
+    "resilient",
+    "resync",
+    "server_side_across_configs",
+    "size_only",
+    "skip_links",
+    "stats_one_line",
+    "suffix_keep_extension",
+    "track_renames",
+    "update",
+    "use_json_log",
+    "use_server_modtime",
+    "verbose",
+];
+
+/// Output and progress flags: the runner owns logging, so these are dropped without comment.
+const OUTPUT_FLAGS: &[&str] = &[
+    "human_readable",
+    "interactive",
+    "log_file",
+    "log_level",
+    "progress",
+    "quiet",
+    "stats",
+    "stats_log_level",
+    "stats_one_line",
+    "use_json_log",
+    "verbose",
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Candidate {
+    /// The crontab line as written — the key for retiring it after adoption.
+    pub line: String,
+    pub cron: String,
+    pub operation: String,
+    pub name: String,
+    /// `ScheduledTask['args']` for `operation`.
+    pub args: Value,
+    /// `NAME=value` assignments in front of the command.
+    pub env: BTreeMap<String, String>,
+    /// Flags and settings that were not carried over, one sentence each.
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rejected {
+    pub line: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scan {
+    pub candidates: Vec<Candidate>,
+    pub rejected: Vec<Rejected>,
+}
+
+/// Candidates among the unmanaged lines of `content` that mention rclone. `is_local_file` tells
+/// a local file source from a folder.
+pub fn scan(content: &str, is_local_file: impl Fn(&str) -> bool) -> Scan {
+    let mut scan = Scan::default();
+    let mut after_marker = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if std::mem::take(&mut after_marker) || trimmed.is_empty() || trimmed.starts_with('#') {
+            after_marker = is_marker(trimmed);
+            continue;
+        }
+        if is_env_line(trimmed) || !trimmed.contains("rclone") {
+            continue;
+        }
+        match parse_line(trimmed, &is_local_file) {
+            Ok(Some(mut candidate)) => {
+                candidate.line = line.to_string();
+                scan.candidates.push(candidate);
+            }
+            Ok(None) => {}
+            Err(reason) => scan.rejected.push(Rejected {
+                line: line.to_string(),
+                reason,
+            }),
+        }
+    }
+    scan
+}
+
+/// Precedes a managed pair's entry line; neither line is a candidate. Only crontab.rs writes
+/// them, so off Unix there are none.
+fn is_marker(line: &str) -> bool {
+    #[cfg(unix)]
+    {
+        line.starts_with(super::crontab::MARKER_PREFIX)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = line;
+        false
+    }
+}
+
+/// `SHELL=/bin/bash`, `MAILTO=…` — crontab environment settings, not entries.
+fn is_env_line(line: &str) -> bool {
+    line.split_once('=').is_some_and(|(name, _)| {
+        let name = name.trim();
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        name.chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// The schedule (nicknames expanded) and the command of a crontab entry.
+fn split_schedule(line: &str) -> Result<(String, &str), String> {
+    if let Some(rest) = line.strip_prefix('@') {
+        let (nickname, command) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let cron = match nickname.to_ascii_lowercase().as_str() {
+            "hourly" => "0 * * * *",
+            "daily" | "midnight" => "0 0 * * *",
+            "weekly" => "0 0 * * 0",
+            "monthly" => "0 0 1 * *",
+            "yearly" | "annually" => "0 0 1 1 *",
+            _ => return Err(format!("the @{} schedule has no cron equivalent", nickname)),
+        };
+        return Ok((cron.to_string(), command.trim()));
+    }
+    let mut rest = line;
+    let mut fields = Vec::new();
+    for _ in 0..5 {
+        let (field, tail) = rest
+            .trim_start()
+            .split_once(char::is_whitespace)
+            .ok_or("not a crontab entry")?;
+        fields.push(field);
+        rest = tail;
+    }
+    Ok((fields.join(" "), rest.trim()))
+}
+
+/// POSIX-shell word splitting for one simple command. Anything that makes the line more than
+/// that (pipes, lists, substitutions, backgrounding) is an error; redirections are dropped.
+fn shell_words(command: &str) -> Result<Vec<String>, String> {
+    const COMPOUND: &str = "runs more than a single rclone command";
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut redirect = false;
+    let mut chars = command.chars().peekable();
+    let mut finish = |word: &mut String, in_word: &mut bool, redirect: &mut bool| {
+        if *in_word && !std::mem::take(redirect) {
+            words.push(std::mem::take(word));
+        }
+        word.clear();
+        *in_word = false;
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("has an unterminated quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("has an unterminated quote".to_string()),
+                        },
+                        Some('$' | '`') => return Err(COMPOUND.to_string()),
+                        Some(c) => word.push(c),
+                        None => return Err("has an unterminated quote".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    word.push(c);
+                }
+            }
+            ';' | '|' | '&' | '`' | '$' | '(' | ')' => {
+                // `2>&1` / `>&2` are redirections, not backgrounding.
+                if c == '&' && redirect && word.is_empty() {
+                    in_word = true;
+                    continue;
+                }
+                return Err(COMPOUND.to_string());
+            }
+            '>' | '<' => {
+                // A digit right before is the fd (`2>`), not part of an argument.
+                if in_word && !word.chars().all(|c| c.is_ascii_digit()) {
+                    finish(&mut word, &mut in_word, &mut redirect);
+                }
+                word.clear();
+                in_word = false;
+                redirect = true;
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    finish(&mut word, &mut in_word, &mut redirect);
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    finish(&mut word, &mut in_word, &mut redirect);
+    Ok(words)
+}
+
+fn flag_value(name: &str, raw: &str) -> Value {
+    if NUMERIC_CONFIG_FLAGS.contains(&name) {
+        if let Ok(n) = raw.parse::<i64>() {
+            return n.into();
+        }
+        if let Ok(n) = raw.parse::<f64>() {
+            return json!(n);
+        }
+    }
+    match raw {
+        "true" => true.into(),
+        "false" => false.into(),
+        _ => raw.into(),
+    }
+}
+
+/// Folder sources end in '/' in the GUI's args; local files don't.
+fn as_folder(path: &str, is_local_file: &impl Fn(&str) -> bool) -> String {
+    if (!path.contains(':') && is_local_file(path)) || path.ends_with('/') {
+        path.to_string()
+    } else {
+        format!("{}/", path)
+    }
+}
+
+/// `Ok(None)`: the line mentions rclone only in passing (an argument, another program's path).
+fn parse_line(
+    line: &str,
+    is_local_file: &impl Fn(&str) -> bool,
+) -> Result<Option<Candidate>, String> {
+    let (cron, command) = split_schedule(line)?;
+    cronconv::parse(&cron)?;
+    cronconv::validate_for_current_platform(&cron)?;
+
+    // cron turns an unescaped % into a newline and feeds the rest to stdin.
+    let command = command.replace(r"\%", "\u{0}");
+    if command.contains('%') {
+        return Err("uses cron's % (stdin) syntax".to_string());
+    }
+    let command = command.replace('\u{0}', "%");
+
+    let words = shell_words(&command)?;
+    let mut words = words.into_iter().peekable();
+    let mut env = BTreeMap::new();
+    while let Some(word) = words.next_if(|w| is_assignment(w)) {
+        if let Some((name, value)) = word.split_once('=') {
+            env.insert(name.to_string(), value.to_string());
+        }
+    }
+    let is_rclone = |word: &str| matches!(word.rsplit('/').next(), Some("rclone" | "rclone.exe"));
+    let Some(program) = words.next() else {
+        return Ok(None);
+    };
+    if !is_rclone(&program) {
+        // `flock … rclone sync …`, `nice rclone …`: rclone runs, but wrapped in something the
+        // task can't reproduce.
+        return if words.any(|word| is_rclone(&word)) {
+            Err("does not run rclone directly".to_string())
+        } else {
+            Ok(None)
+        };
+    }
+
+    let mut positionals = Vec::new();
+    let mut flags: Vec<(String, Option<String>)> = Vec::new();
+    while let Some(word) = words.next() {
+        if word == "--" {
+            positionals.extend(words.by_ref());
+            break;
+        }
+        if let Some(long) = word.strip_prefix("--") {
+            let (name, inline) = match long.split_once('=') {
+                Some((name, value)) => (name.replace('-', "_"), Some(value.to_string())),
+                None => (long.replace('-', "_"), None),
+            };
+            let value = match inline {
+                Some(value) => Some(value),
+                None if BOOL_FLAGS.contains(&name.as_str()) => None,
+                None => Some(
+                    words
+                        .next()
+                        .ok_or_else(|| format!("--{} is missing its value", long))?,
+                ),
+            };
+            flags.push((name, value));
+        } else if let Some(short) = word.strip_prefix('-').filter(|s| !s.is_empty()) {
+            for c in short.chars() {
+                let name = match c {
+                    'n' => "dry_run",
+                    'c' => "checksum",
+                    'u' => "update",
+                    'M' => "metadata",
+                    'L' => "copy_links",
+                    'l' => "links",
+                    'x' => "one_file_system",
+                    'v' => "verbose",
+                    'q' => "quiet",
+                    'P' => "progress",
+                    'i' => "interactive",
+                    _ => return Err(format!("uses the flag -{}, which can't be carried over", c)),
+                };
+                flags.push((name.to_string(), None));
+            }
+        } else {
+            positionals.push(word);
+        }
+    }
+
+    let operation = match positionals.first() {
+        Some(op) if OPERATIONS.contains(&op.as_str()) => op.clone(),
+        Some(op) => return Err(format!("rclone {} can't be scheduled from the app", op)),
+        None => return Err("runs rclone without a command".to_string()),
+    };
+    let [_, source, destination] = positionals.as_slice() else {
+        return Err(format!(
+            "rclone {} needs exactly a source and a destination",
+            operation
+        ));
+    };
+
+    let mut config = Map::new();
+    let mut filter = Map::new();
+    let mut dropped = Vec::new();
+    let mut notes = Vec::new();
+    for (name, value) in flags {
+        let flag = format!("--{}", name.replace('_', "-"));
+        if OUTPUT_FLAGS.contains(&name.as_str()) {
+            continue;
+        }
+        if name == "config" {
+            notes.push(format!(
+                "It used the config file {}; the task runs with the app's config, which needs \
+                 the same remotes",
+                value.unwrap_or_default()
+            ));
+            continue;
+        }
+        if name == "resync" && operation == "bisync" {
+            notes.push("--resync is left to the task's resync setting".to_string());
+            continue;
+        }
+        if name == "create_empty_src_dirs" && operation == "sync" {
+            // The sync builder always sets it.
+            continue;
+        }
+        let value = value
+            .map(|raw| flag_value(&name, &raw))
+            .unwrap_or(Value::Bool(true));
+        if FILTER_FLAGS.contains(&name.as_str()) {
+            if REPEATABLE_FILTER_FLAGS.contains(&name.as_str()) {
+                let entry = filter
+                    .entry(name)
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if let Value::Array(values) = entry {
+                    values.push(value);
+                }
+            } else {
+                filter.insert(name, value);
+            }
+        } else if CONFIG_FLAGS.contains(&name.as_str()) {
+            config.insert(name, value);
+        } else {
+            dropped.push(flag);
+        }
+    }
+    if !dropped.is_empty() {
+        dropped.dedup();
+        notes.push(format!("Not carried over: {}", dropped.join(", ")));
+    }
+    env.retain(|name, _| match jobfile::check_env_name(name) {
+        Ok(()) => true,
+        Err(reason) => {
+            notes.push(format!("Not carried over: {}", reason));
+            false
+        }
+    });
+    if !env.is_empty() {
+        notes.push(format!(
+            "Environment kept as task variables: {}",
+            env.keys().cloned().collect::<Vec<_>>().join(", ")
+        ));
+    }
+
+    let mut options = Map::new();
+    if !config.is_empty() {
+        options.insert("config".into(), Value::Object(config));
+    }
+    if !filter.is_empty() {
+        options.insert("filter".into(), Value::Object(filter));
+    }
+    let args = match operation.as_str() {
+        "copy" | "move" => json!({
+            "sources": [as_folder(source, is_local_file)],
+            "destination": as_folder(destination, is_local_file),
+            "options": options,
+        }),
+        _ => json!({
+            "source": as_folder(source, is_local_file),
+            "destination": as_folder(destination, is_local_file),
+            "options": options,
+        }),
+    };
+
+    Ok(Some(Candidate {
+        line: line.to_string(),
+        name: format!("{} {} → {}", operation, source, destination),
+        cron,
+        operation,
+        args,
+        env,
+        notes,
+    }))
+}
+
+/// `content` with each of `lines` (matched exactly, unmanaged only) commented out. Returns the
+/// new content and how many lines were retired.
+pub fn retire(content: &str, lines: &[String]) -> (String, u32) {
+    let mut result = String::with_capacity(content.len());
+    let mut retired = 0;
+    let mut after_marker = false;
+    for line in content.lines() {
+        let managed = std::mem::take(&mut after_marker);
+        after_marker = is_marker(line.trim());
+        if !managed && lines.iter().any(|l| l == line) {
+            result.push_str(RETIRED_PREFIX);
+            retired += 1;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    (result, retired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scans_rclone_lines_into_task_args() {
+        let content = "\
+SHELL=/bin/bash
+MAILTO=me@example.com
+# nightly photos
+30 2 * * * RCLONE_TRANSFERS=8 RCLONE_CONFIG=/root/alt.conf /usr/bin/rclone sync /srv/photos 'gdrive:Backup/Photos Old' \
+--exclude '*.tmp' --exclude=cache/** --fast-list -v --bwlimit 10M --drive-chunk-size 64M \
+>> /var/log/rclone.log 2>&1
+@daily rclone copy -P --max-age 24h --config /root/.rclone.conf s3:bucket /mnt/backup
+0 * * * * rclone lsd remote: | mail me
+0 4 * * * /usr/local/bin/backup.sh --with-rclone
+0 5 * * * flock -n /tmp/lock rclone sync a: b:
+# rclone-ui-task: t1
+0 6 * * * '/opt/Rclone UI' run-task t1 --host local >/dev/null 2>&1
+";
+        let scan = scan(content, |_| false);
+        assert_eq!(scan.candidates.len(), 2, "{:?}", scan.rejected);
+
+        let sync = &scan.candidates[0];
+        assert_eq!(sync.cron, "30 2 * * *");
+        assert_eq!(sync.operation, "sync");
+        assert_eq!(
+            sync.env.get("RCLONE_TRANSFERS").map(String::as_str),
+            Some("8")
+        );
+        // Registration would refuse it — flagged now, not after the user adopts.
+        assert!(!sync.env.contains_key("RCLONE_CONFIG"));
+        assert!(sync
+            .notes
+            .iter()
+            .any(|note| note.contains("RCLONE_CONFIG is set by the scheduler")));
+        assert_eq!(sync.args["destination"], "gdrive:Backup/Photos Old/");
+        assert_eq!(
+            sync.args["options"]["filter"]["exclude"],
+            json!(["*.tmp", "cache/**"])
+        );
+        assert_eq!(sync.args["options"]["config"]["fast_list"], true);
+        assert_eq!(sync.args["options"]["config"]["bwlimit"], "10M");
+        assert!(sync
+            .notes
+            .iter()
+            .any(|note| note.contains("--drive-chunk-size")));
+
+        let copy = &scan.candidates[1];
+        assert_eq!(copy.cron, "0 0 * * *");
+        assert_eq!(copy.args["sources"], json!(["s3:bucket/"]));
+        assert_eq!(copy.args["options"]["filter"]["max_age"], "24h");
+        assert!(copy
+            .notes
+            .iter()
+            .any(|note| note.contains("/root/.rclone.conf")));
+
+        let reasons: Vec<&str> = scan.rejected.iter().map(|r| r.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "runs more than a single rclone command",
+                "does not run rclone directly"
+            ]
+        );
+    }
+
+    #[test]
+    fn retires_only_unmanaged_matching_lines() {
+        let original = "0 1 * * * rclone sync a: b:";
+        let content = format!(
+            "{}\n# rclone-ui-task: t1\n{}\n0 2 * * * other\n",
+            original, original
+        );
+        let (result, retired) = retire(&content, &[original.to_string()]);
+        assert_eq!(retired, 1);
+        assert_eq!(
+            result,
+            format!(
+                "{}{}\n# rclone-ui-task: t1\n{}\n0 2 * * * other\n",
+                RETIRED_PREFIX, original, original
+            )
+        );
+        // Already retired lines are comments — a second scan skips them.
+        assert!(scan(&result, |_| false).candidates.is_empty());
+    }
+}
@@ -0,0 +1,800 @@
+//! Standalone equivalents of a task for machines without the app: a POSIX shell script, or a
+//! systemd service/timer pair with an environment file.
+//!
+//! The task's RC requests are translated back into `rclone` CLI commands: the fs strings (with
+//! any connection-string options) become positionals, the `_config`/`_filter` params become the
+//! global flags they were built from, and each `/job/batch` input becomes its own command. The
+//! artifacts run the commands one after another with the CLI's own logging — no daemon, no
+//! history, no notifications.
+//!
+//! Secrets never leave the machine: secret env vars and an encrypted config's password are
+//! written as placeholders for whoever installs the artifact to fill in.
+
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use super::cronconv;
+use super::jobfile::{JobSpec, RcRequest};
+
+/// Where the exported commands find their config — resolved from the task's config id.
+pub struct ConfigInfo {
+    pub path: String,
+    pub encrypted: bool,
+    /// The config's password command, when it has one (a command line, not the password).
+    pub pass_command: Option<String>,
+}
+
+/// Config fields whose name isn't the PascalCase of the flag (lib/rclone/requests.ts
+/// CONFIG_FIELD_NAMES, reversed).
+const CONFIG_FLAG_NAMES: &[(&str, &str)] = &[
+    ("ConnectTimeout", "contimeout"),
+    ("InsecureSkipVerify", "no-check-certificate"),
+    ("RetriesInterval", "retries-sleep"),
+    ("UpdateOlder", "update"),
+    ("NoGzip", "no-gzip-encoding"),
+    ("UseListR", "fast-list"),
+    ("DataRateUnit", "stats-unit"),
+    ("Cookie", "use-cookies"),
+    ("TerminalColorMode", "color"),
+];
+
+/// Config fields the serializer splits into a list; the CLI takes them as one spaced value.
+const SPACE_SEPARATED_FIELDS: &[&str] = &["PasswordCommand", "MetadataMapper"];
+
+/// `_filter` fields (lib/rclone/requests.ts FILTER_FIELD_NAMES, reversed).
+const FILTER_FLAG_NAMES: &[(&str, &str)] = &[
+    ("FilterRule", "filter"),
+    ("FilterFrom", "filter-from"),
+    ("ExcludeRule", "exclude"),
+    ("ExcludeFrom", "exclude-from"),
+    ("IncludeRule", "include"),
+    ("IncludeFrom", "include-from"),
+    ("ExcludeFile", "exclude-if-present"),
+    ("FilesFrom", "files-from"),
+    ("FilesFromRaw", "files-from-raw"),
+    ("DeleteExcluded", "delete-excluded"),
+    ("MinAge", "min-age"),
+    ("MaxAge", "max-age"),
+    ("MinSize", "min-size"),
+    ("MaxSize", "max-size"),
+    ("IgnoreCase", "ignore-case"),
+    ("HashFilter", "hash-filter"),
+];
+
+/// `_filter.MetaRules` fields (METADATA_FILTER_FIELD_NAMES, reversed).
+const METADATA_FILTER_FLAG_NAMES: &[(&str, &str)] = &[
+    ("FilterRule", "metadata-filter"),
+    ("FilterFrom", "metadata-filter-from"),
+    ("ExcludeRule", "metadata-exclude"),
+    ("ExcludeFrom", "metadata-exclude-from"),
+    ("IncludeRule", "metadata-include"),
+    ("IncludeFrom", "metadata-include-from"),
+];
+
+/// The `rclone` arguments (without the program) for each of the task's requests, in run order.
+pub fn commands(spec: &JobSpec) -> Result<Vec<Vec<String>>, String> {
+    let mut commands = Vec::new();
+    for request in &spec.requests {
+        commands.extend(request_commands(request)?);
+    }
+    for command in &mut commands {
+        command.extend(spec.daemon_flags.iter().cloned());
+        if spec.verbose_logging {
+            command.push("-v".to_string());
+        }
+    }
+    Ok(commands)
+}
+
+fn request_commands(request: &RcRequest) -> Result<Vec<Vec<String>>, String> {
+    let body = request
+        .body
+        .as_object()
+        .ok_or_else(|| format!("{} has no body", request.endpoint))?;
+    match request.endpoint.as_str() {
+        "/job/batch" => {
+            let inputs = body
+                .get("inputs")
+                .and_then(Value::as_array)
+                .ok_or("/job/batch has no inputs")?;
+            inputs
+                .iter()
+                .map(|input| {
+                    let input = input
+                        .as_object()
+                        .ok_or("a /job/batch input is not an object")?;
+                    let path = input
+                        .get("_path")
+                        .and_then(Value::as_str)
+                        .ok_or("a /job/batch input has no _path")?;
+                    call_command(path, input, body)
+                })
+                .collect()
+        }
+        endpoint => Ok(vec![call_command(
+            endpoint.trim_start_matches('/'),
+            body,
+            &Map::new(),
+        )?]),
+    }
+}
+
+/// One RC call as a CLI command. `batch` is the enclosing `/job/batch` body, whose `_config`
+/// and `_filter` apply where the input sets none of its own.
+fn call_command(
+    path: &str,
+    params: &Map<String, Value>,
+    batch: &Map<String, Value>,
+) -> Result<Vec<String>, String> {
+    let text = |key: &str| {
+        params
+            .get(key)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("{} is missing {}", path, key))
+    };
+    let (mut args, used): (Vec<String>, &[&str]) = match path {
+        "sync/sync" | "sync/copy" | "sync/move" => (
+            vec![
+                path["sync/".len()..].to_string(),
+                text("srcFs")?.to_string(),
+                text("dstFs")?.to_string(),
+            ],
+            &["srcFs", "dstFs"],
+        ),
+        "sync/bisync" => (
+            vec![
+                "bisync".to_string(),
+                text("path1")?.to_string(),
+                text("path2")?.to_string(),
+            ],
+            &["path1", "path2"],
+        ),
+        "operations/copyfile" | "operations/movefile" => (
+            vec![
+                if path == "operations/copyfile" {
+                    "copyto"
+                } else {
+                    "moveto"
+                }
+                .to_string(),
+                join_fs(text("srcFs")?, text("srcRemote")?),
+                join_fs(text("dstFs")?, text("dstRemote")?),
+            ],
+            &["srcFs", "srcRemote", "dstFs", "dstRemote"],
+        ),
+        "operations/delete" => (vec!["delete".to_string(), text("fs")?.to_string()], &["fs"]),
+        "operations/deletefile" | "operations/purge" => (
+            vec![
+                path["operations/".len()..].to_string(),
+                join_fs(text("fs")?, text("remote")?),
+            ],
+            &["fs", "remote"],
+        ),
+        _ => return Err(format!("{} has no rclone command equivalent", path)),
+    };
+
+    for (key, value) in params {
+        if used.contains(&key.as_str()) || matches!(key.as_str(), "_path" | "_async") {
+            continue;
+        }
+        match key.as_str() {
+            "_config" | "_filter" => {}
+            key if key.starts_with('_') => {
+                return Err(format!(
+                    "{} sets {}, which the CLI has no flag for",
+                    path, key
+                ))
+            }
+            // Call parameters (createEmptySrcDirs, bisync's checkAccess/resync/…) are the
+            // command's own flags.
+            key => push_flag(&mut args, &kebab(key), value)?,
+        }
+    }
+
+    if let Some(config) = params.get("_config").or_else(|| batch.get("_config")) {
+        for (field, value) in &param_object(config, "_config")? {
+            let flag = CONFIG_FLAG_NAMES
+                .iter()
+                .find(|(name, _)| *name == field.as_str())
+                .map(|(_, flag)| flag.to_string())
+                .unwrap_or_else(|| kebab(field));
+            match value {
+                Value::Array(words) if SPACE_SEPARATED_FIELDS.contains(&field.as_str()) => {
+                    let words: Vec<String> = words.iter().map(scalar).collect::<Result<_, _>>()?;
+                    push_flag(&mut args, &flag, &Value::String(words.join(" ")))?;
+                }
+                value => push_flag(&mut args, &flag, value)?,
+            }
+        }
+    }
+    if let Some(filter) = params.get("_filter").or_else(|| batch.get("_filter")) {
+        for (field, value) in &param_object(filter, "_filter")? {
+            if field == "MetaRules" {
+                for (field, value) in &param_object(value, "_filter.MetaRules")? {
+                    push_flag(
+                        &mut args,
+                        &mapped(METADATA_FILTER_FLAG_NAMES, field)?,
+                        value,
+                    )?;
+                }
+            } else {
+                push_flag(&mut args, &mapped(FILTER_FLAG_NAMES, field)?, value)?;
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// The serializer stores `_config`/`_filter` as JSON text; both forms are accepted.
+fn param_object(value: &Value, name: &str) -> Result<Map<String, Value>, String> {
+    let value = match value {
+        Value::String(raw) => {
+            serde_json::from_str(raw).map_err(|e| format!("{} is not valid JSON: {}", name, e))?
+        }
+        value => value.clone(),
+    };
+    match value {
+        Value::Object(object) => Ok(object),
+        _ => Err(format!("{} is not an object", name)),
+    }
+}
+
+fn mapped(names: &[(&str, &str)], field: &str) -> Result<String, String> {
+    names
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, flag)| flag.to_string())
+        .ok_or_else(|| format!("the filter field {} has no flag", field))
+}
+
+/// `createEmptySrcDirs` / `MaxBacklog` → `create-empty-src-dirs` / `max-backlog`.
+fn kebab(name: &str) -> String {
+    let mut flag = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                flag.push('-');
+            }
+            flag.push(c.to_ascii_lowercase());
+        } else if c == '_' {
+            flag.push('-');
+        } else {
+            flag.push(c);
+        }
+    }
+    flag
+}
+
+fn scalar(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        _ => Err(format!("{} can't be written as a flag value", value)),
+    }
+}
+
+/// `true` → `--flag`, lists repeat the flag, `null` is unset.
+fn push_flag(args: &mut Vec<String>, flag: &str, value: &Value) -> Result<(), String> {
+    match value {
+        Value::Null => {}
+        Value::Bool(true) => args.push(format!("--{}", flag)),
+        Value::Array(values) => {
+            for value in values {
+                args.push(format!("--{}={}", flag, scalar(value)?));
+            }
+        }
+        value => args.push(format!("--{}={}", flag, scalar(value)?)),
+    }
+    Ok(())
+}
+
+/// An operations call's fs root plus its path inside it, as one CLI path.
+fn join_fs(fs: &str, remote: &str) -> String {
+    if remote.is_empty() {
+        fs.to_string()
+    } else if fs.ends_with(':') || fs.ends_with('/') {
+        format!("{}{}", fs, remote)
+    } else {
+        format!("{}/{}", fs, remote)
+    }
+}
+
+/// POSIX shell quoting; words made only of unambiguous characters stay bare.
+fn shell_quote(word: &str) -> String {
+    let bare = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+".contains(c));
+    if bare {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// systemd's command-line quoting: `%` is a specifier and `$` an expansion in ExecStart.
+fn systemd_quote(word: &str) -> String {
+    let escaped = word.replace('%', "%%").replace('$', "$$");
+    let bare = !escaped.is_empty()
+        && escaped
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%$".contains(c));
+    if bare {
+        escaped
+    } else {
+        format!("\"{}\"", escaped.replace('\\', r"\\").replace('"', "\\\""))
+    }
+}
+
+/// A value in an `EnvironmentFile=`.
+fn env_file_value(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', r"\\").replace('"', "\\\""))
+}
+
+/// The rclone both artifacts run: the task's own binary, or the one on PATH for a task that uses
+/// the app's.
+fn binary(spec: &JobSpec) -> &str {
+    if spec.binary == "app-default" {
+        "rclone"
+    } else {
+        &spec.binary
+    }
+}
+
+/// Caveats both artifacts carry as comments.
+fn caveats(spec: &JobSpec) -> Vec<String> {
+    let mut caveats = Vec::new();
+    if spec.max_parallel > 1 {
+        caveats.push(format!(
+            "The task runs {} requests at once; these commands run one after another.",
+            spec.max_parallel
+        ));
+    }
+    if spec.operation == "bisync" {
+        caveats
+            .push("bisync needs one run with --resync before the first scheduled run.".to_string());
+    }
+    caveats
+}
+
+/// A `#!/bin/sh` script running the task's commands, for cron or by hand.
+pub fn render_script(spec: &JobSpec, config: &ConfigInfo) -> Result<String, String> {
+    let commands = commands(spec)?;
+    let mut script = String::from("#!/bin/sh\n");
+    script.push_str(&format!("# {}\n", spec.name));
+    script.push_str(&format!(
+        "# Exported from Rclone UI (task {}). Schedule: {}\n",
+        spec.task_id, spec.cron
+    ));
+    script.push_str(&format!("# crontab: {} /path/to/this/script\n", spec.cron));
+    for caveat in caveats(spec) {
+        script.push_str(&format!("# {}\n", caveat));
+    }
+    script.push('\n');
+    script.push_str(&format!(
+        "RCLONE=${{RCLONE:-{}}}\n",
+        shell_quote(binary(spec))
+    ));
+    script.push_str(&format!(
+        "RCLONE_CONFIG=${{RCLONE_CONFIG:-{}}}\n",
+        shell_quote(&config.path)
+    ));
+    script.push_str("export RCLONE_CONFIG\n");
+    if config.encrypted {
+        script.push_str("RCLONE_ASK_PASSWORD=false\nexport RCLONE_ASK_PASSWORD\n");
+        match &config.pass_command {
+            Some(command) => script.push_str(&format!(
+                "RCLONE_CONFIG_PASS_COMMAND={}\nexport RCLONE_CONFIG_PASS_COMMAND\n",
+                shell_quote(command)
+            )),
+            None => script.push_str(
+                "# The config is encrypted: its password comes from RCLONE_CONFIG_PASS.\n\
+                 : \"${RCLONE_CONFIG_PASS:?set RCLONE_CONFIG_PASS to the config password}\"\n\
+                 export RCLONE_CONFIG_PASS\n",
+            ),
+        }
+    }
+    for (name, value) in &spec.env {
+        script.push_str(&format!(
+            "{}={}\nexport {}\n",
+            name,
+            shell_quote(value),
+            name
+        ));
+    }
+    for name in &spec.secret_env {
+        script.push_str(&format!(
+            ": \"${{{name}:?set {name} (a secret of the task)}}\"\nexport {name}\n",
+            name = name
+        ));
+    }
+    script.push('\n');
+
+    let on_error = if spec.continue_on_error {
+        "status=1"
+    } else {
+        "exit 1"
+    };
+    if spec.continue_on_error {
+        script.push_str("status=0\n");
+    }
+    for command in commands {
+        let words: Vec<String> = command.iter().map(|word| shell_quote(word)).collect();
+        script.push_str(&format!(
+            "\"$RCLONE\" {} || {}\n",
+            words.join(" "),
+            on_error
+        ));
+    }
+    script.push_str(if spec.continue_on_error {
+        "exit $status\n"
+    } else {
+        "exit 0\n"
+    });
+    Ok(script)
+}
+
+pub struct SystemdUnits {
+    pub service: String,
+    pub timer: String,
+    pub env: String,
+}
+
+/// A oneshot service running the task's commands, a timer on its schedule, and the
+/// `EnvironmentFile=` both read the config and variables from.
+pub fn render_systemd(spec: &JobSpec, config: &ConfigInfo) -> Result<SystemdUnits, String> {
+    let commands = commands(spec)?;
+    let calendar = cronconv::to_systemd_calendar(&cronconv::parse(&spec.cron)?);
+    let base = format!("rclone-ui-{}", spec.task_id);
+
+    let mut service = format!(
+        "# {}\n# Exported from Rclone UI (task {}).\n",
+        spec.name, spec.task_id
+    );
+    for caveat in caveats(spec) {
+        service.push_str(&format!("# {}\n", caveat));
+    }
+    service.push_str(&format!(
+        "\n[Unit]\nDescription=rclone: {}\nWants=network-online.target\n\
+         After=network-online.target\n\n[Service]\nType=oneshot\nEnvironmentFile=/etc/{}.env\n",
+        spec.name.replace('\n', " "),
+        base
+    ));
+    service.push_str(&format!("TimeoutStartSec={}\n", spec.max_run_seconds));
+    // Continuing on error: a failing command leaves a mark in the run's own runtime directory
+    // instead of stopping the ones after it, and the last step fails the unit if any did — the
+    // script's exit status, as the service's.
+    let wrapper: &[&str] = if spec.continue_on_error {
+        service.push_str(&format!("RuntimeDirectory={}\n", base));
+        &[
+            "/bin/sh",
+            "-c",
+            "\"$@\" || touch \"$RUNTIME_DIRECTORY/failed\"",
+            "rclone",
+        ]
+    } else {
+        &[]
+    };
+    for command in commands {
+        let words: Vec<String> = wrapper
+            .iter()
+            .map(|word| word.to_string())
+            .chain(std::iter::once(binary(spec).to_string()))
+            .chain(command)
+            .map(|word| systemd_quote(&word))
+            .collect();
+        service.push_str(&format!("ExecStart={}\n", words.join(" ")));
+    }
+    if spec.continue_on_error {
+        let check: Vec<String> = ["/bin/sh", "-c", "test ! -e \"$RUNTIME_DIRECTORY/failed\""]
+            .iter()
+            .map(|word| systemd_quote(word))
+            .collect();
+        service.push_str(&format!("ExecStart={}\n", check.join(" ")));
+    }
+
+    let mut timer = format!(
+        "[Unit]\nDescription=Schedule for {}.service\n\n[Timer]\n",
+        base
+    );
+    for value in calendar {
+        timer.push_str(&format!("OnCalendar={}\n", value));
+    }
+    timer.push_str("Persistent=false\n\n[Install]\nWantedBy=timers.target\n");
+
+    let mut env = format!(
+        "# Environment for {}.service — install as /etc/{}.env, readable by root only.\n",
+        base, base
+    );
+    env.push_str(&format!("RCLONE_CONFIG={}\n", env_file_value(&config.path)));
+    if config.encrypted {
+        env.push_str("RCLONE_ASK_PASSWORD=false\n");
+        match &config.pass_command {
+            Some(command) => env.push_str(&format!(
+                "RCLONE_CONFIG_PASS_COMMAND={}\n",
+                env_file_value(command)
+            )),
+            None => env.push_str("# The config password:\nRCLONE_CONFIG_PASS=\n"),
+        }
+    }
+    for (name, value) in &spec.env {
+        env.push_str(&format!("{}={}\n", name, env_file_value(value)));
+    }
+    if !spec.secret_env.is_empty() {
+        env.push_str("# Secrets of the task — fill in:\n");
+        for name in &spec.secret_env {
+            env.push_str(&format!("{}=\n", name));
+        }
+    }
+
+    Ok(SystemdUnits {
+        service,
+        timer,
+        env,
+    })
+}
+
+/// Renders `kind` ("script" or "systemd") for `spec` into `dir`. Returns the written paths.
+pub fn write(
+    dir: &Path,
+    spec: &JobSpec,
+    kind: &str,
+    config: &ConfigInfo,
+) -> Result<Vec<String>, String> {
+    let base = format!("rclone-ui-{}", spec.task_id);
+    let files = match kind {
+        "script" => vec![(format!("{}.sh", base), render_script(spec, config)?, 0o755)],
+        "systemd" => {
+            let units = render_systemd(spec, config)?;
+            vec![
+                (format!("{}.service", base), units.service, 0o644),
+                (format!("{}.timer", base), units.timer, 0o644),
+                (format!("{}.env", base), units.env, 0o600),
+            ]
+        }
+        _ => return Err(format!("unknown export kind '{}'", kind)),
+    };
+    let mut written = Vec::new();
+    for (name, content, mode) in files {
+        let path = dir.join(name);
+        write_file(&path, &content, mode)?;
+        written.push(path.to_string_lossy().into_owned());
+    }
+    Ok(written)
+}
+
+fn write_file(path: &Path, content: &str, mode: u32) -> Result<(), String> {
+    use std::io::Write as _;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt as _;
+        options.mode(mode);
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("failed to create {}: {}", path.display(), e))?;
+    // The open only applies the mode to a new file: a re-export must not keep an old .env
+    // readable by others.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        file.set_permissions(std::fs::Permissions::from_mode(mode))
+            .map_err(|e| format!("failed to set permissions of {}: {}", path.display(), e))?;
+    }
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(requests: Vec<RcRequest>) -> JobSpec {
+        serde_json::from_value(json!({
+            "schemaVersion": 1,
+            "taskId": "t1",
+            "hostId": "local",
+            "name": "Nightly",
+            "operation": "sync",
+            "cron": "0 3 * * *",
+            "configId": "default",
+            "binary": "app-default",
+            "requests": requests,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn sync_round_trips_through_a_crontab_line() {
+        // What buildSyncRequests makes of these options (lib/rclone/requests.ts).
+        let options = json!({
+            "config": {
+                "fast_list": true,
+                "transfers": 8,
+                "bwlimit": "10M",
+                "contimeout": "30s",
+                "no_check_certificate": true,
+                "backup_dir": "b2:archive/it's old",
+            },
+            "filter": {
+                "exclude": ["*.tmp", "cache/**"],
+                "max_age": "24h",
+            },
+        });
+        let body = json!({
+            "srcFs": "/srv/photos",
+            "dstFs": "gdrive,chunk_size='64M':Backup/Photos Old",
+            "createEmptySrcDirs": true,
+            "_config": r#"{"UseListR":true,"Transfers":8,"Bwlimit":"10M","ConnectTimeout":"30s","InsecureSkipVerify":true,"BackupDir":"b2:archive/it's old"}"#,
+            "_filter": r#"{"ExcludeRule":["*.tmp","cache/**"],"MaxAge":"24h"}"#,
+            "_async": true,
+        });
+        let spec = spec(vec![RcRequest {
+            endpoint: "/sync/sync".into(),
+            body,
+        }]);
+
+        let script = render_script(
+            &spec,
+            &ConfigInfo {
+                path: "/home/me/.config/rclone/rclone.conf".into(),
+                encrypted: false,
+                pass_command: None,
+            },
+        )
+        .unwrap();
+        let command = script
+            .lines()
+            .find_map(|line| line.strip_prefix("\"$RCLONE\" "))
+            .and_then(|line| line.strip_suffix(" || exit 1"))
+            .unwrap();
+
+        let line = format!("0 3 * * * rclone {}\n", command);
+        let scan = super::super::adopt::scan(&line, |_| false);
+        assert_eq!(scan.candidates.len(), 1, "{:?}", scan.rejected);
+        let adopted = &scan.candidates[0];
+        assert_eq!(adopted.operation, "sync");
+        assert_eq!(adopted.args["source"], "/srv/photos/");
+        assert_eq!(
+            adopted.args["destination"],
+            "gdrive,chunk_size='64M':Backup/Photos Old/"
+        );
+        assert_eq!(adopted.args["options"], options);
+        assert!(adopted.notes.is_empty(), "{:?}", adopted.notes);
+    }
+
+    #[test]
+    fn batch_inputs_become_one_command_each() {
+        let mut spec = spec(vec![
+            RcRequest {
+                endpoint: "/job/batch".into(),
+                body: json!({
+                    "inputs": [
+                        {
+                            "_path": "sync/copy",
+                            "srcFs": "s3:bucket/photos",
+                            "dstFs": "/mnt/backup/photos",
+                            "createEmptySrcDirs": true,
+                            "_config": r#"{"Transfers":4}"#,
+                            "_filter": r#"{"MetaRules":{"IncludeRule":["tier=hot"]}}"#,
+                        },
+                        {
+                            "_path": "operations/copyfile",
+                            "srcFs": "s3:",
+                            "srcRemote": "bucket/a.txt",
+                            "dstFs": "/",
+                            "dstRemote": "mnt/backup/a.txt",
+                            "_config": r#"{"Transfers":4}"#,
+                        },
+                    ],
+                    "_config": r#"{"Transfers":4}"#,
+                    "_async": true,
+                }),
+            },
+            RcRequest {
+                endpoint: "/job/batch".into(),
+                body: json!({
+                    "inputs": [{ "_path": "operations/purge", "fs": "s3:", "remote": "old" }],
+                    "_async": true,
+                }),
+            },
+        ]);
+        spec.verbose_logging = true;
+        spec.continue_on_error = true;
+        spec.secret_env = vec!["RCLONE_S3_SECRET_ACCESS_KEY".into()];
+        spec.cron = "0 3 1 * 1".into();
+
+        assert_eq!(
+            commands(&spec).unwrap(),
+            vec![
+                vec![
+                    "copy",
+                    "s3:bucket/photos",
+                    "/mnt/backup/photos",
+                    "--create-empty-src-dirs",
+                    "--transfers=4",
+                    "--metadata-include=tier=hot",
+                    "-v",
+                ],
+                vec![
+                    "copyto",
+                    "s3:bucket/a.txt",
+                    "/mnt/backup/a.txt",
+                    "--transfers=4",
+                    "-v"
+                ],
+                vec!["purge", "s3:old", "-v"],
+            ]
+        );
+
+        let units = render_systemd(
+            &spec,
+            &ConfigInfo {
+                path: "/etc/rclone.conf".into(),
+                encrypted: true,
+                pass_command: None,
+            },
+        )
+        .unwrap();
+        // Continuing on error: every command runs, and the last step fails the unit if any did.
+        assert!(units.service.contains(
+            "ExecStart=/bin/sh -c \"\\\"$$@\\\" || touch \\\"$$RUNTIME_DIRECTORY/failed\\\"\" rclone rclone purge s3:old -v\n"
+        ));
+        assert!(units
+            .service
+            .ends_with("ExecStart=/bin/sh -c \"test ! -e \\\"$$RUNTIME_DIRECTORY/failed\\\"\"\n"));
+        assert!(units
+            .timer
+            .contains("OnCalendar=*-*-01 03:00:00\nOnCalendar=Mon *-*-* 03:00:00\n"));
+        assert!(units.env.contains("\nRCLONE_CONFIG_PASS=\n"));
+        assert!(units.env.contains("\nRCLONE_S3_SECRET_ACCESS_KEY=\n"));
+
+        let unknown = RcRequest {
+            endpoint: "/operations/mkdir".into(),
+            body: json!({ "fs": "s3:", "remote": "new" }),
+        };
+        assert!(commands(&self::spec(vec![unknown])).is_err());
+    }
+
+    #[test]
+    fn both_exports_run_the_tasks_binary() {
+        let mut spec = spec(vec![RcRequest {
+            endpoint: "/sync/copy".into(),
+            body: json!({ "srcFs": "/srv", "dstFs": "b2:srv" }),
+        }]);
+        spec.binary = "/opt/rclone/rclone".into();
+        let config = ConfigInfo {
+            path: "/etc/rclone.conf".into(),
+            encrypted: false,
+            pass_command: None,
+        };
+        let script = render_script(&spec, &config).unwrap();
+        assert!(script.contains("\nRCLONE=${RCLONE:-/opt/rclone/rclone}\n"));
+        let units = render_systemd(&spec, &config).unwrap();
+        assert!(units
+            .service
+            .contains("\nExecStart=/opt/rclone/rclone copy /srv b2:srv\n"));
+
+        // A re-export rewrites the .env owner-only, whatever its mode was.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt as _;
+            let dir = std::env::temp_dir().join(format!("rcloneui-export-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let env = dir.join("rclone-ui-t1.env");
+            std::fs::write(&env, "").unwrap();
+            std::fs::set_permissions(&env, std::fs::Permissions::from_mode(0o644)).unwrap();
+            write(&dir, &spec, "systemd", &config).unwrap();
+            let mode = std::fs::metadata(&env).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+            let _ = std::fs::remove_dir_all(&dir);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Bisync-specific recovery for scheduled runs.
+//!
+//! Unlike the other endpoints, `/sync/bisync` keeps state between runs: listings of both paths
+//! (without them it refuses to run until someone passes `--resync`) and a `.lck` file for the
+//! duration of a run (left behind when the run is killed, after which every later run fails with
+//! "prior lock file found"). An unattended schedule hits both — the first run and any crash —
+//! and cannot recover on its own, so the runner recognizes them here and retries the request.
+
+use std::path::{Path, PathBuf};
+
+use super::daemonlog;
+
+pub const ENDPOINT: &str = "/sync/bisync";
+
+/// How the runner may answer a bisync that needs `--resync`. The user picks one per task; a
+/// resync merges both sides into a superset (deletions since the last good run come back), so
+/// it is never done without consent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResyncPolicy {
+    /// Fail the run; the user resyncs by hand. The default, and the reading of unknown values.
+    Never,
+    /// Resync only when the task has never completed — the listings simply don't exist yet.
+    FirstRun,
+    /// Also resync after a critical error that aborted a previous run.
+    Always,
+}
+
+impl ResyncPolicy {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "first-run" => Self::FirstRun,
+            "always" => Self::Always,
+            _ => Self::Never,
+        }
+    }
+}
+
+/// rclone colors parts of its bisync errors when its color flag is on; the escapes must not end
+/// up in a path.
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// No prior listings: the first run of a pair, or the listings were renamed away after a
+/// critical error.
+pub fn is_missing_listings(error: &str) -> bool {
+    error
+        .to_lowercase()
+        .contains("cannot find prior path1 or path2 listings")
+}
+
+/// Whether the failed bisync (its RC error, plus the daemon log lines it wrote) can only be
+/// recovered by a resync. rclone returns the underlying critical error over RC; the "Must run
+/// --resync to recover" verdict only appears in the log.
+pub fn needs_resync(error: &str, log_lines: &[String]) -> bool {
+    is_missing_listings(error)
+        || std::iter::once(error)
+            .chain(log_lines.iter().map(String::as_str))
+            .any(|text| text.to_lowercase().contains("must run --resync"))
+}
+
+/// Whether `policy` allows answering this failure with a resync. `first_run` means the task has
+/// no successful run in its history.
+pub fn resync_allowed(
+    policy: ResyncPolicy,
+    error: &str,
+    log_lines: &[String],
+    first_run: bool,
+) -> bool {
+    match policy {
+        ResyncPolicy::Never => false,
+        ResyncPolicy::FirstRun => first_run && is_missing_listings(error),
+        ResyncPolicy::Always => needs_resync(error, log_lines),
+    }
+}
+
+/// The lock file named by a "prior lock file found: <path>" error. The path runs up to its
+/// `.lck` extension — it may contain spaces (Windows profile directories), and rclone appends
+/// a tip after it.
+pub fn lock_file(error: &str) -> Option<PathBuf> {
+    let error = strip_ansi(error);
+    let (_, rest) = error.split_once("prior lock file found: ")?;
+    let end = rest.find(".lck")? + ".lck".len();
+    let path = rest[..end].trim().trim_matches(['"', '\'']);
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// The pid recorded in a bisync lock file: JSON (`{"PID": "1234", …}`, rclone 1.66+) or the
+/// bare pid older versions wrote.
+fn lock_pid(content: &str) -> Option<u32> {
+    if let Ok(value @ serde_json::Value::Object(_)) = serde_json::from_str(content) {
+        let pid = value.get("PID")?;
+        return pid
+            .as_u64()
+            .and_then(|p| u32::try_from(p).ok())
+            .or_else(|| pid.as_str()?.trim().parse().ok());
+    }
+    content.trim().parse().ok()
+}
+
+/// Whether a bisync lock file left behind can be removed. The caller holds the task's run lock,
+/// so no other run of THIS task is live; the file must also be an actual `.lck` whose recorded
+/// process is gone — a live pid may be a bisync of the same pair started from the GUI.
+pub fn lock_is_stale(path: &Path) -> bool {
+    if path.extension().and_then(|e| e.to_str()) != Some("lck") || !path.is_file() {
+        return false;
+    }
+    let Some(pid) = std::fs::read_to_string(path)
+        .ok()
+        .as_deref()
+        .and_then(lock_pid)
+    else {
+        // Unreadable or empty: nothing claims it.
+        return true;
+    };
+    let pid = sysinfo::Pid::from_u32(pid);
+    let mut system = sysinfo::System::new();
+    system.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).is_none()
+}
+
+/// Conflicts bisync resolved during the run, counted from its per-file "New or changed in both
+/// paths" notices in the daemon log segment (rclone keeps both versions, renamed).
+pub fn count_conflicts(path: &Path, offset: u64) -> u32 {
+    daemonlog::segment_lines(path, offset)
+        .map(|lines| {
+            lines
+                .filter(|line| line.contains("New or changed in both paths"))
+                .count() as u32
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resync_follows_the_policy() {
+        let missing =
+            "cannot find prior Path1 or Path2 listings, likely due to critical error on prior run";
+        let aborted = "bisync aborted: path1 and path2 are out of sync".to_string();
+        let log = vec![
+            "2026/01/01 03:00:01 ERROR : Bisync critical error: too many deletes".to_string(),
+            "2026/01/01 03:00:01 ERROR : Bisync aborted. Must run --resync to recover.".to_string(),
+        ];
+
+        assert!(!resync_allowed(ResyncPolicy::Never, missing, &[], true));
+        assert!(resync_allowed(ResyncPolicy::FirstRun, missing, &[], true));
+        // Listings gone after the task once succeeded: a critical error, not a first run.
+        assert!(!resync_allowed(ResyncPolicy::FirstRun, missing, &[], false));
+        assert!(resync_allowed(ResyncPolicy::Always, missing, &[], false));
+        assert!(resync_allowed(ResyncPolicy::Always, &aborted, &log, false));
+        assert!(!resync_allowed(
+            ResyncPolicy::Always,
+            "directory not found",
+            &[],
+            false
+        ));
+        assert_eq!(ResyncPolicy::parse("bogus"), ResyncPolicy::Never);
+    }
+
+    #[test]
+    fn finds_and_judges_lock_files() {
+        let error = "prior lock file found: \u{1b}[93mC:\\Users\\Jo Doe\\AppData\\Local\\rclone\\bisync\\local_C_data..gdrive_backup.lck\u{1b}[0m\nTip: this indicates that another bisync run (of these same paths) either is still running or was interrupted";
+        assert_eq!(
+            lock_file(error),
+            Some(PathBuf::from(
+                "C:\\Users\\Jo Doe\\AppData\\Local\\rclone\\bisync\\local_C_data..gdrive_backup.lck"
+            ))
+        );
+        assert_eq!(lock_file("directory not found"), None);
+
+        assert_eq!(
+            lock_pid(r#"{"Session":"a..b","PID":"4242","TimeRenewed":"2026-01-01T03:00:00Z"}"#),
+            Some(4242)
+        );
+        assert_eq!(lock_pid("4242\n"), Some(4242));
+
+        let dir = std::env::temp_dir();
+        let stale = dir.join(format!("rcloneui-bisync-test-{}.lck", std::process::id()));
+        std::fs::write(&stale, format!(r#"{{"PID":"{}"}}"#, u32::MAX - 1)).unwrap();
+        assert!(lock_is_stale(&stale));
+        // Our own (live) pid: somebody is still running it.
+        std::fs::write(&stale, std::process::id().to_string()).unwrap();
+        assert!(!lock_is_stale(&stale));
+        let _ = std::fs::remove_file(&stale);
+        assert!(!lock_is_stale(&dir.join("not-a-lock.txt")));
+    }
+}
@@ -0,0 +1,504 @@
+//! Portable export/import of scheduled tasks (`scheduler_export` / `scheduler_import`).
+//!
+//! A job file alone can't move to another machine: it names this machine's host, rclone binary
+//! and config ids, and the GUI rebuilds it from the host store's task definition anyway (the
+//! store is the source of truth; a job file without a definition is swept as a stray). A bundle
+//! therefore carries each task's `JobSpec` together with its definition, plus the label of the
+//! config it ran with (config ids are per-install uuids, labels are what a person recognizes),
+//! and the notification targets subscribed to schedule events.
+//!
+//! Import adapts each task to this machine, registers it through the normal path and reports
+//! per task what was changed or why it was rejected; the GUI then adds the imported definitions
+//! to its store. Secret env values never leave the machine: tasks that declare secrets arrive
+//! paused, their names stored with empty values, until the user re-enters them.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::jobfile::{self, JobSpec};
+use super::storeread::{self, AppDirs};
+use super::{cronconv, history};
+use crate::notifications::digest::TargetDelivery;
+use crate::notifications::filters::TargetFilters;
+use crate::notifications::headers::TargetHeader;
+use crate::notifications::targets::{self, NewTarget};
+use crate::notifications::template::PayloadTemplate;
+
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    pub bundle_version: u32,
+    pub exported_at: String,
+    pub tasks: Vec<BundledTask>,
+    #[serde(default)]
+    pub notification_targets: Vec<BundledTarget>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledTask {
+    pub spec: JobSpec,
+    /// The host store's `ScheduledTask`, opaque here except for the fields import adapts.
+    pub definition: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledTarget {
+    pub provider: String,
+    pub name: String,
+    pub url: String,
+    pub is_enabled: bool,
+    pub events: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<PayloadTemplate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<TargetHeader>,
+    #[serde(default, skip_serializing_if = "TargetFilters::is_empty")]
+    pub filters: TargetFilters,
+    #[serde(default, skip_serializing_if = "TargetDelivery::is_immediate")]
+    pub delivery: TargetDelivery,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportReport {
+    pub tasks: u32,
+    pub notification_targets: u32,
+    /// Definitions left out because they have no readable job file (never registered here).
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskImport {
+    pub task_id: String,
+    pub name: String,
+    pub imported: bool,
+    /// What was changed to fit this machine, one sentence each.
+    pub adaptations: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The adapted definition to add to the host store (imported tasks only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetImport {
+    pub name: String,
+    pub imported: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub tasks: Vec<TaskImport>,
+    pub notification_targets: Vec<TargetImport>,
+}
+
+fn is_schedule_event(event: &str) -> bool {
+    event.starts_with("schedule.")
+}
+
+/// Bundles the registered job file of every definition the GUI passes (its local host store's
+/// tasks) plus the notification targets listening to schedule events.
+pub fn build(dirs: &AppDirs, definitions: Vec<serde_json::Value>) -> (Bundle, Vec<String>) {
+    let host = storeread::read_host(dirs, "local").ok();
+    let mut tasks = Vec::new();
+    let mut skipped = Vec::new();
+    for definition in definitions {
+        let id = definition
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let spec = super::sanitize_id(&id).and_then(|id| jobfile::load(dirs, "local", &id));
+        match spec {
+            Ok(spec) => {
+                let config_label = host
+                    .as_ref()
+                    .and_then(|host| storeread::find_config(host, &spec.config_id))
+                    .and_then(|config| config.label.clone());
+                tasks.push(BundledTask {
+                    spec,
+                    definition,
+                    config_label,
+                });
+            }
+            Err(_) => skipped.push(
+                definition
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or(&id)
+                    .to_string(),
+            ),
+        }
+    }
+    let notification_targets = targets::load(dirs)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|target| target.events.iter().any(|e| is_schedule_event(e)))
+        .map(|target| BundledTarget {
+            provider: target.provider,
+            name: target.name,
+            url: target.url,
+            is_enabled: target.is_enabled,
+            events: target.events,
+            template: target.template,
+            headers: target.headers,
+            filters: target.filters,
+            delivery: target.delivery,
+        })
+        .collect();
+    (
+        Bundle {
+            bundle_version: BUNDLE_VERSION,
+            exported_at: history::now_iso(),
+            tasks,
+            notification_targets,
+        },
+        skipped,
+    )
+}
+
+/// Owner-only, like the secrets files: the bundle holds webhook URLs, which embed their tokens.
+pub fn write(path: &Path, bundle: &Bundle) -> Result<(), String> {
+    use std::io::Write as _;
+    let json = serde_json::to_string_pretty(bundle).map_err(|e| e.to_string())?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt as _;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("failed to create {}: {}", path.display(), e))?;
+    file.write_all(json.as_bytes())
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+pub fn read(path: &Path) -> Result<Bundle, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    let version = serde_json::from_str::<serde_json::Value>(&raw)
+        .map_err(|e| format!("not a schedules bundle: {}", e))?
+        .get("bundleVersion")
+        .and_then(|v| v.as_u64())
+        .ok_or("not a schedules bundle")?;
+    if version > u64::from(BUNDLE_VERSION) {
+        return Err(format!(
+            "the bundle was written by a newer version of the app (format {})",
+            version
+        ));
+    }
+    serde_json::from_str(&raw).map_err(|e| format!("invalid schedules bundle: {}", e))
+}
+
+/// What import needs to know about this machine.
+pub struct ImportContext {
+    /// Local configs as (id, label).
+    pub configs: Vec<(String, Option<String>)>,
+    /// Used when neither the id nor the label matches — the GUI's active config.
+    pub fallback_config: Option<String>,
+    /// Task ids already taken here (job files and the GUI store).
+    pub taken_ids: HashSet<String>,
+}
+
+#[derive(Debug)]
+pub struct Adapted {
+    pub spec: JobSpec,
+    pub definition: serde_json::Value,
+    pub enabled: bool,
+    pub adaptations: Vec<String>,
+}
+
+/// Fits one bundled task to this machine. `binary_exists` is `Path::exists` outside tests.
+pub fn adapt(
+    task: &BundledTask,
+    context: &ImportContext,
+    binary_exists: impl Fn(&str) -> bool,
+) -> Result<Adapted, String> {
+    let mut spec = task.spec.clone();
+    let mut definition = task.definition.clone();
+    let mut adaptations = Vec::new();
+
+    super::sanitize_id(&spec.task_id)?;
+    if definition.get("id").and_then(|v| v.as_str()) != Some(spec.task_id.as_str())
+        || definition.get("args").is_none()
+    {
+        return Err("the bundled task definition is incomplete".to_string());
+    }
+    if spec.schema_version != jobfile::JOB_SCHEMA_VERSION {
+        return Err(format!(
+            "unsupported job schema version {}",
+            spec.schema_version
+        ));
+    }
+    if context.taken_ids.contains(&spec.task_id) {
+        return Err("this task is already scheduled here".to_string());
+    }
+    cronconv::validate_for_current_platform(&spec.cron)?;
+
+    if spec.host_id != "local" {
+        spec.host_id = "local".to_string();
+    }
+
+    if spec.binary != "app-default" && !binary_exists(&spec.binary) {
+        adaptations.push(format!(
+            "{} does not exist here; the app's rclone is used instead",
+            spec.binary
+        ));
+        spec.binary = "app-default".to_string();
+    }
+
+    let config_id = if context.configs.iter().any(|(id, _)| *id == spec.config_id) {
+        spec.config_id.clone()
+    } else if let Some((id, label)) = context
+        .configs
+        .iter()
+        .find(|(_, label)| label.is_some() && label.as_deref() == task.config_label.as_deref())
+    {
+        adaptations.push(format!(
+            "runs with the config \"{}\" on this machine",
+            label.as_deref().unwrap_or(id)
+        ));
+        id.clone()
+    } else if let Some(fallback) = &context.fallback_config {
+        adaptations.push(match &task.config_label {
+            Some(label) => format!(
+                "no config named \"{}\" here; the active config is used",
+                label
+            ),
+            None => "its config does not exist here; the active config is used".to_string(),
+        });
+        fallback.clone()
+    } else {
+        return Err("no config file to run it with".to_string());
+    };
+    spec.config_id = config_id;
+
+    let mut enabled = definition
+        .get("isEnabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    if !spec.secret_env.is_empty() {
+        adaptations.push(format!(
+            "paused: re-enter the secrets {} and enable it",
+            spec.secret_env.join(", ")
+        ));
+        enabled = false;
+    }
+
+    if let Some(object) = definition.as_object_mut() {
+        object.insert("configId".into(), spec.config_id.clone().into());
+        object.insert("binaryPath".into(), spec.binary.clone().into());
+        object.insert("isEnabled".into(), enabled.into());
+        object.remove("registrationError");
+    }
+
+    Ok(Adapted {
+        spec,
+        definition,
+        enabled,
+        adaptations,
+    })
+}
+
+/// Adapts and registers every task, then adds the bundle's notification targets that aren't
+/// configured here yet (matched by URL, like `targets::add`).
+pub fn import(dirs: &AppDirs, bundle: Bundle, mut context: ImportContext) -> ImportReport {
+    context.taken_ids.extend(
+        jobfile::list(dirs, "local")
+            .into_iter()
+            .map(|spec| spec.task_id),
+    );
+
+    let mut tasks = Vec::new();
+    for task in &bundle.tasks {
+        let name = task.spec.name.clone();
+        let task_id = task.spec.task_id.clone();
+        let outcome = adapt(task, &context, |path| Path::new(path).exists()).and_then(|adapted| {
+            // Empty values keep the names in the edit drawer for the user to fill in.
+            let secrets: HashMap<String, String> = adapted
+                .spec
+                .secret_env
+                .iter()
+                .map(|name| (name.clone(), String::new()))
+                .collect();
+            super::register(dirs, &adapted.spec, adapted.enabled, &secrets)?;
+            Ok(adapted)
+        });
+        tasks.push(match outcome {
+            Ok(adapted) => {
+                context.taken_ids.insert(task_id.clone());
+                TaskImport {
+                    task_id,
+                    name,
+                    imported: true,
+                    adaptations: adapted.adaptations,
+                    error: None,
+                    definition: Some(adapted.definition),
+                }
+            }
+            Err(error) => TaskImport {
+                task_id,
+                name,
+                imported: false,
+                adaptations: Vec::new(),
+                error: Some(error),
+                definition: None,
+            },
+        });
+    }
+
+    let notification_targets = bundle
+        .notification_targets
+        .into_iter()
+        .map(|target| {
+            let name = target.name.clone();
+            let result = targets::add(
+                dirs,
+                NewTarget {
+                    provider: target.provider,
+                    name: target.name,
+                    url: target.url,
+                    is_enabled: target.is_enabled,
+                    events: target.events,
+                    template: target.template,
+                    headers: target.headers,
+                    filters: target.filters,
+                    delivery: target.delivery,
+                    // Sealed with the exporting machine's key — re-entered after import.
+                    signing_secret: None,
+                    auth: None,
+                },
+            );
+            TargetImport {
+                name,
+                imported: result.is_ok(),
+                error: result.err(),
+            }
+        })
+        .collect();
+
+    ImportReport {
+        tasks,
+        notification_targets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundled(spec: serde_json::Value, config_label: Option<&str>) -> BundledTask {
+        let mut spec_json = serde_json::json!({
+            "schemaVersion": 1,
+            "taskId": "t1",
+            "hostId": "local",
+            "name": "Photos",
+            "operation": "sync",
+            "cron": "0 3 * * *",
+            "configId": "old-config",
+            "binary": "/opt/old/rclone",
+            "maxRunSeconds": 3600,
+            "verboseLogging": false,
+            "runMode": "system",
+            "requests": [{"endpoint": "/sync/sync", "body": {}}],
+        });
+        for (key, value) in spec.as_object().unwrap() {
+            spec_json[key] = value.clone();
+        }
+        BundledTask {
+            spec: serde_json::from_value(spec_json).unwrap(),
+            definition: serde_json::json!({
+                "id": "t1",
+                "isEnabled": true,
+                "configId": "old-config",
+                "binaryPath": "/opt/old/rclone",
+                "secretEnvKeys": ["RCLONE_CONFIG_PASS"],
+                "registrationError": "stale",
+                "args": {},
+            }),
+            config_label: config_label.map(str::to_string),
+        }
+    }
+
+    fn context() -> ImportContext {
+        ImportContext {
+            configs: vec![
+                ("default".into(), Some("Default".into())),
+                ("c2".into(), Some("Work".into())),
+            ],
+            fallback_config: Some("default".into()),
+            taken_ids: HashSet::new(),
+        }
+    }
+
+    #[test]
+    fn adapts_binary_config_and_secrets_to_this_machine() {
+        let task = bundled(
+            serde_json::json!({"secretEnv": ["RCLONE_CONFIG_PASS"]}),
+            Some("Work"),
+        );
+        let adapted = adapt(&task, &context(), |_| false).unwrap();
+        assert_eq!(adapted.spec.binary, "app-default");
+        assert_eq!(adapted.spec.config_id, "c2");
+        assert_eq!(adapted.spec.secret_env, vec!["RCLONE_CONFIG_PASS"]);
+        assert!(!adapted.enabled);
+        assert_eq!(adapted.adaptations.len(), 3);
+        assert_eq!(adapted.definition["configId"], "c2");
+        assert_eq!(adapted.definition["binaryPath"], "app-default");
+        assert_eq!(adapted.definition["isEnabled"], false);
+        assert_eq!(
+            adapted.definition["secretEnvKeys"],
+            serde_json::json!(["RCLONE_CONFIG_PASS"])
+        );
+        assert!(adapted.definition.get("registrationError").is_none());
+
+        // Unknown label: the active config, noted.
+        let adapted = adapt(
+            &bundled(serde_json::json!({}), Some("Gone")),
+            &context(),
+            |_| true,
+        )
+        .unwrap();
+        assert_eq!(adapted.spec.config_id, "default");
+        assert_eq!(adapted.spec.binary, "/opt/old/rclone");
+        assert!(adapted.enabled);
+        assert_eq!(adapted.adaptations.len(), 1);
+    }
+
+    #[test]
+    fn rejects_what_cannot_run_here() {
+        let mut taken = context();
+        taken.taken_ids.insert("t1".into());
+        assert!(adapt(&bundled(serde_json::json!({}), None), &taken, |_| true).is_err());
+
+        let bad_cron = bundled(serde_json::json!({"cron": "61 * * * *"}), None);
+        assert!(adapt(&bad_cron, &context(), |_| true).is_err());
+
+        let no_config = ImportContext {
+            configs: Vec::new(),
+            fallback_config: None,
+            taken_ids: HashSet::new(),
+        };
+        assert_eq!(
+            adapt(&bundled(serde_json::json!({}), None), &no_config, |_| true).unwrap_err(),
+            "no config file to run it with"
+        );
+    }
+}
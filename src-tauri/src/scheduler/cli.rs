@@ -0,0 +1,534 @@
+//! Headless inspection and control of scheduled tasks, for boxes reached over SSH:
+//!
+//! ```text
+//! "Rclone UI" list-tasks [--json]
+//! "Rclone UI" task-status <taskId> [--json]
+//! "Rclone UI" task-history <taskId> [--json] [--limit <n>]
+//! "Rclone UI" task-log <taskId> [--daemon | --json] [--follow]
+//! "Rclone UI" enable|disable|run-now <taskId>
+//! ```
+//!
+//! Every subcommand also takes `--data-dir <path> --local-data-dir <path>` (the same pair the
+//! triggers carry) for when the session's data roots differ from the GUI's. Only the local host's
+//! tasks exist on disk, so there is no `--host`.
+//!
+//! Output goes to stdout (JSON with `--json`, otherwise one human-readable line per item);
+//! diagnostics go to stderr. Exit codes are stable for scripts — see the `EXIT_*` constants.
+//!
+//! `enable`/`disable` flip the OS trigger and keep the choice in the task's job file, like the
+//! GUI's toggle; the GUI adopts it into the task on its next status poll or startup reconcile,
+//! so a headless disable isn't re-armed.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::jobfile::{self, JobSpec};
+use super::storeread::{self, AppDirs};
+use super::{history, sanitize_id};
+
+pub const EXIT_OK: i32 = 0;
+/// The operation itself failed (backend error, unreadable file).
+pub const EXIT_FAILED: i32 = 1;
+/// Unknown subcommand, missing argument or bad flag.
+pub const EXIT_USAGE: i32 = 2;
+/// No task with that id is registered on this machine.
+pub const EXIT_NOT_FOUND: i32 = 3;
+
+/// Subcommands handled here (`run-task` is the runner's own entry point).
+pub const COMMANDS: &[&str] = &[
+    "list-tasks",
+    "task-status",
+    "task-history",
+    "task-log",
+    "enable",
+    "disable",
+    "run-now",
+];
+
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+const FOLLOW_POLL: Duration = Duration::from_millis(500);
+
+const USAGE: &str = "usage:
+  list-tasks [--json]
+  task-status <taskId> [--json]
+  task-history <taskId> [--json] [--limit <n>]
+  task-log <taskId> [--daemon | --json] [--follow]
+  enable <taskId> | disable <taskId> | run-now <taskId>
+options:
+  --data-dir <path> --local-data-dir <path>   use these data roots instead of the defaults";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    ListTasks,
+    TaskStatus(String),
+    TaskHistory(String),
+    TaskLog(String),
+    SetEnabled(String, bool),
+    RunNow(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Invocation {
+    command: Command,
+    json: bool,
+    limit: usize,
+    daemon: bool,
+    follow: bool,
+    data_dir: Option<String>,
+    local_data_dir: Option<String>,
+}
+
+/// Parses `args` (without the program name). Flags not meaningful for the subcommand are
+/// rejected rather than ignored, so a typo never silently changes the output format.
+fn parse(args: &[String]) -> Result<Invocation, String> {
+    let (name, rest) = args.split_first().ok_or("missing subcommand")?;
+    let mut positional = Vec::new();
+    let mut json = false;
+    let mut limit = None;
+    let mut daemon = false;
+    let mut follow = false;
+    let mut data_dir = None;
+    let mut local_data_dir = None;
+
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+        match arg.as_str() {
+            "--json" => json = true,
+            "--daemon" => daemon = true,
+            "--follow" | "-f" => follow = true,
+            "--limit" => {
+                let raw = value("--limit")?;
+                limit = Some(
+                    raw.parse::<usize>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| format!("invalid --limit '{}'", raw))?,
+                );
+            }
+            "--data-dir" => data_dir = Some(value("--data-dir")?),
+            "--local-data-dir" => local_data_dir = Some(value("--local-data-dir")?),
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    let takes_task = name != "list-tasks";
+    let task_id = match (takes_task, positional.as_slice()) {
+        (false, []) => String::new(),
+        (true, [id]) => sanitize_id(id)?,
+        (true, []) => return Err(format!("{} needs a task id", name)),
+        _ => {
+            return Err(format!(
+                "unexpected argument '{}'",
+                positional.last().unwrap()
+            ))
+        }
+    };
+    let command = match name.as_str() {
+        "list-tasks" => Command::ListTasks,
+        "task-status" => Command::TaskStatus(task_id),
+        "task-history" => Command::TaskHistory(task_id),
+        "task-log" => Command::TaskLog(task_id),
+        "enable" => Command::SetEnabled(task_id, true),
+        "disable" => Command::SetEnabled(task_id, false),
+        "run-now" => Command::RunNow(task_id),
+        other => return Err(format!("unknown subcommand '{}'", other)),
+    };
+
+    // The runner log is JSON lines underneath; `task-log --json` passes them through.
+    let allowed_json = match command {
+        Command::ListTasks | Command::TaskStatus(_) | Command::TaskHistory(_) => true,
+        Command::TaskLog(_) => !daemon,
+        _ => false,
+    };
+    if json && !allowed_json {
+        return Err(format!("{} has no --json output", name));
+    }
+    if limit.is_some() && !matches!(command, Command::TaskHistory(_)) {
+        return Err("--limit only applies to task-history".to_string());
+    }
+    if (daemon || follow) && !matches!(command, Command::TaskLog(_)) {
+        return Err("--daemon and --follow only apply to task-log".to_string());
+    }
+    if data_dir.is_some() != local_data_dir.is_some() {
+        return Err("--data-dir and --local-data-dir must be given together".to_string());
+    }
+
+    Ok(Invocation {
+        command,
+        json,
+        limit: limit.unwrap_or(DEFAULT_HISTORY_LIMIT),
+        daemon,
+        follow,
+        data_dir,
+        local_data_dir,
+    })
+}
+
+/// Entry point for the subcommands in `COMMANDS`; `args` excludes the program name. Returns
+/// the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    #[cfg(windows)]
+    attach_console();
+
+    let invocation = match parse(args) {
+        Ok(invocation) => invocation,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+    let dirs = match (&invocation.data_dir, &invocation.local_data_dir) {
+        (Some(data), Some(local)) => AppDirs {
+            app_data: PathBuf::from(data),
+            app_local_data: PathBuf::from(local),
+        },
+        _ => match storeread::app_dirs() {
+            Ok(dirs) => dirs,
+            Err(e) => {
+                eprintln!("{}", e);
+                return EXIT_FAILED;
+            }
+        },
+    };
+
+    let result = match &invocation.command {
+        Command::ListTasks => list_tasks(&dirs, invocation.json),
+        Command::TaskStatus(id) => {
+            load(&dirs, id).and_then(|spec| task_status(&dirs, &spec, invocation.json))
+        }
+        Command::TaskHistory(id) => {
+            load(&dirs, id).and_then(|_| task_history(&dirs, id, invocation.limit, invocation.json))
+        }
+        Command::TaskLog(id) => load(&dirs, id).and_then(|_| {
+            let path = if invocation.daemon {
+                history::daemon_log_path(&dirs, id)
+            } else {
+                history::log_path(&dirs, id)
+            };
+            let render = !invocation.daemon && !invocation.json;
+            task_log(&path, invocation.follow, render)
+        }),
+        Command::SetEnabled(id, enabled) => load(&dirs, id).and_then(|_| {
+            super::set_enabled(&dirs, id, *enabled).map_err(Failure::Failed)?;
+            println!("{} {}", id, if *enabled { "enabled" } else { "disabled" });
+            Ok(())
+        }),
+        Command::RunNow(id) => load(&dirs, id).and_then(|_| {
+            super::run_now(&dirs, id).map_err(Failure::Failed)?;
+            println!("{} started", id);
+            Ok(())
+        }),
+    };
+    match result {
+        Ok(()) => EXIT_OK,
+        Err(Failure::NotFound(id)) => {
+            eprintln!("no scheduled task '{}'", id);
+            EXIT_NOT_FOUND
+        }
+        Err(Failure::Failed(e)) => {
+            eprintln!("{}", e);
+            EXIT_FAILED
+        }
+    }
+}
+
+enum Failure {
+    NotFound(String),
+    Failed(String),
+}
+
+fn load(dirs: &AppDirs, task_id: &str) -> Result<JobSpec, Failure> {
+    if !jobfile::job_path(dirs, "local", task_id).is_file() {
+        return Err(Failure::NotFound(task_id.to_string()));
+    }
+    jobfile::load(dirs, "local", task_id).map_err(Failure::Failed)
+}
+
+/// The job file's identity merged with its live status — one object per task in `--json`.
+fn status_json(dirs: &AppDirs, spec: &JobSpec) -> serde_json::Value {
+    let mut value = serde_json::to_value(super::task_status(dirs, spec)).unwrap_or_default();
+    if let Some(object) = value.as_object_mut() {
+        object.insert("name".into(), spec.name.clone().into());
+        object.insert("operation".into(), spec.operation.clone().into());
+        object.insert("cron".into(), spec.cron.clone().into());
+        object.insert("runMode".into(), spec.run_mode.clone().into());
+    }
+    value
+}
+
+fn state_label(status: &serde_json::Value) -> &'static str {
+    let flag = |key: &str| status.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+    if flag("running") {
+        "running"
+    } else if !flag("installed") {
+        "not installed"
+    } else if flag("enabled") {
+        "enabled"
+    } else {
+        "disabled"
+    }
+}
+
+fn describe_last(last: Option<&serde_json::Value>) -> String {
+    let Some(last) = last.filter(|v| !v.is_null()) else {
+        return "never".to_string();
+    };
+    let ts = last.get("ts").and_then(|v| v.as_str()).unwrap_or("?");
+    let error = last.get("error").and_then(|v| v.as_str()).unwrap_or("");
+    if last.get("interrupted").and_then(|v| v.as_bool()) == Some(true) {
+        format!("{} interrupted", ts)
+    } else if last.get("success").and_then(|v| v.as_bool()) == Some(true) {
+        format!("{} ok", ts)
+    } else if error.is_empty() {
+        format!("{} failed", ts)
+    } else {
+        format!("{} failed: {}", ts, first_line(error))
+    }
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or("")
+}
+
+fn print_json(value: &serde_json::Value) -> Result<(), Failure> {
+    let text = serde_json::to_string_pretty(value).map_err(|e| Failure::Failed(e.to_string()))?;
+    println!("{}", text);
+    Ok(())
+}
+
+fn list_tasks(dirs: &AppDirs, json: bool) -> Result<(), Failure> {
+    let statuses: Vec<serde_json::Value> = jobfile::list(dirs, "local")
+        .iter()
+        .map(|spec| status_json(dirs, spec))
+        .collect();
+    if json {
+        return print_json(&serde_json::Value::Array(statuses));
+    }
+    for status in &statuses {
+        println!("{}", list_row(status));
+    }
+    Ok(())
+}
+
+/// `<id>\t<state>\t<cron>\t<operation>\t<name>\t<last run>` — tab-separated so `cut` works.
+fn list_row(status: &serde_json::Value) -> String {
+    let text = |key: &str| status.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        text("taskId"),
+        state_label(status),
+        text("cron"),
+        text("operation"),
+        text("name"),
+        describe_last(status.get("lastFinished"))
+    )
+}
+
+fn task_status(dirs: &AppDirs, spec: &JobSpec, json: bool) -> Result<(), Failure> {
+    let status = status_json(dirs, spec);
+    if json {
+        return print_json(&status);
+    }
+    let text = |key: &str| status.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    println!("task:      {}", spec.task_id);
+    println!("name:      {}", spec.name);
+    println!("operation: {}", spec.operation);
+    println!("schedule:  {}", spec.cron);
+    println!("run mode:  {}", spec.run_mode);
+    println!("state:     {}", state_label(&status));
+    println!("last run:  {}", describe_last(status.get("lastFinished")));
+    if !text("warning").is_empty() {
+        println!("warning:   {}", text("warning"));
+    }
+    Ok(())
+}
+
+fn task_history(dirs: &AppDirs, task_id: &str, limit: usize, json: bool) -> Result<(), Failure> {
+    let lines = history::read(dirs, task_id, limit);
+    if json {
+        return print_json(&serde_json::Value::Array(lines));
+    }
+    for line in &lines {
+        println!("{}", history_row(line));
+    }
+    Ok(())
+}
+
+/// One history record as `<ts>\t<event>\t<runId>\t<detail>`, newest first like `history::read`.
+fn history_row(line: &serde_json::Value) -> String {
+    let text = |key: &str| line.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    let detail = match text("event") {
+        "finished" => {
+            let seconds = line.get("durationMs").and_then(|v| v.as_u64()).unwrap_or(0) / 1000;
+            if line.get("success").and_then(|v| v.as_bool()) == Some(true) {
+                format!("ok in {}s", seconds)
+            } else {
+                format!("failed after {}s: {}", seconds, first_line(text("error")))
+            }
+        }
+        "skipped" => text("reason").to_string(),
+        _ => String::new(),
+    };
+    format!(
+        "{}\t{}\t{}\t{}",
+        text("ts"),
+        text("event"),
+        text("runId"),
+        detail
+    )
+    .trim_end()
+    .to_string()
+}
+
+/// Prints the log, then with `follow` keeps printing what is appended. Rotation starts a fresh
+/// file, so one shorter than what was already printed is read from the start. `render` turns
+/// runner log records into text, so only whole lines are consumed.
+fn task_log(path: &Path, follow: bool, render: bool) -> Result<(), Failure> {
+    let mut stdout = std::io::stdout();
+    let mut offset = 0u64;
+    loop {
+        if let Ok(mut file) = std::fs::File::open(path) {
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            if len < offset {
+                offset = 0;
+            }
+            if len > offset {
+                let mut chunk = Vec::new();
+                file.seek(SeekFrom::Start(offset))
+                    .and_then(|_| file.take(len - offset).read_to_end(&mut chunk))
+                    .map_err(|e| {
+                        Failure::Failed(format!("failed to read {}: {}", path.display(), e))
+                    })?;
+                if render {
+                    let whole = chunk.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+                    chunk.truncate(whole);
+                    chunk = String::from_utf8_lossy(&chunk)
+                        .lines()
+                        .map(|line| match history::LogRecord::parse(line) {
+                            Some(record) => format!("{}\n", record.render()),
+                            None => format!("{}\n", line),
+                        })
+                        .collect::<String>()
+                        .into_bytes();
+                    offset += whole as u64;
+                } else {
+                    offset += chunk.len() as u64;
+                }
+                // A closed pipe (`| head`) ends the command quietly.
+                if stdout
+                    .write_all(&chunk)
+                    .and_then(|_| stdout.flush())
+                    .is_err()
+                {
+                    return Ok(());
+                }
+            }
+        }
+        if !follow {
+            return Ok(());
+        }
+        std::thread::sleep(FOLLOW_POLL);
+    }
+}
+
+/// Release builds use the GUI subsystem, so a CLI process starts without a console; attach to
+/// the invoking terminal's so stdout/stderr reach it.
+#[cfg(windows)]
+fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    // Fails harmlessly when there is no parent console or one is already attached.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_subcommands_and_rejects_misplaced_flags() {
+        let invocation = parse(&args("task-history nightly --json --limit 5")).unwrap();
+        assert_eq!(invocation.command, Command::TaskHistory("nightly".into()));
+        assert!(invocation.json);
+        assert_eq!(invocation.limit, 5);
+
+        let invocation = parse(&args("task-log nightly --daemon -f")).unwrap();
+        assert_eq!(invocation.command, Command::TaskLog("nightly".into()));
+        assert!(invocation.daemon && invocation.follow);
+
+        assert_eq!(
+            parse(&args("disable nightly")).unwrap().command,
+            Command::SetEnabled("nightly".into(), false)
+        );
+        assert_eq!(
+            parse(&args("list-tasks")).unwrap().limit,
+            DEFAULT_HISTORY_LIMIT
+        );
+
+        for bad in [
+            "",
+            "list-tasks extra",
+            "task-status",
+            "task-status ../etc",
+            "task-status a b",
+            "run-now nightly --json",
+            "task-status nightly --limit 5",
+            "task-history nightly --limit 0",
+            "list-tasks --follow",
+            "list-tasks --data-dir /tmp",
+            "list-tasks --verbose",
+            "frobnicate nightly",
+        ] {
+            assert!(parse(&args(bad)).is_err(), "accepted '{}'", bad);
+        }
+    }
+
+    #[test]
+    fn formats_rows_for_the_terminal() {
+        let status = serde_json::json!({
+            "taskId": "nightly",
+            "installed": true,
+            "enabled": false,
+            "running": false,
+            "cron": "0 3 * * *",
+            "operation": "sync",
+            "name": "Photos",
+            "lastFinished": {
+                "ts": "2026-01-01T03:00:00.000Z",
+                "success": false,
+                "error": "directory not found\nmore detail",
+            },
+        });
+        assert_eq!(
+            list_row(&status),
+            "nightly\tdisabled\t0 3 * * *\tsync\tPhotos\t2026-01-01T03:00:00.000Z failed: directory not found"
+        );
+        assert_eq!(describe_last(Some(&serde_json::Value::Null)), "never");
+
+        let finished = serde_json::json!({
+            "event": "finished",
+            "runId": "r1",
+            "ts": "2026-01-01T03:00:00.000Z",
+            "success": true,
+            "durationMs": 61_500,
+        });
+        assert_eq!(
+            history_row(&finished),
+            "2026-01-01T03:00:00.000Z\tfinished\tr1\tok in 61s"
+        );
+        let started = serde_json::json!({"event": "started", "runId": "r1", "ts": "t"});
+        assert_eq!(history_row(&started), "t\tstarted\tr1");
+    }
+}
@@ -80,6 +80,23 @@ pub struct CronSpec {
     pub dow: Field,
 }
 
+impl CronSpec {
+    /// Whether `self` and `other` fire at the same times however they're written — `1-5` is
+    /// `1,2,3,4,5`. The day fields must also agree on being restricted, which decides cron's
+    /// dom/dow OR.
+    pub fn fires_like(&self, other: &CronSpec) -> bool {
+        let same =
+            |a: &Field, b: &Field, min: u16, max: u16| a.expanded(min, max) == b.expanded(min, max);
+        same(&self.minute, &other.minute, 0, 59)
+            && same(&self.hour, &other.hour, 0, 23)
+            && same(&self.dom, &other.dom, 1, 31)
+            && same(&self.month, &other.month, 1, 12)
+            && same(&self.dow, &other.dow, 0, 6)
+            && self.dom.restricted() == other.dom.restricted()
+            && self.dow.restricted() == other.dow.restricted()
+    }
+}
+
 const MONTH_NAMES: [&str; 12] = [
     "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
 ];
@@ -371,6 +388,48 @@ pub fn to_crontab(spec: &CronSpec) -> String {
     )
 }
 
+// ---------------------------------------------------------------------------
+// systemd timers (exported units only — see artifact.rs)
+// ---------------------------------------------------------------------------
+
+/// `OnCalendar=` values for a timer. Within one value systemd ANDs the weekday with the date,
+/// so cron's dom/dow OR becomes two values (a timer fires on the union of its OnCalendar lines).
+pub fn to_systemd_calendar(spec: &CronSpec) -> Vec<String> {
+    fn list(field: &Field, min: u16, max: u16, name: impl Fn(u16) -> String) -> String {
+        if field.wildcard {
+            "*".to_string()
+        } else {
+            field
+                .expanded(min, max)
+                .into_iter()
+                .map(name)
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+    }
+    const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    let two_digits = |v: u16| format!("{:02}", v);
+    let time = format!(
+        "{}:{}:00",
+        list(&spec.hour, 0, 23, two_digits),
+        list(&spec.minute, 0, 59, two_digits)
+    );
+    let month = list(&spec.month, 1, 12, two_digits);
+    let dom = list(&spec.dom, 1, 31, two_digits);
+    let dow = list(&spec.dow, 0, 6, |v| WEEKDAYS[v as usize].to_string());
+
+    if day_fields_use_or(spec) {
+        vec![
+            format!("*-{}-{} {}", month, dom, time),
+            format!("{} *-{}-* {}", dow, month, time),
+        ]
+    } else if spec.dow.wildcard {
+        vec![format!("*-{}-{} {}", month, dom, time)]
+    } else {
+        vec![format!("{} *-{}-{} {}", dow, month, dom, time)]
+    }
+}
+
 // ---------------------------------------------------------------------------
 // macOS launchd (StartCalendarInterval)
 // ---------------------------------------------------------------------------
@@ -646,6 +705,574 @@ fn uniform_minute_interval(minute: &Field) -> Option<u16> {
     Some(interval)
 }
 
+// ---------------------------------------------------------------------------
+// Human-readable descriptions
+// ---------------------------------------------------------------------------
+
+/// The wording of one language. Braced placeholders are filled by `describe`; lists are joined
+/// with `sep` and a final `and`, runs of three or more consecutive names with `range`.
+struct Phrases {
+    every_minute: &'static str,
+    /// {n}
+    every_n_minutes: &'static str,
+    /// {n} {minutes}
+    every_n_hours: &'static str,
+    /// {time} — appended to `every_n_hours` when the first run isn't at midnight.
+    starting: &'static str,
+    /// {minutes}
+    hourly_at: &'static str,
+    /// {minutes} — followed by the hour windows.
+    past_hour: &'static str,
+    /// {times}
+    at: &'static str,
+    /// {m}: one minute value, two digits.
+    minute: &'static str,
+    /// {days}
+    days_of_month: &'static str,
+    /// {dom} {dow} — cron's OR, when both day fields are restricted.
+    either: &'static str,
+    /// {dom} {dow} — the AND, when one of them is a `*/n` step.
+    both: &'static str,
+    /// {months}
+    in_months: &'static str,
+    /// {a} {b}
+    range: &'static str,
+    sep: &'static str,
+    and: &'static str,
+    /// Between the time, day and month clauses.
+    clause_sep: &'static str,
+    weekdays: [&'static str; 7],
+    months: [&'static str; 12],
+}
+
+const ENGLISH_WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const ENGLISH_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const EN: Phrases = Phrases {
+    every_minute: "Every minute",
+    every_n_minutes: "Every {n} minutes",
+    every_n_hours: "Every {n} hours at {minutes}",
+    starting: "starting at {time}",
+    hourly_at: "Every hour at {minutes}",
+    past_hour: "At {minutes} past every hour",
+    at: "At {times}",
+    minute: ":{m}",
+    days_of_month: "on day {days} of the month",
+    either: "{dom}, or on {dow}",
+    both: "{dom}, but only on {dow}",
+    in_months: "in {months}",
+    range: "{a} to {b}",
+    sep: ", ",
+    and: " and ",
+    clause_sep: ", ",
+    weekdays: ENGLISH_WEEKDAYS,
+    months: ENGLISH_MONTHS,
+};
+
+const PIRATE: Phrases = Phrases {
+    every_minute: "Ev'ry minute",
+    every_n_minutes: "Ev'ry {n} minutes",
+    every_n_hours: "Ev'ry {n} hours at {minutes}",
+    starting: "startin' at {time}",
+    hourly_at: "Ev'ry hour at {minutes}",
+    past_hour: "At {minutes} past ev'ry hour",
+    at: "At {times}",
+    minute: ":{m}",
+    days_of_month: "on day {days} o' the month",
+    either: "{dom}, or on {dow}",
+    both: "{dom}, but only on {dow}",
+    in_months: "in {months}",
+    range: "{a} t' {b}",
+    sep: ", ",
+    and: " an' ",
+    clause_sep: ", ",
+    weekdays: ENGLISH_WEEKDAYS,
+    months: ENGLISH_MONTHS,
+};
+
+const DE: Phrases = Phrases {
+    every_minute: "Jede Minute",
+    every_n_minutes: "Alle {n} Minuten",
+    every_n_hours: "Alle {n} Stunden um {minutes}",
+    starting: "ab {time}",
+    hourly_at: "Jede Stunde um {minutes}",
+    past_hour: "Um {minutes} jeder Stunde",
+    at: "Um {times}",
+    minute: ":{m}",
+    days_of_month: "an Tag {days} des Monats",
+    either: "{dom} oder {dow}",
+    both: "{dom}, aber nur {dow}",
+    in_months: "im {months}",
+    range: "{a} bis {b}",
+    sep: ", ",
+    and: " und ",
+    clause_sep: ", ",
+    weekdays: [
+        "Sonntag",
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+    ],
+    months: [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+};
+
+const ES: Phrases = Phrases {
+    every_minute: "Cada minuto",
+    every_n_minutes: "Cada {n} minutos",
+    every_n_hours: "Cada {n} horas, en el minuto {minutes}",
+    starting: "desde las {time}",
+    hourly_at: "Cada hora, en el minuto {minutes}",
+    past_hour: "En el minuto {minutes} de cada hora",
+    at: "A las {times}",
+    minute: "{m}",
+    days_of_month: "el día {days} del mes",
+    either: "{dom} o {dow}",
+    both: "{dom}, pero solo {dow}",
+    in_months: "en {months}",
+    range: "{a} a {b}",
+    sep: ", ",
+    and: " y ",
+    clause_sep: ", ",
+    weekdays: [
+        "domingo",
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+    ],
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+};
+
+// Polish and Romanian inflect with the number ("2 minuty" / "5 minut", "20 de minute"), so the
+// interval phrases use the standard abbreviations instead.
+const PL: Phrases = Phrases {
+    every_minute: "Co minutę",
+    every_n_minutes: "Co {n} min",
+    every_n_hours: "Co {n} godz., w minucie {minutes}",
+    starting: "od {time}",
+    hourly_at: "Co godzinę, w minucie {minutes}",
+    past_hour: "W minucie {minutes} każdej godziny",
+    at: "O godz. {times}",
+    minute: "{m}",
+    days_of_month: "w dniu {days} miesiąca",
+    either: "{dom} lub {dow}",
+    both: "{dom}, ale tylko {dow}",
+    in_months: "w miesiącach: {months}",
+    range: "{a}–{b}",
+    sep: ", ",
+    and: " i ",
+    clause_sep: ", ",
+    weekdays: [
+        "niedziela",
+        "poniedziałek",
+        "wtorek",
+        "środa",
+        "czwartek",
+        "piątek",
+        "sobota",
+    ],
+    months: [
+        "styczeń",
+        "luty",
+        "marzec",
+        "kwiecień",
+        "maj",
+        "czerwiec",
+        "lipiec",
+        "sierpień",
+        "wrzesień",
+        "październik",
+        "listopad",
+        "grudzień",
+    ],
+};
+
+const RO: Phrases = Phrases {
+    every_minute: "În fiecare minut",
+    every_n_minutes: "La fiecare {n} min",
+    every_n_hours: "La fiecare {n} ore, la minutul {minutes}",
+    starting: "începând cu {time}",
+    hourly_at: "În fiecare oră, la minutul {minutes}",
+    past_hour: "La minutul {minutes} al fiecărei ore",
+    at: "La {times}",
+    minute: "{m}",
+    days_of_month: "în ziua {days} a lunii",
+    either: "{dom} sau {dow}",
+    both: "{dom}, dar numai {dow}",
+    in_months: "în {months}",
+    range: "{a}–{b}",
+    sep: ", ",
+    and: " și ",
+    clause_sep: ", ",
+    weekdays: [
+        "duminică",
+        "luni",
+        "marți",
+        "miercuri",
+        "joi",
+        "vineri",
+        "sâmbătă",
+    ],
+    months: [
+        "ianuarie",
+        "februarie",
+        "martie",
+        "aprilie",
+        "mai",
+        "iunie",
+        "iulie",
+        "august",
+        "septembrie",
+        "octombrie",
+        "noiembrie",
+        "decembrie",
+    ],
+};
+
+const ZH: Phrases = Phrases {
+    every_minute: "每分钟",
+    every_n_minutes: "每 {n} 分钟",
+    every_n_hours: "每 {n} 小时，于第 {minutes} 分钟",
+    starting: "从 {time} 开始",
+    hourly_at: "每小时的第 {minutes} 分钟",
+    past_hour: "每小时的第 {minutes} 分钟",
+    at: "在 {times}",
+    minute: "{m}",
+    days_of_month: "每月 {days} 日",
+    either: "{dom}或{dow}",
+    both: "{dom}，且仅限{dow}",
+    in_months: "{months}",
+    range: "{a}至{b}",
+    sep: "、",
+    and: "和",
+    clause_sep: "，",
+    weekdays: [
+        "星期日",
+        "星期一",
+        "星期二",
+        "星期三",
+        "星期四",
+        "星期五",
+        "星期六",
+    ],
+    months: [
+        "一月",
+        "二月",
+        "三月",
+        "四月",
+        "五月",
+        "六月",
+        "七月",
+        "八月",
+        "九月",
+        "十月",
+        "十一月",
+        "十二月",
+    ],
+};
+
+const JA: Phrases = Phrases {
+    every_minute: "毎分",
+    every_n_minutes: "{n}分ごと",
+    every_n_hours: "{n}時間ごと、{minutes}",
+    starting: "{time}から",
+    hourly_at: "毎時{minutes}",
+    past_hour: "毎時{minutes}",
+    at: "{times}",
+    minute: "{m}分",
+    days_of_month: "毎月{days}日",
+    either: "{dom}または{dow}",
+    both: "{dom}（{dow}のみ）",
+    in_months: "{months}",
+    range: "{a}〜{b}",
+    sep: "、",
+    and: "と",
+    clause_sep: "、",
+    weekdays: [
+        "日曜日",
+        "月曜日",
+        "火曜日",
+        "水曜日",
+        "木曜日",
+        "金曜日",
+        "土曜日",
+    ],
+    months: [
+        "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+    ],
+};
+
+/// The languages the README ships in, by BCP 47 primary subtag ("de-AT" → German); English for
+/// the rest.
+fn phrases(language: &str) -> &'static Phrases {
+    let language = language.to_ascii_lowercase();
+    if language.contains("pirate") {
+        return &PIRATE;
+    }
+    match language.split(['-', '_']).next().unwrap_or("") {
+        "de" => &DE,
+        "es" => &ES,
+        "ja" => &JA,
+        "pl" => &PL,
+        "ro" => &RO,
+        "zh" => &ZH,
+        _ => &EN,
+    }
+}
+
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut text = template.to_string();
+    for (key, value) in values {
+        text = text.replace(&format!("{{{}}}", key), value);
+    }
+    text
+}
+
+fn join_list(items: &[String], words: &Phrases) -> String {
+    match items.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{}{}{}", rest.join(words.sep), words.and, last),
+    }
+}
+
+/// Consecutive runs of sorted `values`, as (first, last).
+fn runs(values: &[u16]) -> Vec<(u16, u16)> {
+    let mut runs: Vec<(u16, u16)> = Vec::new();
+    for &v in values {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == v => *last = v,
+            _ => runs.push((v, v)),
+        }
+    }
+    runs
+}
+
+/// (first, step) when `values` are three or more points a constant step above 1 apart.
+fn progression(values: &[u16]) -> Option<(u16, u16)> {
+    if values.len() < 3 {
+        return None;
+    }
+    let step = values[1] - values[0];
+    (step > 1 && values.windows(2).all(|pair| pair[1] - pair[0] == step))
+        .then_some((values[0], step))
+}
+
+/// Weekdays or months: runs of three or more become a range ("Monday to Friday").
+fn name_list(values: &[u16], name: impl Fn(u16) -> String, words: &Phrases) -> String {
+    let mut items = Vec::new();
+    for (first, last) in runs(values) {
+        if last >= first + 2 {
+            items.push(fill(
+                words.range,
+                &[("a", &name(first)), ("b", &name(last))],
+            ));
+        } else {
+            items.extend((first..=last).map(&name));
+        }
+    }
+    join_list(&items, words)
+}
+
+/// Days of the month: "1–7", or "1, 3 … 31" for a step.
+fn number_list(values: &[u16], words: &Phrases) -> String {
+    if let Some((first, step)) = progression(values) {
+        return format!(
+            "{}{}{} … {}",
+            first,
+            words.sep,
+            first + step,
+            values[values.len() - 1]
+        );
+    }
+    let mut items = Vec::new();
+    for (first, last) in runs(values) {
+        if last >= first + 2 {
+            items.push(format!("{}–{}", first, last));
+        } else {
+            items.extend((first..=last).map(|v| v.to_string()));
+        }
+    }
+    join_list(&items, words)
+}
+
+/// Whether `field` takes every value from `min` to `max` — `*`, but also `*/1` or `0-59`, which
+/// read as "every minute", not "every 1 minutes".
+fn covers(field: &Field, min: u16, max: u16) -> bool {
+    field.wildcard || field.values.len() == usize::from(max - min + 1)
+}
+
+fn describe_time(spec: &CronSpec, words: &Phrases) -> String {
+    let every_minute = covers(&spec.minute, 0, 59);
+    let every_hour = covers(&spec.hour, 0, 23);
+    let minutes = spec.minute.expanded(0, 59);
+    let hours = spec.hour.expanded(0, 23);
+    let minute_list = || {
+        let items: Vec<String> = minutes
+            .iter()
+            .map(|m| fill(words.minute, &[("m", &format!("{:02}", m))]))
+            .collect();
+        join_list(&items, words)
+    };
+    let windows = || {
+        let items: Vec<String> = runs(&hours)
+            .into_iter()
+            .map(|(first, last)| format!("{:02}:00–{:02}:59", first, last))
+            .collect();
+        join_list(&items, words)
+    };
+
+    // Only an interval that starts on the hour reads as "every n minutes"; `5-59/15` is a list.
+    let interval = if every_minute {
+        None
+    } else if minutes[0] == 0 {
+        uniform_minute_interval(&spec.minute)
+    } else {
+        None
+    };
+    if every_minute || interval.is_some() {
+        let base = match interval {
+            Some(n) => fill(words.every_n_minutes, &[("n", &n.to_string())]),
+            None => words.every_minute.to_string(),
+        };
+        if every_hour {
+            return base;
+        }
+        return format!("{}{}{}", base, words.clause_sep, windows());
+    }
+    if every_hour {
+        return fill(words.hourly_at, &[("minutes", &minute_list())]);
+    }
+    if minutes.len() * hours.len() <= 6 {
+        let times: Vec<String> = hours
+            .iter()
+            .flat_map(|h| minutes.iter().map(move |m| format!("{:02}:{:02}", h, m)))
+            .collect();
+        return fill(words.at, &[("times", &join_list(&times, words))]);
+    }
+    if let Some((first, step)) = progression(&hours) {
+        // Only when the steps run to the end of the day — `0-12/3` stops early.
+        if hours[hours.len() - 1] + step > 23 {
+            let base = fill(
+                words.every_n_hours,
+                &[("n", &step.to_string()), ("minutes", &minute_list())],
+            );
+            if first == 0 {
+                return base;
+            }
+            let time = format!("{:02}:{:02}", first, minutes[0]);
+            return format!(
+                "{}{}{}",
+                base,
+                words.clause_sep,
+                fill(words.starting, &[("time", &time)])
+            );
+        }
+    }
+    format!(
+        "{}{}{}",
+        fill(words.past_hour, &[("minutes", &minute_list())]),
+        words.clause_sep,
+        windows()
+    )
+}
+
+/// A sentence saying when `spec` fires — "Every 15 minutes, 09:00–17:59, Monday to Friday" —
+/// in `language` (a BCP 47 tag like "de-DE", or "pirate"). Both day fields restricted read as
+/// either/or, exactly as cron matches them; a `*/n` day field against a restricted one as both.
+pub fn describe(spec: &CronSpec, language: &str) -> String {
+    let words = phrases(language);
+    let mut clauses = vec![describe_time(spec, words)];
+
+    // A `*/1` day field is no constraint at all; an explicit `1-31` still takes part in the OR.
+    let dom = (!(spec.dom.star && covers(&spec.dom, 1, 31))).then(|| {
+        fill(
+            words.days_of_month,
+            &[("days", &number_list(&spec.dom.expanded(1, 31), words))],
+        )
+    });
+    let dow = (!(spec.dow.star && covers(&spec.dow, 0, 6))).then(|| {
+        name_list(
+            &spec.dow.expanded(0, 6),
+            |v| words.weekdays[v as usize].to_string(),
+            words,
+        )
+    });
+    match (dom, dow) {
+        (Some(dom), Some(dow)) => {
+            let template = if day_fields_use_or(spec) {
+                words.either
+            } else {
+                words.both
+            };
+            clauses.push(fill(template, &[("dom", &dom), ("dow", &dow)]));
+        }
+        (dom, dow) => clauses.extend(dom.or(dow)),
+    }
+
+    if !covers(&spec.month, 1, 12) {
+        let months = name_list(
+            &spec.month.expanded(1, 12),
+            |v| words.months[v as usize - 1].to_string(),
+            words,
+        );
+        clauses.push(fill(words.in_months, &[("months", &months)]));
+    }
+    clauses.join(words.clause_sep)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -955,4 +1582,85 @@ mod tests {
         assert!(matches(&weekly, 0, 0, 20, 3, 1));
         assert!(!matches(&weekly, 0, 0, 20, 3, 2));
     }
+
+    #[test]
+    fn systemd_calendar_keeps_day_semantics() {
+        assert_eq!(
+            to_systemd_calendar(&parse("30 3 * * *").unwrap()),
+            vec!["*-*-* 03:30:00"]
+        );
+        assert_eq!(
+            to_systemd_calendar(&parse("0 */6 * * 1-5").unwrap()),
+            vec!["Mon,Tue,Wed,Thu,Fri *-*-* 00,06,12,18:00:00"]
+        );
+        // Both day fields restricted: cron ORs them, so two OnCalendar values.
+        assert_eq!(
+            to_systemd_calendar(&parse("0 3 1 6 1").unwrap()),
+            vec!["*-06-01 03:00:00", "Mon *-06-* 03:00:00"]
+        );
+        // A `*/n` day field is unrestricted in cron's eyes: the fields AND.
+        assert_eq!(
+            to_systemd_calendar(&parse("0 0 */10 * 0").unwrap()),
+            vec!["Sun *-*-01,11,21,31 00:00:00"]
+        );
+    }
+
+    #[test]
+    fn describes_schedules_and_day_semantics() {
+        let describe_en = |expr: &str| describe(&parse(expr).unwrap(), "en-US");
+        assert_eq!(
+            describe_en("*/15 9-17 * * 1-5"),
+            "Every 15 minutes, 09:00–17:59, Monday to Friday"
+        );
+        assert_eq!(describe_en("30 2 * * *"), "At 02:30");
+        assert_eq!(
+            describe_en("0 */6 * * *"),
+            "At 00:00, 06:00, 12:00 and 18:00"
+        );
+        assert_eq!(
+            describe_en("15 1-23/2 * * *"),
+            "Every 2 hours at :15, starting at 01:15"
+        );
+        assert_eq!(describe_en("5,35 * * * *"), "Every hour at :05 and :35");
+        // Both day fields restricted: either matches.
+        assert_eq!(
+            describe_en("0 3 1,15 * 0"),
+            "At 03:00, on day 1 and 15 of the month, or on Sunday"
+        );
+        // A `*/n` day field is unrestricted to cron: both must match.
+        assert_eq!(
+            describe_en("0 0 */10 * 1"),
+            "At 00:00, on day 1, 11 … 31 of the month, but only on Monday"
+        );
+        assert_eq!(
+            describe_en("0 12 * 1-3,7 *"),
+            "At 12:00, in January to March and July"
+        );
+        // A step of 1 is every value, not "every 1 minutes".
+        assert_eq!(describe_en("*/1 * * * *"), "Every minute");
+        assert_eq!(
+            describe_en("*/1 */1 */1 */1 1-5"),
+            "Every minute, Monday to Friday"
+        );
+        assert_eq!(describe_en("0 */1 * * *"), "Every hour at :00");
+
+        let spec = parse("*/15 9-17 * * 1-5").unwrap();
+        assert_eq!(
+            describe(&spec, "de-DE"),
+            "Alle 15 Minuten, 09:00–17:59, Montag bis Freitag"
+        );
+        assert_eq!(
+            describe(&spec, "zh-CN"),
+            "每 15 分钟，09:00–17:59，星期一至星期五"
+        );
+        assert_eq!(
+            describe(&spec, "ja"),
+            "15分ごと、09:00–17:59、月曜日〜金曜日"
+        );
+        assert_eq!(
+            describe(&spec, "pirate"),
+            "Ev'ry 15 minutes, 09:00–17:59, Monday t' Friday"
+        );
+        assert_eq!(describe(&spec, "tlh"), describe(&spec, "en"));
+    }
 }
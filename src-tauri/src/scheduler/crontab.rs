@@ -22,9 +22,10 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use super::storeread::AppDirs;
-use super::{InstallState, RenderedSchedule, SchedulerBackend};
+use super::{ArtifactView, InstallState, RenderedSchedule, SchedulerBackend};
 
-const MARKER_PREFIX: &str = "# rclone-ui-task: ";
+/// Precedes each managed entry line (adopt.rs skips both).
+pub(super) const MARKER_PREFIX: &str = "# rclone-ui-task: ";
 const DISABLED_PREFIX: &str = "#off# ";
 
 /// A `Command` for a host program — direct off Flatpak, `flatpak-spawn --host <program>` inside
@@ -269,7 +270,68 @@ impl CrontabBackend {
     }
 }
 
+/// Shell words of a command we wrote (single quotes, backslash escapes), up to the first
+/// redirection.
+fn command_words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                word.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '\\' => {
+                in_word = true;
+                word.extend(chars.next());
+            }
+            '>' | '<' => break,
+            c if c.is_whitespace() => {
+                if std::mem::take(&mut in_word) {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+impl CrontabBackend {
+    /// An entry line as an ArtifactView: the schedule fields, and the program and args inside
+    /// the `/bin/sh -c` wrapper (entries written before the wrapper existed are read as-is).
+    fn view(entry: &str) -> Option<ArtifactView> {
+        let enabled = !entry.starts_with(DISABLED_PREFIX);
+        let mut parts = entry.trim_start_matches(DISABLED_PREFIX).splitn(6, ' ');
+        let schedule = parts.by_ref().take(5).collect::<Vec<_>>().join(" ");
+        let command = parts.next()?.replace(r"\%", "%");
+        let mut words = command_words(&command);
+        if words.len() == 3 && words[0] == "/bin/sh" && words[1] == "-c" {
+            words = command_words(&words[2]);
+        }
+        let mut words = words.into_iter();
+        Some(ArtifactView {
+            enabled,
+            schedule,
+            program: words.next()?,
+            args: words.collect(),
+        })
+    }
+}
+
 impl SchedulerBackend for CrontabBackend {
+    fn kind(&self) -> &'static str {
+        "crontab"
+    }
+
     fn install(&self, task_id: &str, rendered: &RenderedSchedule) -> Result<(), String> {
         let entry = Self::build_entry(rendered)?;
         // Install directly in the target state — a disabled task must never be briefly armed.
@@ -366,6 +428,45 @@ impl SchedulerBackend for CrontabBackend {
             None => Ok(InstallState::NotInstalled),
         }
     }
+
+    fn expected(
+        &self,
+        _task_id: &str,
+        rendered: &RenderedSchedule,
+    ) -> Result<ArtifactView, String> {
+        let entry = Self::build_entry(rendered)?;
+        let mut view = Self::view(&entry).ok_or("Malformed crontab entry")?;
+        view.enabled = rendered.enabled;
+        Ok(view)
+    }
+
+    fn inspect(&self, task_id: &str) -> Result<Option<ArtifactView>, String> {
+        let content = Self::read()?;
+        match Self::find_entry(&content, task_id) {
+            Some(entry) => Self::view(&entry)
+                .map(Some)
+                .ok_or_else(|| "its crontab entry is malformed".to_string()),
+            None => Ok(None),
+        }
+    }
+}
+
+/// The user's crontab as-is, for adoption scans (adopt.rs).
+pub fn read_user_crontab() -> Result<String, String> {
+    CrontabBackend::read()
+}
+
+/// Comments out adopted lines (see `adopt::retire`) under the crontab lock. Returns how many
+/// were found; a line the user changed since the scan is left alone.
+pub fn retire_lines(dirs: &AppDirs, lines: &[String]) -> Result<u32, String> {
+    let backend = CrontabBackend::new(dirs);
+    let _lock = CrontabLock::acquire(&backend.lock_path)?;
+    let content = CrontabBackend::read()?;
+    let (result, retired) = super::adopt::retire(&content, lines);
+    if retired > 0 {
+        backend.write(&result)?;
+    }
+    Ok(retired)
 }
 
 /// Uninstall managed pairs except those in `keep` (the task ids that still have job files —
@@ -461,6 +562,37 @@ mod tests {
         assert!(entry.contains("Application Support"));
     }
 
+    #[test]
+    fn entries_read_back_as_what_was_rendered() {
+        let rendered = RenderedSchedule {
+            cron: super::super::cronconv::parse("30 2 * * 1-5").unwrap(),
+            program: std::path::PathBuf::from("/home/x/Apps/Rclone UI's.AppImage"),
+            args: vec![
+                "run-task".into(),
+                "abc".into(),
+                "--data-dir".into(),
+                "/home/x/.local/share/100% data".into(),
+            ],
+            display_name: "abc".into(),
+            user_mode: true,
+            enabled: false,
+            max_run_seconds: 86_400,
+        };
+        let entry = CrontabBackend::build_entry(&rendered).unwrap();
+        let view = CrontabBackend::view(&format!("{}{}", DISABLED_PREFIX, entry)).unwrap();
+        assert!(!view.enabled);
+        assert_eq!(view.schedule, "30 2 * * 1,2,3,4,5");
+        assert_eq!(view.program, "/home/x/Apps/Rclone UI's.AppImage");
+        assert_eq!(view.args, rendered.args);
+
+        // An entry from before the sh -c wrapper names the same program and args.
+        let legacy =
+            CrontabBackend::view("30 2 * * 1-5 '/opt/Rclone UI' 'run-task' 'abc' >/dev/null 2>&1")
+                .unwrap();
+        assert_eq!(legacy.program, "/opt/Rclone UI");
+        assert_eq!(legacy.args, vec!["run-task", "abc"]);
+    }
+
     #[test]
     fn only_no_crontab_failures_read_as_empty() {
         assert!(stderr_means_no_crontab("crontab: no crontab for alice"));
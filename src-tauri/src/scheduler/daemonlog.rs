@@ -0,0 +1,284 @@
+//! Failure summary parsed from the transient daemon's log (`<task>.daemon.log`).
+//!
+//! The job-level RC error is usually just "N errors" or the last error rclone saw; the per-file
+//! reasons only exist as `ERROR :` lines in the daemon's stderr. The runner records the log's
+//! length before spawning the daemon, so a run's segment is everything after that offset — no
+//! timestamp parsing, and earlier runs' errors never leak into this run's summary.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Examples kept per category — enough to recognize the pattern without bloating history lines.
+const MAX_EXAMPLES: usize = 3;
+/// Examples are single log lines; a pathological one (a JSON error body) is cut here.
+const MAX_EXAMPLE_CHARS: usize = 300;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureSummary {
+    pub total: u32,
+    /// Most frequent first.
+    pub categories: Vec<FailureCategory>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureCategory {
+    /// "permissionDenied" | "quotaExceeded" | "rateLimited" | "authExpired" | "unauthorized" |
+    /// "notFound" | "other" — stable wire strings (history lines and webhook data).
+    pub kind: String,
+    pub count: u32,
+    pub examples: Vec<String>,
+}
+
+/// Buckets one error message. Order matters: providers phrase rate limits and expired tokens as
+/// 403s (Drive's `rateLimitExceeded`, OneDrive's `InvalidAuthenticationToken`), so those are
+/// checked before the generic permission patterns. `authExpired` is an OAuth token failing;
+/// a bare 401 is `unauthorized` — on S3 or WebDAV that's wrong keys, not a sign-in to renew.
+pub fn classify(message: &str) -> &'static str {
+    let lower = message.to_lowercase();
+    let any = |needles: &[&str]| needles.iter().any(|n| lower.contains(n));
+
+    if any(&[
+        "ratelimitexceeded",
+        "rate limit",
+        "rate_limit",
+        "too many requests",
+        "error 429",
+        "status 429",
+        "throttl",
+        "slowdown",
+    ]) {
+        "rateLimited"
+    } else if any(&[
+        "invalid_grant",
+        "token expired",
+        "token has been expired",
+        "expired or revoked",
+        "couldn't fetch token",
+        "failed to refresh token",
+        "invalidauthenticationtoken",
+    ]) {
+        "authExpired"
+    } else if any(&["unauthorized", "error 401", "status 401"]) {
+        "unauthorized"
+    } else if any(&[
+        "quotaexceeded",
+        "quota exceeded",
+        "over quota",
+        "insufficient storage",
+        "insufficientstorage",
+        "no space left",
+        "error 507",
+    ]) {
+        "quotaExceeded"
+    } else if any(&[
+        "permission denied",
+        "access denied",
+        "accessdenied",
+        "operation not permitted",
+        "forbidden",
+        "insufficientpermissions",
+        "error 403",
+        "status 403",
+    ]) {
+        "permissionDenied"
+    } else if any(&[
+        "not found",
+        "notfound",
+        "no such file",
+        "doesn't exist",
+        "does not exist",
+        "error 404",
+        "status 404",
+    ]) {
+        "notFound"
+    } else {
+        "other"
+    }
+}
+
+/// The message part of an rclone `ERROR` line (`2026/01/01 03:00:00 ERROR : path: reason`), or
+/// None for any other line.
+fn error_message(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once(" ERROR ")?;
+    Some(rest.trim_start_matches(':').trim())
+}
+
+/// Summarizes the `ERROR` lines from `offset` to the end of the log. None when the log is
+/// unreadable or the segment holds no errors (a setup failure before the daemon ever logged).
+pub fn summarize(path: &Path, offset: u64) -> Option<FailureSummary> {
+    summarize_lines(segment_lines(path, offset)?)
+}
+
+/// The log's lines from `offset` to the end (lossy UTF-8), or None when it can't be opened.
+pub fn segment_lines(path: &Path, offset: u64) -> Option<impl Iterator<Item = String>> {
+    let mut file = std::fs::File::open(path).ok()?;
+    // A log shorter than the recorded offset was replaced underneath us — read it whole.
+    let len = file.metadata().ok()?.len();
+    file.seek(SeekFrom::Start(if offset <= len { offset } else { 0 }))
+        .ok()?;
+    Some(
+        BufReader::new(file)
+            .split(b'\n')
+            .map_while(Result::ok)
+            .map(|raw| String::from_utf8_lossy(&raw).into_owned()),
+    )
+}
+
+/// rclone's own roll-up after each whole-run retry ("Attempt 1/3 failed with 6 errors and: …") —
+/// it repeats errors already logged line by line.
+fn is_attempt_rollup(message: &str) -> bool {
+    message
+        .strip_prefix("Attempt ")
+        .and_then(|rest| rest.split_once(" failed with "))
+        .is_some_and(|(attempts, _)| attempts.contains('/'))
+}
+
+/// An error counts once per path (the text before the first ": "): rclone retries the whole run
+/// (3 attempts by default) and logs every file that fails again. The last attempt's reason wins.
+fn summarize_lines(lines: impl Iterator<Item = String>) -> Option<FailureSummary> {
+    let mut order: Vec<String> = Vec::new();
+    let mut by_path: HashMap<String, String> = HashMap::new();
+    for line in lines {
+        let Some(message) = error_message(&line) else {
+            continue;
+        };
+        if is_attempt_rollup(message) {
+            continue;
+        }
+        let path = message.split_once(": ").map_or(message, |(path, _)| path);
+        if by_path
+            .insert(path.to_string(), message.to_string())
+            .is_none()
+        {
+            order.push(path.to_string());
+        }
+    }
+    let total = order.len() as u32;
+    let mut buckets: HashMap<&'static str, FailureCategory> = HashMap::new();
+    for message in order.iter().map(|path| &by_path[path]) {
+        let kind = classify(message);
+        let bucket = buckets.entry(kind).or_insert_with(|| FailureCategory {
+            kind: kind.to_string(),
+            count: 0,
+            examples: Vec::new(),
+        });
+        bucket.count += 1;
+        if bucket.examples.len() < MAX_EXAMPLES {
+            bucket
+                .examples
+                .push(message.chars().take(MAX_EXAMPLE_CHARS).collect());
+        }
+    }
+    if total == 0 {
+        return None;
+    }
+    let mut categories: Vec<FailureCategory> = buckets.into_values().collect();
+    categories.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.kind.cmp(&b.kind)));
+    Some(FailureSummary { total, categories })
+}
+
+impl FailureSummary {
+    /// One-line form for the runner log: "12 errors (permissionDenied 10, notFound 2)".
+    pub fn describe(&self) -> String {
+        let parts: Vec<String> = self
+            .categories
+            .iter()
+            .map(|c| format!("{} {}", c.kind, c.count))
+            .collect();
+        format!(
+            "{} error{} ({})",
+            self.total,
+            if self.total == 1 { "" } else { "s" },
+            parts.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_provider_phrasings() {
+        assert_eq!(
+            classify("googleapi: Error 403: User Rate Limit Exceeded, userRateLimitExceeded"),
+            "rateLimited"
+        );
+        assert_eq!(
+            classify("couldn't fetch token: invalid_grant: maybe token expired?"),
+            "authExpired"
+        );
+        assert_eq!(
+            classify("googleapi: Error 403: The user's Drive storage quota has been exceeded., storageQuotaExceeded"),
+            "quotaExceeded"
+        );
+        assert_eq!(
+            classify("open /data/x: permission denied"),
+            "permissionDenied"
+        );
+        assert_eq!(
+            classify("s3 upload: 401 Unauthorized: InvalidAccessKeyId"),
+            "unauthorized"
+        );
+        assert_eq!(classify("directory not found"), "notFound");
+        assert_eq!(classify("corrupted on transfer: md5 hash differ"), "other");
+    }
+
+    #[test]
+    fn summarizes_only_the_runs_segment() {
+        let path = std::env::temp_dir().join(format!(
+            "rcloneui-daemonlog-test-{}.log",
+            std::process::id()
+        ));
+        let earlier = "2026/01/01 02:00:00 ERROR : old.txt: Failed to copy: permission denied\n";
+        std::fs::write(&path, earlier).unwrap();
+        let offset = earlier.len() as u64;
+
+        let mut segment = String::new();
+        for i in 0..5 {
+            segment.push_str(&format!(
+                "2026/01/01 03:00:0{} ERROR : a/{}.txt: Failed to copy: directory not found\n",
+                i, i
+            ));
+        }
+        segment.push_str("2026/01/01 03:00:06 NOTICE: b.txt: Skipped copy\n");
+        segment.push_str(
+            "2026/01/01 03:00:07 ERROR : b.txt: Failed to copy: Error 429: Too Many Requests\n",
+        );
+        segment.push_str("2026/01/01 03:00:08 ERROR : Attempt 1/3 failed with 6 errors and: directory not found\n");
+        // The second attempt fails the same files again.
+        segment
+            .push_str("2026/01/01 03:00:09 ERROR : a/0.txt: Failed to copy: directory not found\n");
+        segment.push_str(
+            "2026/01/01 03:00:10 ERROR : b.txt: Failed to copy: Error 429: Too Many Requests\n",
+        );
+        segment.push_str("2026/01/01 03:00:11 ERROR : Attempt 2/3 failed with 2 errors and: directory not found\n");
+        std::fs::write(&path, format!("{}{}", earlier, segment)).unwrap();
+
+        let summary = summarize(&path, offset).unwrap();
+        assert_eq!(
+            summary.total, 6,
+            "earlier run, non-ERROR lines, roll-ups and retries excluded"
+        );
+        assert_eq!(summary.categories[0].kind, "notFound");
+        assert_eq!(summary.categories[0].count, 5);
+        assert_eq!(summary.categories[0].examples.len(), MAX_EXAMPLES);
+        assert!(summary.categories[0].examples[0].starts_with("a/0.txt:"));
+        assert_eq!(summary.categories[1].kind, "rateLimited");
+        assert!(!summary
+            .categories
+            .iter()
+            .any(|c| c.kind == "permissionDenied"));
+        assert_eq!(summary.describe(), "6 errors (notFound 5, rateLimited 1)");
+
+        // Nothing logged since the offset (setup failure) → no summary at all.
+        let end = std::fs::metadata(&path).unwrap().len();
+        assert_eq!(summarize(&path, end), None);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,521 @@
+//! Preflight for scheduled tasks: everything a run does before its first transfer, done now.
+//!
+//! A schedule that fails at 3am on a missing binary or a config password that no longer decrypts
+//! only says so in its history the next morning. The doctor walks each task through the runner's
+//! own setup steps — resolve the binary, build the config environment, load the secrets, pass
+//! the session gate, start the transient daemon — then asks that daemon to read the config and
+//! open every fs the task names. Nothing is transferred and nothing is recorded in history; the
+//! daemon is shut down before the next task starts.
+
+use std::path::Path;
+use std::process::Stdio;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::daemonlog;
+use super::history::RunLog;
+use super::jobfile::{self, JobSpec};
+use super::runner;
+use super::storeread::{self, AppDirs};
+use crate::notifications::targets::{self, NotificationTarget};
+use crate::notifications::webhooks;
+
+/// Daemon stderr lines quoted when it fails to start.
+const STDERR_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    /// Works today but is likely to bite: a target whose last delivery failed.
+    Warn,
+    Fail,
+    /// Not applicable, or not reachable because an earlier step failed.
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Check {
+    /// "binary" | "config" | "secrets" | "session" | "daemon" | "decrypt" | "remote" |
+    /// "notifications".
+    pub id: &'static str,
+    pub label: String,
+    pub status: Status,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(id: &'static str, label: &str, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            id,
+            label: label.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskChecks {
+    pub task_id: String,
+    pub name: String,
+    pub checks: Vec<Check>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub tasks: Vec<TaskChecks>,
+}
+
+/// Every local task, in job-file order.
+pub fn run(dirs: &AppDirs) -> Result<DoctorReport, String> {
+    let root = storeread::read_root(dirs)?;
+    let notifications = match targets::load(dirs) {
+        Ok(targets) => notification_check(&targets),
+        Err(e) => Check::new(
+            "notifications",
+            "Notifications",
+            Status::Fail,
+            format!("The notification targets can't be read: {}", e),
+        ),
+    };
+    let mut report = DoctorReport::default();
+    for spec in jobfile::list(dirs, "local") {
+        let mut checks = check_task(dirs, &spec, &root);
+        checks.push(notifications.clone());
+        report.tasks.push(TaskChecks {
+            task_id: spec.task_id,
+            name: spec.name,
+            checks,
+        });
+    }
+    Ok(report)
+}
+
+fn check_task(dirs: &AppDirs, spec: &JobSpec, root: &storeread::RootState) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let binary = runner::resolve_binary(spec, root);
+    checks.push(match &binary {
+        Ok(path) => Check::new("binary", "rclone binary", Status::Pass, path.as_str()),
+        Err(e) => Check::new("binary", "rclone binary", Status::Fail, e.as_str()),
+    });
+
+    let env = runner::config_env(dirs, spec);
+    checks.push(match &env {
+        Ok(env) => Check::new(
+            "config",
+            "Config file",
+            Status::Pass,
+            env.get("RCLONE_CONFIG").cloned().unwrap_or_default(),
+        ),
+        Err(e) => Check::new("config", "Config file", Status::Fail, e.as_str()),
+    });
+
+    let config_ok = env.is_ok();
+    let mut env = env.unwrap_or_default();
+    checks.push(if spec.secret_env.is_empty() {
+        Check::new(
+            "secrets",
+            "Secrets",
+            Status::Skip,
+            "The task has no secrets",
+        )
+    } else {
+        match runner::add_secrets(dirs, spec, &mut env) {
+            Ok(()) => Check::new(
+                "secrets",
+                "Secrets",
+                Status::Pass,
+                format!("Stored: {}", spec.secret_env.join(", ")),
+            ),
+            Err(e) => Check::new("secrets", "Secrets", Status::Fail, e),
+        }
+    });
+
+    checks.push(session_check(spec));
+
+    let Ok(binary) = binary else {
+        skip_daemon(&mut checks, spec, "Needs the rclone binary");
+        return checks;
+    };
+    if !config_ok {
+        skip_daemon(&mut checks, spec, "Needs the config file");
+        return checks;
+    }
+
+    // The daemon's stderr and the runner's lines go to scratch files, not the task's logs: a
+    // preflight is not a run, and the logs' lines and segments belong to runs.
+    let scratch = std::env::temp_dir().join(format!("rcloneui-doctor-{}", uuid::Uuid::new_v4()));
+    let stderr_path = scratch.with_extension("daemon.log");
+    let stderr = std::fs::File::create(&stderr_path)
+        .map(Stdio::from)
+        .unwrap_or_else(|_| Stdio::null());
+    let log_path = scratch.with_extension("log");
+    let mut log = RunLog::at(log_path.clone());
+    let started = runner::spawn_daemon(&binary, &env, spec, stderr, &mut log).and_then(|mut d| {
+        runner::await_ready(&mut d, "")?;
+        Ok(d)
+    });
+    let daemon = match started {
+        Ok(daemon) => daemon,
+        Err(e) => {
+            let tail: Vec<String> = daemonlog::segment_lines(&stderr_path, 0)
+                .map(|lines| lines.filter(|l| !l.trim().is_empty()).collect())
+                .unwrap_or_default();
+            remove_scratch(&[&stderr_path, &log_path]);
+            let detail = match tail.len() {
+                0 => e,
+                n => format!(
+                    "{}: {}",
+                    e,
+                    tail[n.saturating_sub(STDERR_LINES)..].join(" / ")
+                ),
+            };
+            checks.push(Check::new(
+                "daemon",
+                "Transient daemon",
+                Status::Fail,
+                detail,
+            ));
+            skip_remotes(&mut checks, spec, "Needs the transient daemon");
+            return checks;
+        }
+    };
+    checks.push(Check::new(
+        "daemon",
+        "Transient daemon",
+        Status::Pass,
+        "Started and answered",
+    ));
+
+    match daemon.call("/config/listremotes", &json!({})) {
+        Ok(value) => {
+            let configured: Vec<&str> = value
+                .get("remotes")
+                .and_then(|r| r.as_array())
+                .map(|r| r.iter().filter_map(|name| name.as_str()).collect())
+                .unwrap_or_default();
+            let missing: Vec<String> = spec
+                .remotes()
+                .into_iter()
+                .filter(|name| !configured.contains(&name.as_str()))
+                .collect();
+            checks.push(if missing.is_empty() {
+                Check::new(
+                    "decrypt",
+                    "Config readable",
+                    Status::Pass,
+                    format!("{} remote(s) configured", configured.len()),
+                )
+            } else {
+                Check::new(
+                    "decrypt",
+                    "Config readable",
+                    Status::Fail,
+                    format!("The config has no remote named {}", missing.join(", ")),
+                )
+            });
+        }
+        Err(e) => {
+            checks.push(Check::new(
+                "decrypt",
+                "Config readable",
+                Status::Fail,
+                format!("rclone can't read the config: {}", e),
+            ));
+            skip_fs(&mut checks, spec, "Needs a readable config");
+            drop(daemon);
+            remove_scratch(&[&stderr_path, &log_path]);
+            return checks;
+        }
+    }
+
+    for fs in probe_targets(spec) {
+        checks.push(probe_fs(&daemon, &fs));
+    }
+
+    // The guard quits the daemon (and its run dir) here.
+    drop(daemon);
+    remove_scratch(&[&stderr_path, &log_path]);
+    checks
+}
+
+fn remove_scratch(paths: &[&Path]) {
+    for path in paths {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Linux 'User' mode runs only inside a login session (see runner::user_has_login_session).
+fn session_check(spec: &JobSpec) -> Check {
+    if !spec.is_user_mode() {
+        return Check::new(
+            "session",
+            "Login session",
+            Status::Skip,
+            "System mode runs without a login session",
+        );
+    }
+    #[cfg(target_os = "linux")]
+    {
+        if runner::user_has_login_session() {
+            Check::new(
+                "session",
+                "Login session",
+                Status::Pass,
+                "You are logged in, so User mode runs fire",
+            )
+        } else {
+            Check::new(
+                "session",
+                "Login session",
+                Status::Fail,
+                "No login session is reported, so every User mode fire is skipped",
+            )
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Check::new(
+            "session",
+            "Login session",
+            Status::Skip,
+            "The OS scheduler only starts User mode runs while you are logged in",
+        )
+    }
+}
+
+fn skip_daemon(checks: &mut Vec<Check>, spec: &JobSpec, reason: &str) {
+    checks.push(Check::new(
+        "daemon",
+        "Transient daemon",
+        Status::Skip,
+        reason,
+    ));
+    skip_remotes(checks, spec, reason);
+}
+
+fn skip_remotes(checks: &mut Vec<Check>, spec: &JobSpec, reason: &str) {
+    checks.push(Check::new(
+        "decrypt",
+        "Config readable",
+        Status::Skip,
+        reason,
+    ));
+    skip_fs(checks, spec, reason);
+}
+
+fn skip_fs(checks: &mut Vec<Check>, spec: &JobSpec, reason: &str) {
+    for fs in probe_targets(spec) {
+        checks.push(Check::new("remote", &fs, Status::Skip, reason));
+    }
+}
+
+/// Every distinct fs the task opens, in request order.
+fn probe_targets(spec: &JobSpec) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for fs in spec.requests.iter().flat_map(|r| r.fs_strings()) {
+        if !targets.iter().any(|t| t == fs) {
+            targets.push(fs.to_string());
+        }
+    }
+    targets
+}
+
+/// `operations/about` when the backend reports usage (it has to reach the remote to answer),
+/// `operations/fsinfo` when it doesn't. A missing directory is only a warning: destinations are
+/// created by the first run.
+fn probe_fs(daemon: &runner::DaemonGuard, fs: &str) -> Check {
+    let body = json!({ "fs": fs });
+    let about_error = match daemon.call("/operations/about", &body) {
+        Ok(about) => {
+            let detail = match about.get("free").and_then(Value::as_u64) {
+                Some(free) => format!("Reachable, {} free", webhooks::format_bytes(free)),
+                None => "Reachable".to_string(),
+            };
+            return Check::new("remote", fs, Status::Pass, detail);
+        }
+        Err(e) => e,
+    };
+    let lower = about_error.to_lowercase();
+    if lower.contains("directory not found") {
+        return Check::new(
+            "remote",
+            fs,
+            Status::Warn,
+            "Reachable, but the directory doesn't exist yet",
+        );
+    }
+    if !lower.contains("not supported") && !lower.contains("doesn't support") {
+        return Check::new("remote", fs, Status::Fail, about_error);
+    }
+    match daemon.call("/operations/fsinfo", &body) {
+        Ok(info) => Check::new(
+            "remote",
+            fs,
+            Status::Pass,
+            format!(
+                "Opened ({} backend)",
+                info.get("Name")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown")
+            ),
+        ),
+        Err(e) => Check::new("remote", fs, Status::Fail, e),
+    }
+}
+
+/// Health of the targets a scheduled run notifies — the same for every task, since targets
+/// subscribe to events rather than tasks. Validated without sending: a preflight that posted
+/// to every channel would be its own incident.
+fn notification_check(targets: &[NotificationTarget]) -> Check {
+    let subscribed: Vec<&NotificationTarget> = targets
+        .iter()
+        .filter(|t| t.is_enabled && t.events.iter().any(|e| e.starts_with("schedule.")))
+        .collect();
+    if subscribed.is_empty() {
+        return Check::new(
+            "notifications",
+            "Notifications",
+            Status::Skip,
+            "No enabled target is subscribed to scheduled task events",
+        );
+    }
+    let (mut broken, mut flaky) = (Vec::new(), Vec::new());
+    for target in &subscribed {
+        let name = if target.name.is_empty() {
+            &target.provider
+        } else {
+            &target.name
+        };
+        if let Err(e) = webhooks::validate(target) {
+            broken.push(format!("{}: {}", name, e));
+        } else if let Some(e) = &target.last_error {
+            flaky.push(format!("{}: last delivery failed ({})", name, e));
+        }
+    }
+    if !broken.is_empty() {
+        return Check::new(
+            "notifications",
+            "Notifications",
+            Status::Fail,
+            broken.join("; "),
+        );
+    }
+    if !flaky.is_empty() {
+        return Check::new(
+            "notifications",
+            "Notifications",
+            Status::Warn,
+            flaky.join("; "),
+        );
+    }
+    Check::new(
+        "notifications",
+        "Notifications",
+        Status::Pass,
+        format!("{} target(s) ready", subscribed.len()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(
+        name: &str,
+        url: &str,
+        events: &[&str],
+        last_error: Option<&str>,
+    ) -> NotificationTarget {
+        NotificationTarget {
+            id: name.into(),
+            provider: "generic".into(),
+            name: name.into(),
+            url: url.into(),
+            is_enabled: true,
+            events: events.iter().map(|e| e.to_string()).collect(),
+            created_at: 0,
+            last_sent_at: None,
+            last_error: last_error.map(String::from),
+            template: None,
+            signing_secret: None,
+            headers: Vec::new(),
+            auth: None,
+            filters: Default::default(),
+            delivery: Default::default(),
+        }
+    }
+
+    #[test]
+    fn notification_check_ranks_broken_targets_first() {
+        let transfers = target("Ops", "https://example.com/hook", &["job.failed"], None);
+        assert_eq!(
+            notification_check(std::slice::from_ref(&transfers)).status,
+            Status::Skip
+        );
+
+        let healthy = target(
+            "Ops",
+            "https://example.com/hook",
+            &["schedule.failed"],
+            None,
+        );
+        let flaky = target(
+            "Pager",
+            "https://example.com/pager",
+            &["schedule.completed"],
+            Some("Webhook responded with status 502"),
+        );
+        assert_eq!(
+            notification_check(&[healthy.clone(), transfers]).detail,
+            "1 target(s) ready"
+        );
+        let warned = notification_check(&[healthy.clone(), flaky.clone()]);
+        assert_eq!(warned.status, Status::Warn);
+        assert_eq!(
+            warned.detail,
+            "Pager: last delivery failed (Webhook responded with status 502)"
+        );
+
+        let broken = target("Chat", "ftp://example.com", &["schedule.failed"], None);
+        let failed = notification_check(&[healthy, flaky, broken]);
+        assert_eq!(failed.status, Status::Fail);
+        assert_eq!(failed.detail, "Chat: unsupported url scheme 'ftp'");
+    }
+
+    #[test]
+    fn probes_each_fs_once() {
+        let spec: JobSpec = serde_json::from_value(json!({
+            "schemaVersion": jobfile::JOB_SCHEMA_VERSION,
+            "taskId": "t1",
+            "hostId": "local",
+            "name": "Backup",
+            "operation": "copy",
+            "cron": "0 3 * * *",
+            "configId": "default",
+            "binary": "app-default",
+            "requests": [
+                {
+                    "endpoint": "/job/batch",
+                    "body": { "inputs": [
+                        { "srcFs": "/home/me/docs", "dstFs": "gdrive:backup" },
+                        { "srcFs": "/home/me/photos", "dstFs": "gdrive:backup" },
+                    ] },
+                },
+                { "endpoint": "/sync/copy", "body": { "srcFs": "/home/me/docs", "dstFs": "b2:x" } },
+            ],
+        }))
+        .unwrap();
+        assert_eq!(
+            probe_targets(&spec),
+            vec!["/home/me/docs", "gdrive:backup", "/home/me/photos", "b2:x"]
+        );
+        assert_eq!(spec.remotes(), vec!["b2", "gdrive"]);
+    }
+}
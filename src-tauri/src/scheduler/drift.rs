@@ -0,0 +1,471 @@
+//! Drift between the job files and the OS artifacts registered for them.
+//!
+//! `sweep_orphans` handles artifacts without a job file; this covers the opposite direction: a
+//! job file whose artifact is missing, or differs from what `render` produces today — a moved
+//! AppImage or app bundle, a data dir that changed with XDG_DATA_HOME, a crontab line someone
+//! edited by hand, or a task left behind in the other macOS backend after a mode flip.
+//!
+//! Each backend reads its artifact back into an `ArtifactView` and renders the expected one the
+//! same way, so the comparison never depends on formatting. Repairing is a re-install (the same
+//! upsert registration performs) plus removal from any backend the task doesn't belong to.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::cronconv;
+use super::jobfile::{self, JobSpec};
+use super::storeread::AppDirs;
+use super::{ArtifactView, InstallState, SchedulerBackend};
+
+/// A task's own backend, and the ones it must not also be registered in.
+type Backends = (Box<dyn SchedulerBackend>, Vec<Box<dyn SchedulerBackend>>);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDrift {
+    pub task_id: String,
+    pub name: String,
+    /// One sentence per mismatch.
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftReport {
+    pub checked: u32,
+    /// Only the tasks with at least one problem.
+    pub drifted: Vec<TaskDrift>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairFailure {
+    pub task_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairReport {
+    pub repaired: Vec<String>,
+    pub failed: Vec<RepairFailure>,
+}
+
+/// The value after `flag` in an invocation's args.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+/// Crontab schedules compare by the times they fire: a line an older version wrote as `1-5`
+/// isn't drift against today's `1,2,3,4,5`. Other backends' encodings compare as text.
+fn same_schedule(a: &str, b: &str) -> bool {
+    match (cronconv::parse(a), cronconv::parse(b)) {
+        (Ok(a), Ok(b)) => a.fires_like(&b),
+        _ => a == b,
+    }
+}
+
+/// The mismatches between the artifact `install` would write and the installed one. `enabled`
+/// is the state the task should be in, when known.
+pub fn explain(
+    kind: &str,
+    cron: &str,
+    expected: &ArtifactView,
+    actual: Option<&ArtifactView>,
+    enabled: Option<bool>,
+) -> Vec<String> {
+    let Some(actual) = actual else {
+        return vec![format!("It has no {} entry, so it never runs", kind)];
+    };
+    let mut problems = Vec::new();
+    if !same_schedule(&actual.schedule, &expected.schedule) {
+        problems.push(format!(
+            "Its {} schedule no longer matches \"{}\"",
+            kind, cron
+        ));
+    }
+    if actual.program != expected.program {
+        problems.push(format!(
+            "It starts {}, but the app is now {}",
+            actual.program, expected.program
+        ));
+    }
+    let mut args_explained = false;
+    for (flag, what) in [("--data-dir", "data"), ("--local-data-dir", "local data")] {
+        let (was, now) = (
+            flag_value(&actual.args, flag),
+            flag_value(&expected.args, flag),
+        );
+        if was != now {
+            args_explained = true;
+            problems.push(format!(
+                "It reads its {} from {} instead of {}",
+                what,
+                was.unwrap_or("the default location"),
+                now.unwrap_or("the default location")
+            ));
+        }
+    }
+    if actual.args != expected.args && !args_explained {
+        problems.push(format!(
+            "It runs with the arguments \"{}\" instead of \"{}\"",
+            actual.args.join(" "),
+            expected.args.join(" ")
+        ));
+    }
+    match enabled {
+        Some(true) if !actual.enabled => {
+            problems.push(format!("It is paused in {} but enabled in the app", kind))
+        }
+        Some(false) if actual.enabled => {
+            problems.push(format!("It is paused in the app but armed in {}", kind))
+        }
+        _ => {}
+    }
+    problems
+}
+
+/// The OS backends for `spec`.
+fn os_backends(dirs: &AppDirs, spec: &JobSpec) -> Result<Backends, String> {
+    Ok((
+        super::backend_for(dirs, spec.is_user_mode())?,
+        super::other_backends(dirs, spec.is_user_mode())?,
+    ))
+}
+
+/// The state `spec` should be in: what the GUI wants, else the last one chosen for it (a
+/// headless `disable` included). None when neither said.
+fn wanted_state(spec: &JobSpec, enabled: &HashMap<String, bool>) -> Option<bool> {
+    enabled.get(&spec.task_id).copied().or(spec.enabled)
+}
+
+/// The problems of one task; the installed state is kept where nothing says otherwise.
+fn check(dirs: &AppDirs, spec: &JobSpec, enabled: Option<bool>, backends: Backends) -> Vec<String> {
+    let (backend, others) = backends;
+    let actual = match backend.inspect(&spec.task_id) {
+        Ok(actual) => actual,
+        Err(e) => return vec![format!("Its {} entry can't be read: {}", backend.kind(), e)],
+    };
+    let rendered_enabled = enabled
+        .or(actual.as_ref().map(|view| view.enabled))
+        .unwrap_or(true);
+    let mut problems = match super::render(dirs, spec, rendered_enabled)
+        .and_then(|rendered| backend.expected(&spec.task_id, &rendered))
+    {
+        Ok(expected) => explain(
+            backend.kind(),
+            &spec.cron,
+            &expected,
+            actual.as_ref(),
+            enabled,
+        ),
+        Err(e) => vec![format!("It can't be registered as it is: {}", e)],
+    };
+    for other in others {
+        if matches!(
+            other.is_installed(&spec.task_id),
+            Ok(InstallState::Installed { .. })
+        ) {
+            problems.push(format!(
+                "It is also registered in {}, so it can run twice",
+                other.kind()
+            ));
+        }
+    }
+    problems
+}
+
+/// Every local job file checked against its backend. `enabled` maps task ids to the state the
+/// GUI wants; tasks it doesn't list keep the state last chosen for them, else the installed one.
+pub fn report(dirs: &AppDirs, enabled: &HashMap<String, bool>) -> DriftReport {
+    report_with(dirs, enabled, &|spec| os_backends(dirs, spec))
+}
+
+fn report_with(
+    dirs: &AppDirs,
+    enabled: &HashMap<String, bool>,
+    backends: &dyn Fn(&JobSpec) -> Result<Backends, String>,
+) -> DriftReport {
+    let mut report = DriftReport::default();
+    for spec in jobfile::list(dirs, "local") {
+        report.checked += 1;
+        let problems = match backends(&spec) {
+            Ok(backends) => check(dirs, &spec, wanted_state(&spec, enabled), backends),
+            Err(e) => vec![e],
+        };
+        if !problems.is_empty() {
+            report.drifted.push(TaskDrift {
+                task_id: spec.task_id,
+                name: spec.name,
+                problems,
+            });
+        }
+    }
+    report
+}
+
+/// Re-registers every drifted task after removing it from the backends it doesn't belong to —
+/// `register`'s order, so a failure never leaves it registered twice.
+pub fn repair(dirs: &AppDirs, enabled: &HashMap<String, bool>) -> RepairReport {
+    repair_with(dirs, enabled, &|spec| os_backends(dirs, spec))
+}
+
+fn repair_with(
+    dirs: &AppDirs,
+    enabled: &HashMap<String, bool>,
+    backends: &dyn Fn(&JobSpec) -> Result<Backends, String>,
+) -> RepairReport {
+    let mut result = RepairReport::default();
+    for drift in report_with(dirs, enabled, backends).drifted {
+        let outcome = jobfile::load(dirs, "local", &drift.task_id).and_then(|spec| {
+            let (backend, others) = backends(&spec)?;
+            let installed = match backend.is_installed(&spec.task_id)? {
+                InstallState::Installed { enabled } => Some(enabled),
+                InstallState::NotInstalled => None,
+            };
+            let state = wanted_state(&spec, enabled).or(installed).unwrap_or(true);
+            let rendered = super::render(dirs, &spec, state)?;
+            for other in others {
+                other.uninstall(&spec.task_id)?;
+            }
+            backend.install(&spec.task_id, &rendered)
+        });
+        match outcome {
+            Ok(()) => result.repaired.push(drift.task_id),
+            Err(error) => result.failed.push(RepairFailure {
+                task_id: drift.task_id,
+                error,
+            }),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use super::super::RenderedSchedule;
+
+    /// A backend keeping its artifacts in memory, the schedule as the cron text it was given.
+    #[derive(Clone)]
+    struct FakeBackend {
+        kind: &'static str,
+        artifacts: Arc<Mutex<HashMap<String, ArtifactView>>>,
+    }
+
+    impl FakeBackend {
+        fn new(kind: &'static str) -> Self {
+            Self {
+                kind,
+                artifacts: Arc::default(),
+            }
+        }
+
+        fn get(&self, task_id: &str) -> Option<ArtifactView> {
+            self.artifacts.lock().unwrap().get(task_id).cloned()
+        }
+    }
+
+    impl SchedulerBackend for FakeBackend {
+        fn kind(&self) -> &'static str {
+            self.kind
+        }
+        fn install(&self, task_id: &str, rendered: &RenderedSchedule) -> Result<(), String> {
+            let view = self.expected(task_id, rendered)?;
+            self.artifacts
+                .lock()
+                .unwrap()
+                .insert(task_id.to_string(), view);
+            Ok(())
+        }
+        fn uninstall(&self, task_id: &str) -> Result<(), String> {
+            self.artifacts.lock().unwrap().remove(task_id);
+            Ok(())
+        }
+        fn set_enabled(&self, _task_id: &str, _enabled: bool) -> Result<(), String> {
+            unimplemented!()
+        }
+        fn run_now(&self, _task_id: &str) -> Result<(), String> {
+            unimplemented!()
+        }
+        fn is_installed(&self, task_id: &str) -> Result<InstallState, String> {
+            Ok(match self.get(task_id) {
+                Some(view) => InstallState::Installed {
+                    enabled: view.enabled,
+                },
+                None => InstallState::NotInstalled,
+            })
+        }
+        fn expected(
+            &self,
+            _task_id: &str,
+            rendered: &RenderedSchedule,
+        ) -> Result<ArtifactView, String> {
+            let cron = &rendered.cron;
+            Ok(ArtifactView {
+                enabled: rendered.enabled,
+                schedule: [&cron.minute, &cron.hour, &cron.dom, &cron.month, &cron.dow]
+                    .map(|field| field.raw.as_str())
+                    .join(" "),
+                program: rendered.program.to_string_lossy().into_owned(),
+                args: rendered.args.clone(),
+            })
+        }
+        fn inspect(&self, task_id: &str) -> Result<Option<ArtifactView>, String> {
+            Ok(self.get(task_id))
+        }
+    }
+
+    fn test_dirs(tag: &str) -> AppDirs {
+        let root = std::env::temp_dir().join(format!("rcloneui-drift-test-{}", tag));
+        let _ = std::fs::remove_dir_all(&root);
+        AppDirs {
+            app_data: root.clone(),
+            app_local_data: root,
+        }
+    }
+
+    fn save_spec(dirs: &AppDirs, task_id: &str, cron: &str, enabled: Option<bool>) -> JobSpec {
+        let spec: JobSpec = serde_json::from_value(serde_json::json!({
+            "schemaVersion": jobfile::JOB_SCHEMA_VERSION,
+            "taskId": task_id,
+            "hostId": "local",
+            "name": task_id,
+            "operation": "sync",
+            "cron": cron,
+            "configId": "default",
+            "binary": "app-default",
+            "runMode": "system",
+            "enabled": enabled,
+            "requests": [],
+        }))
+        .unwrap();
+        jobfile::save(dirs, &spec).unwrap();
+        spec
+    }
+
+    #[test]
+    fn reports_and_repairs_against_the_backend() {
+        let dirs = test_dirs("repair");
+        let (own, other) = (FakeBackend::new("fake"), FakeBackend::new("other"));
+        let backends = |_: &JobSpec| -> Result<Backends, String> {
+            Ok((Box::new(own.clone()), vec![Box::new(other.clone())]))
+        };
+        // Disabled headlessly, then its artifact deleted by hand.
+        save_spec(&dirs, "paused", "0 3 * * *", Some(false));
+        // Written as a range by an older version; rendered as a list today.
+        let legacy = save_spec(&dirs, "legacy", "0 3 * * 1,2,3,4,5", None);
+        let rendered = super::super::render(&dirs, &legacy, true).unwrap();
+        own.install("legacy", &rendered).unwrap();
+        own.artifacts
+            .lock()
+            .unwrap()
+            .get_mut("legacy")
+            .unwrap()
+            .schedule = "0 3 * * 1-5".into();
+        // Left behind in the other backend after a mode flip.
+        let twice = save_spec(&dirs, "twice", "0 4 * * *", Some(true));
+        let rendered = super::super::render(&dirs, &twice, true).unwrap();
+        own.install("twice", &rendered).unwrap();
+        other.install("twice", &rendered).unwrap();
+
+        let report = report_with(&dirs, &HashMap::new(), &backends);
+        assert_eq!(report.checked, 3);
+        let mut drifted: Vec<(&str, &[String])> = report
+            .drifted
+            .iter()
+            .map(|d| (d.task_id.as_str(), d.problems.as_slice()))
+            .collect();
+        // Job files are listed in directory order.
+        drifted.sort();
+        assert_eq!(
+            drifted,
+            vec![
+                (
+                    "paused",
+                    &["It has no fake entry, so it never runs".to_string()][..]
+                ),
+                (
+                    "twice",
+                    &["It is also registered in other, so it can run twice".to_string()][..]
+                ),
+            ]
+        );
+
+        let mut repaired = repair_with(&dirs, &HashMap::new(), &backends);
+        repaired.repaired.sort();
+        assert_eq!(repaired.repaired, vec!["paused", "twice"]);
+        assert!(repaired.failed.is_empty());
+        // The job file's state, not armed by default.
+        assert!(!own.get("paused").unwrap().enabled);
+        assert!(other.get("twice").is_none());
+        assert!(report_with(&dirs, &HashMap::new(), &backends)
+            .drifted
+            .is_empty());
+
+        // What the GUI wants wins over the job file.
+        let wanted = HashMap::from([("paused".to_string(), true)]);
+        let report = report_with(&dirs, &wanted, &backends);
+        assert_eq!(
+            report.drifted[0].problems,
+            vec!["It is paused in fake but enabled in the app"]
+        );
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
+
+    fn view(program: &str, data_dir: &str) -> ArtifactView {
+        ArtifactView {
+            enabled: true,
+            schedule: "0 3 * * *".into(),
+            program: program.into(),
+            args: ["run-task", "t1", "--host", "local", "--data-dir", data_dir]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+
+    #[test]
+    fn explains_each_mismatch() {
+        let expected = view(
+            "/home/me/Apps/Rclone-UI.AppImage",
+            "/home/me/.local/share/ui",
+        );
+        assert!(explain(
+            "crontab",
+            "0 3 * * *",
+            &expected,
+            Some(&expected),
+            Some(true)
+        )
+        .is_empty());
+        assert_eq!(
+            explain("crontab", "0 3 * * *", &expected, None, None),
+            vec!["It has no crontab entry, so it never runs"]
+        );
+
+        let mut actual = view("/home/me/Downloads/Rclone-UI.AppImage", "/data/ui");
+        actual.schedule = "0 4 * * *".into();
+        actual.enabled = false;
+        assert_eq!(
+            explain("crontab", "0 3 * * *", &expected, Some(&actual), Some(true)),
+            vec![
+                "Its crontab schedule no longer matches \"0 3 * * *\"",
+                "It starts /home/me/Downloads/Rclone-UI.AppImage, but the app is now \
+                 /home/me/Apps/Rclone-UI.AppImage",
+                "It reads its data from /data/ui instead of /home/me/.local/share/ui",
+                "It is paused in crontab but enabled in the app",
+            ]
+        );
+
+        // Without the GUI's word, the installed state isn't drift.
+        let mut paused = expected.clone();
+        paused.enabled = false;
+        assert!(explain("crontab", "0 3 * * *", &expected, Some(&paused), None).is_empty());
+    }
+}
@@ -3,10 +3,14 @@
 //! The runner is the only writer of a task's history/lock/log; the GUI only reads. History
 //! replaces the old zustand isRunning/lastRun/lastRunError fields, which avoids concurrent
 //! writes to the store file from two processes.
+//!
+//! The runner log is JSON lines too (`LogRecord`), each tagged with the run that wrote it, so
+//! one run's log can be read back out of the interleaved file. Logs are rotated into gzip
+//! archives (`<task>.log.1.gz` newest) kept up to the task's `logRetention`.
 
 use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
@@ -16,6 +20,9 @@ use super::storeread::AppDirs;
 const HISTORY_ROTATE_BYTES: u64 = 512 * 1024;
 const HISTORY_KEEP_LINES: usize = 200;
 const LOG_ROTATE_BYTES: u64 = 1024 * 1024;
+/// Rotated generations kept per log when the task doesn't say.
+pub const DEFAULT_LOG_RETENTION: u32 = 5;
+pub const MAX_LOG_RETENTION: u32 = 50;
 
 pub fn history_path(dirs: &AppDirs, task_id: &str) -> PathBuf {
     dirs.app_data
@@ -38,6 +45,11 @@ pub fn log_path(dirs: &AppDirs, task_id: &str) -> PathBuf {
         .join(format!("{}.log", task_id))
 }
 
+/// The transient rclone daemon's stderr, next to the runner log.
+pub fn daemon_log_path(dirs: &AppDirs, task_id: &str) -> PathBuf {
+    log_path(dirs, task_id).with_extension("daemon.log")
+}
+
 pub fn now_iso() -> String {
     // RFC3339 UTC with millisecond precision, no chrono dependency.
     let now = SystemTime::now()
@@ -98,6 +110,28 @@ pub enum HistoryLine {
         jobids: Option<Vec<i64>>,
         #[serde(skip_serializing_if = "Option::is_none")]
         stats: Option<serde_json::Value>,
+        /// Failed runs only: the daemon log's ERROR lines for this run, classified.
+        #[serde(
+            rename = "failureSummary",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        failure_summary: Option<super::daemonlog::FailureSummary>,
+        /// The run failed on an expired/revoked OAuth token — the GUI offers "reconnect remote"
+        /// instead of a generic failure.
+        #[serde(
+            rename = "needsReauth",
+            default,
+            skip_serializing_if = "std::ops::Not::not"
+        )]
+        needs_reauth: bool,
+        /// The remote to reconnect, when it could be identified.
+        #[serde(
+            rename = "reauthRemote",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        reauth_remote: Option<String>,
     },
     Skipped {
         ts: String,
@@ -436,40 +470,231 @@ pub fn is_running(dirs: &AppDirs, task_id: &str) -> bool {
     pid_is_this_holder(info.pid, &info.process_name)
 }
 
-/// Rename `path` to `.old` when it exceeds `max_bytes` (single-generation rotation).
-pub fn rotate_file(path: &PathBuf, max_bytes: u64) {
-    if let Ok(meta) = std::fs::metadata(path) {
-        if meta.len() > max_bytes {
-            let mut old = path.as_os_str().to_owned();
-            old.push(".old");
-            let _ = std::fs::rename(path, std::path::PathBuf::from(old));
+/// `<path>.<generation>.gz` — generation 1 is the newest archive.
+fn archive_path(path: &Path, generation: u32) -> PathBuf {
+    let mut archive = path.as_os_str().to_owned();
+    archive.push(format!(".{}.gz", generation));
+    PathBuf::from(archive)
+}
+
+/// Once `path` exceeds `max_bytes`, compresses it into `<path>.1.gz`, shifting older archives
+/// up a generation and deleting those past `keep` (0 keeps none: the log just starts over). A
+/// failed compression leaves `path` in place.
+pub fn rotate_file(path: &Path, max_bytes: u64, keep: u32) {
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.len() > max_bytes => {}
+        _ => return,
+    }
+    for generation in (1..=MAX_LOG_RETENTION).rev() {
+        let archive = archive_path(path, generation);
+        if generation >= keep {
+            let _ = std::fs::remove_file(&archive);
+        } else if archive.exists() {
+            let _ = std::fs::rename(&archive, archive_path(path, generation + 1));
+        }
+    }
+    if keep > 0 {
+        // Compressed beside the target first, so a crash never leaves a truncated `.1.gz`.
+        let archive = archive_path(path, 1);
+        let partial = archive.with_extension("gz.partial");
+        let compressed = (|| -> std::io::Result<()> {
+            let mut source = std::fs::File::open(path)?;
+            let mut encoder = flate2::write::GzEncoder::new(
+                std::fs::File::create(&partial)?,
+                flate2::Compression::default(),
+            );
+            std::io::copy(&mut source, &mut encoder)?;
+            encoder.finish()?.sync_all()?;
+            std::fs::rename(&partial, &archive)
+        })();
+        if compressed.is_err() {
+            // Keep the live log: an oversized log beats losing it. The next run retries.
+            let _ = std::fs::remove_file(&partial);
+            return;
+        }
+    }
+    let _ = std::fs::remove_file(path);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+/// Which part of a run wrote a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    /// Job file, session gate, lock, binary/config/secrets.
+    Setup,
+    /// The transient rclone daemon's lifecycle.
+    Daemon,
+    /// The task's RC requests and their jobs.
+    Request,
+    /// Notification delivery.
+    Webhook,
+}
+
+/// One runner log line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    pub ts: String,
+    /// Absent on lines written before a run id existed (and on pre-JSON log lines).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    pub level: Level,
+    pub phase: Phase,
+    pub message: String,
+}
+
+impl LogRecord {
+    /// A log line, JSON or the older `[ts] message` text.
+    pub fn parse(line: &str) -> Option<Self> {
+        if let Ok(record) = serde_json::from_str(line) {
+            return Some(record);
+        }
+        let (ts, message) = line.strip_prefix('[')?.split_once("] ")?;
+        Some(Self {
+            ts: ts.to_string(),
+            run_id: None,
+            level: Level::Info,
+            phase: Phase::Setup,
+            message: message.to_string(),
+        })
+    }
+
+    /// The line for people: `[ts] WARN daemon: message` (the level only when not info).
+    pub fn render(&self) -> String {
+        let level = match self.level {
+            Level::Info => "",
+            Level::Warn => "WARN ",
+            Level::Error => "ERROR ",
+        };
+        let phase = match self.phase {
+            Phase::Setup => "setup",
+            Phase::Daemon => "daemon",
+            Phase::Request => "request",
+            Phase::Webhook => "webhook",
+        };
+        format!("[{}] {}{}: {}", self.ts, level, phase, self.message)
+    }
+}
+
+/// Every record of `run_id`, oldest first — from the live log and whichever archives hold it.
+pub fn read_run(path: &Path, run_id: &str) -> Vec<LogRecord> {
+    let mut found: Vec<Vec<LogRecord>> = Vec::new();
+    for generation in 0..=MAX_LOG_RETENTION {
+        let lines: Box<dyn BufRead> = if generation == 0 {
+            match std::fs::File::open(path) {
+                Ok(file) => Box::new(BufReader::new(file)),
+                Err(_) => continue,
+            }
+        } else {
+            match std::fs::File::open(archive_path(path, generation)) {
+                Ok(file) => Box::new(BufReader::new(flate2::read::GzDecoder::new(file))),
+                Err(_) => break,
+            }
+        };
+        let records: Vec<LogRecord> = lines
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| LogRecord::parse(&line))
+            .filter(|record| record.run_id.as_deref() == Some(run_id))
+            .collect();
+        // A run is written in one sitting: once found, the first older file without it ends it.
+        if records.is_empty() && !found.is_empty() {
+            break;
+        }
+        if !records.is_empty() {
+            found.push(records);
         }
     }
+    found.into_iter().rev().flatten().collect()
 }
 
-/// Simple appending logger for the runner, rotated at 1 MB.
+/// The runner's log: JSON lines tagged with the run id and the phase the runner is in.
 pub struct RunLog {
+    path: PathBuf,
     file: Option<std::fs::File>,
+    run_id: Option<String>,
+    phase: Phase,
 }
 
 impl RunLog {
     pub fn open(dirs: &AppDirs, task_id: &str) -> Self {
-        let path = log_path(dirs, task_id);
+        Self::at(log_path(dirs, task_id))
+    }
+
+    /// A log at `path` rather than the task's, for work that isn't a run.
+    pub fn at(path: PathBuf) -> Self {
         if let Some(parent) = path.parent() {
             let _ = std::fs::create_dir_all(parent);
         }
-        if let Ok(meta) = std::fs::metadata(&path) {
-            if meta.len() > LOG_ROTATE_BYTES {
-                let _ = std::fs::rename(&path, path.with_extension("log.old"));
-            }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .ok();
+        Self {
+            path,
+            file,
+            run_id: None,
+            phase: Phase::Setup,
         }
-        let file = OpenOptions::new().create(true).append(true).open(&path).ok();
-        Self { file }
+    }
+
+    /// Rotates the log once it passes 1 MB, keeping `keep` archives. Call before the run writes
+    /// its first line, so the whole run lands in one file.
+    pub fn rotate(&mut self, keep: u32) {
+        // Closed first: Windows can't rename a file that is open.
+        self.file = None;
+        rotate_file(&self.path, LOG_ROTATE_BYTES, keep);
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .ok();
+    }
+
+    pub fn set_run_id(&mut self, run_id: &str) {
+        self.run_id = Some(run_id.to_string());
+    }
+
+    /// The phase of the lines that follow.
+    pub fn set_phase(&mut self, phase: Phase) {
+        self.phase = phase;
     }
 
     pub fn line(&mut self, message: &str) {
-        if let Some(file) = &mut self.file {
-            let _ = writeln!(file, "[{}] {}", now_iso(), message);
+        self.record(self.phase, Level::Info, message);
+    }
+
+    pub fn warn(&mut self, message: &str) {
+        self.record(self.phase, Level::Warn, message);
+    }
+
+    pub fn error(&mut self, message: &str) {
+        self.record(self.phase, Level::Error, message);
+    }
+
+    /// One line outside the current phase.
+    pub fn record(&mut self, phase: Phase, level: Level, message: &str) {
+        let Some(file) = &mut self.file else {
+            return;
+        };
+        let record = LogRecord {
+            ts: now_iso(),
+            run_id: self.run_id.clone(),
+            level,
+            phase,
+            message: message.to_string(),
+        };
+        if let Ok(json) = serde_json::to_string(&record) {
+            let _ = writeln!(file, "{}", json);
         }
     }
 }
@@ -477,12 +702,17 @@ impl RunLog {
 pub fn remove_all(dirs: &AppDirs, task_id: &str) {
     let _ = std::fs::remove_file(history_path(dirs, task_id));
     let _ = std::fs::remove_file(lock_path(dirs, task_id));
-    let _ = std::fs::remove_file(log_path(dirs, task_id));
-    let _ = std::fs::remove_file(log_path(dirs, task_id).with_extension("log.old"));
-    // The runner also writes the transient daemon's stderr to `<task>.daemon.log`
-    // (rotated to `.daemon.log.old`); remove both so unregistering leaves nothing behind.
-    let _ = std::fs::remove_file(log_path(dirs, task_id).with_extension("daemon.log"));
-    let _ = std::fs::remove_file(log_path(dirs, task_id).with_extension("daemon.log.old"));
+    // The runner also writes the transient daemon's stderr to `<task>.daemon.log`; both logs
+    // go with their archives (and the `.old` files rotation left before it compressed).
+    for path in [log_path(dirs, task_id), daemon_log_path(dirs, task_id)] {
+        for generation in 1..=MAX_LOG_RETENTION {
+            let _ = std::fs::remove_file(archive_path(&path, generation));
+        }
+        let mut old = path.as_os_str().to_owned();
+        old.push(".old");
+        let _ = std::fs::remove_file(PathBuf::from(old));
+        let _ = std::fs::remove_file(path);
+    }
 }
 
 #[cfg(all(test, unix))]
@@ -542,4 +772,48 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&dirs.app_data);
     }
+
+    #[test]
+    fn run_logs_read_back_from_gzip_archives() {
+        let dirs = test_dirs("runlog");
+        let path = log_path(&dirs, "t1");
+        let write_run = |run_id: &str| {
+            let mut log = RunLog::open(&dirs, "t1");
+            log.set_run_id(run_id);
+            log.line("run-task t1 (host local)");
+            log.set_phase(Phase::Request);
+            log.error("job 3 failed: quota exceeded");
+        };
+
+        write_run("r1");
+        rotate_file(&path, 0, 2);
+        write_run("r2");
+        rotate_file(&path, 0, 2);
+        write_run("r3");
+        assert!(archive_path(&path, 2).exists());
+
+        let r1 = read_run(&path, "r1");
+        assert_eq!(r1.len(), 2);
+        assert_eq!(r1[1].phase, Phase::Request);
+        assert_eq!(r1[1].level, Level::Error);
+        assert!(r1[1]
+            .render()
+            .ends_with("] ERROR request: job 3 failed: quota exceeded"));
+        assert_eq!(read_run(&path, "r3").len(), 2);
+
+        // Past the retention the oldest archive goes.
+        rotate_file(&path, 0, 2);
+        assert!(read_run(&path, "r1").is_empty());
+        assert_eq!(read_run(&path, "r2").len(), 2);
+        assert!(!archive_path(&path, 3).exists());
+
+        // Lines from before the JSON log still read as text.
+        let legacy = LogRecord::parse("[2026-01-01T03:00:00.000Z] finished: success (12 ms)");
+        assert_eq!(
+            legacy.map(|record| record.render()),
+            Some("[2026-01-01T03:00:00.000Z] setup: finished: success (12 ms)".to_string())
+        );
+
+        let _ = std::fs::remove_dir_all(&dirs.app_data);
+    }
 }
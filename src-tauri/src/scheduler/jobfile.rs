@@ -2,8 +2,10 @@
 //!
 //! Written only by the `scheduler_register` command (atomic temp+rename); read by the runner and
 //! by `scheduler_status`. Dynamic state (passwords, proxy, webhook targets) is deliberately NOT
-//! stored here — the runner resolves it live from the app stores so it never goes stale.
+//! stored here — the runner resolves it live from the app stores so it never goes stale. Secret
+//! env values a task declares are kept in a separate owner-only file (`secrets_path`).
 
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
@@ -12,6 +14,8 @@ use super::storeread::AppDirs;
 
 pub const JOB_SCHEMA_VERSION: u32 = 1;
 pub const DEFAULT_MAX_RUN_SECONDS: u64 = 86_400;
+/// Upper bound for `max_parallel` — each job runs its own transfers on top of this.
+pub const MAX_PARALLEL_LIMIT: u32 = 16;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +27,46 @@ pub struct RcRequest {
     pub body: serde_json::Value,
 }
 
+/// Body keys that carry an fs string (`remote:path`, `remote,opt="v":path`, or a local path)
+/// across the endpoints the TS serializer emits — top-level or inside `/job/batch` inputs.
+const FS_KEYS: [&str; 5] = ["srcFs", "dstFs", "fs", "path1", "path2"];
+
+impl RcRequest {
+    /// Every fs string this request names (local paths included), in body order.
+    pub fn fs_strings(&self) -> Vec<&str> {
+        let mut bodies = vec![&self.body];
+        if let Some(inputs) = self.body.get("inputs").and_then(|i| i.as_array()) {
+            bodies.extend(inputs.iter());
+        }
+        bodies
+            .into_iter()
+            .flat_map(|body| FS_KEYS.iter().filter_map(|key| body.get(*key)?.as_str()))
+            .collect()
+    }
+
+    /// Configured remote names this request touches, in body order (duplicates kept).
+    pub fn remotes(&self) -> Vec<String> {
+        self.fs_strings()
+            .into_iter()
+            .filter_map(remote_name)
+            .collect()
+    }
+}
+
+/// The configured remote an fs string names, or None for local paths and on-the-fly backends
+/// (`:s3,…:` has no stored remote). A one-letter head is a Windows drive (`C:\…`), not a remote.
+pub fn remote_name(fs: &str) -> Option<String> {
+    let (head, _) = fs.split_once(':')?;
+    let name = head.split(',').next().unwrap_or(head);
+    if name.is_empty()
+        || name.contains(['/', '\\'])
+        || (name.len() == 1 && name.chars().all(|c| c.is_ascii_alphabetic()))
+    {
+        return None;
+    }
+    Some(name.to_string())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct JobSpec {
@@ -48,6 +92,36 @@ pub struct JobSpec {
     /// so migrated tasks keep their effective semantics.
     #[serde(default = "default_run_mode")]
     pub run_mode: String,
+    /// Bisync tasks only: "never" (the default), "first-run" or "always" — whether the runner may
+    /// retry a bisync that needs `--resync` with resync on (see bisync.rs).
+    #[serde(default = "default_bisync_resync")]
+    pub bisync_resync: String,
+    /// Extra environment for the transient daemon (`RCLONE_TRANSFERS`, `RCLONE_S3_…`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Names of env vars whose values are secrets — the values live in the task's secrets file
+    /// (`secrets_path`), never in the job file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secret_env: Vec<String>,
+    /// Extra global flags appended to the daemon's command line, one `--name[=value]` each.
+    /// Visible in the process list while the run lasts — secrets belong in `secret_env`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub daemon_flags: Vec<String>,
+    /// How many of `requests` run at once; 1 (the default) runs them one after another.
+    #[serde(default = "default_max_parallel")]
+    pub max_parallel: u32,
+    /// Keep going after a request fails — the rest still run, and the run still fails. Default:
+    /// the first failure stops the remaining requests.
+    #[serde(default)]
+    pub continue_on_error: bool,
+    /// Rotated runner/daemon logs kept as gzip archives (0 keeps none).
+    #[serde(default = "default_log_retention")]
+    pub log_retention: u32,
+    /// The last enabled state chosen for the task — by a GUI registration or toggle, or the
+    /// headless `enable`/`disable`. The GUI adopts it from the status, so a headless disable
+    /// isn't re-armed by its next reconcile. Absent in job files written before it was kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
     pub requests: Vec<RcRequest>,
 }
 
@@ -57,6 +131,14 @@ impl JobSpec {
     pub fn is_user_mode(&self) -> bool {
         self.run_mode != "system"
     }
+
+    /// The configured remotes the task touches, each once.
+    pub fn remotes(&self) -> Vec<String> {
+        let mut remotes: Vec<String> = self.requests.iter().flat_map(|r| r.remotes()).collect();
+        remotes.sort();
+        remotes.dedup();
+        remotes
+    }
 }
 
 fn default_max_run_seconds() -> u64 {
@@ -67,6 +149,143 @@ fn default_run_mode() -> String {
     "user".to_string()
 }
 
+fn default_bisync_resync() -> String {
+    "never".to_string()
+}
+
+fn default_max_parallel() -> u32 {
+    1
+}
+
+fn default_log_retention() -> u32 {
+    super::history::DEFAULT_LOG_RETENTION
+}
+
+/// Env the runner sets itself (storeread::build_run_env) or that would redirect the daemon away
+/// from the runner: an override would silently fight it.
+const RESERVED_ENV: [&str; 6] = [
+    "RCLONE_CONFIG",
+    "RCLONE_CONFIG_DIR",
+    "RCLONE_CONFIG_PASS",
+    "RCLONE_CONFIG_PASS_COMMAND",
+    "RCLONE_ASK_PASSWORD",
+    "RCLONE_PASSWORD_COMMAND",
+];
+const RESERVED_ENV_PREFIXES: [&str; 2] = ["RCLONE_RC_", "RCLONE_LOG_FILE"];
+/// Same for flags: the RC server the runner talks to, the pinned config, the daemon log.
+const RESERVED_FLAG_PREFIXES: [&str; 5] = [
+    "--rc",
+    "--config",
+    "--log-file",
+    "--password-command",
+    "--ask-password",
+];
+
+/// Why `name` can't be a task variable: not a portable env name, or one the runner owns.
+pub fn check_env_name(name: &str) -> Result<(), String> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_uppercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(format!(
+            "'{}' is not a valid environment variable name (use A-Z, 0-9 and _)",
+            name
+        ));
+    }
+    if RESERVED_ENV.contains(&name) || RESERVED_ENV_PREFIXES.iter().any(|p| name.starts_with(p)) {
+        return Err(format!(
+            "{} is set by the scheduler itself and can't be overridden",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects env names and flags the runner owns, and anything that can't survive the trip into
+/// a process environment or argv intact.
+pub fn validate_overrides(spec: &JobSpec) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for name in spec.env.keys().chain(&spec.secret_env) {
+        check_env_name(name)?;
+        if !seen.insert(name) {
+            return Err(format!("{} is set more than once", name));
+        }
+    }
+    if let Some((name, _)) = spec
+        .env
+        .iter()
+        .find(|(_, v)| v.contains(['\0', '\n', '\r']))
+    {
+        return Err(format!("the value of {} contains a line break", name));
+    }
+    for flag in &spec.daemon_flags {
+        let name = flag.split('=').next().unwrap_or_default();
+        let valid = name.len() > 2
+            && name.starts_with("--")
+            && name[2..]
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid || flag.contains(['\0', '\n', '\r']) {
+            return Err(format!(
+                "'{}' is not a flag — use one --name or --name=value per entry",
+                flag
+            ));
+        }
+        if RESERVED_FLAG_PREFIXES.iter().any(|p| name.starts_with(p)) {
+            return Err(format!(
+                "{} is set by the scheduler itself and can't be overridden",
+                name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Names whose values must never reach a log, whether or not the user marked them secret.
+fn is_sensitive(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    ["pass", "secret", "token", "key", "credential", "auth"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+}
+
+/// The task's env overrides for the runner log:
+/// "RCLONE_TRANSFERS=8, RCLONE_S3_SECRET_ACCESS_KEY=<redacted>".
+pub fn describe_env(spec: &JobSpec) -> String {
+    spec.env
+        .iter()
+        .map(|(name, value)| {
+            if is_sensitive(name) {
+                format!("{}=<redacted>", name)
+            } else {
+                format!("{}={}", name, value)
+            }
+        })
+        .chain(
+            spec.secret_env
+                .iter()
+                .map(|name| format!("{}=<redacted>", name)),
+        )
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The task's extra flags for the runner log, values of sensitive-looking flags redacted.
+pub fn describe_flags(spec: &JobSpec) -> String {
+    spec.daemon_flags
+        .iter()
+        .map(|flag| match flag.split_once('=') {
+            Some((name, _)) if is_sensitive(name) => format!("{}=<redacted>", name),
+            _ => flag.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn jobs_dir(dirs: &AppDirs, host_id: &str) -> PathBuf {
     dirs.app_data.join("scheduler").join("jobs").join(host_id)
 }
@@ -104,6 +323,77 @@ pub fn save(dirs: &AppDirs, spec: &JobSpec) -> Result<(), String> {
 
 pub fn remove(dirs: &AppDirs, host_id: &str, task_id: &str) {
     let _ = std::fs::remove_file(job_path(dirs, host_id, task_id));
+    let _ = std::fs::remove_file(secrets_path(dirs, host_id, task_id));
+}
+
+/// Secret env values for a task, next to (not inside) the jobs tree: job files are meant to be
+/// readable and shareable, this file is owner-only.
+pub fn secrets_path(dirs: &AppDirs, host_id: &str, task_id: &str) -> PathBuf {
+    dirs.app_data
+        .join("scheduler")
+        .join("secrets")
+        .join(host_id)
+        .join(format!("{}.json", task_id))
+}
+
+/// The stored secret values (empty when none are stored or the file is unreadable).
+pub fn load_secrets(dirs: &AppDirs, host_id: &str, task_id: &str) -> BTreeMap<String, String> {
+    std::fs::read_to_string(secrets_path(dirs, host_id, task_id))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the values for `spec.secret_env`: a value in `provided` replaces the stored one, a
+/// name without one keeps its stored value (re-registration from the GUI never resends them),
+/// and values for names no longer declared are dropped. Errors when a declared secret has no
+/// value at all.
+pub fn save_secrets(
+    dirs: &AppDirs,
+    spec: &JobSpec,
+    provided: &HashMap<String, String>,
+) -> Result<(), String> {
+    let path = secrets_path(dirs, &spec.host_id, &spec.task_id);
+    if spec.secret_env.is_empty() {
+        let _ = std::fs::remove_file(&path);
+        return Ok(());
+    }
+    let mut stored = load_secrets(dirs, &spec.host_id, &spec.task_id);
+    let mut secrets = BTreeMap::new();
+    for name in &spec.secret_env {
+        let value = provided
+            .get(name)
+            .cloned()
+            .or_else(|| stored.remove(name))
+            .ok_or_else(|| format!("no value entered for the secret {}", name))?;
+        if value.contains(['\0', '\n', '\r']) {
+            return Err(format!("the value of {} contains a line break", name));
+        }
+        secrets.insert(name.clone(), value);
+    }
+
+    let dir = path.parent().ok_or("invalid secrets path")?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("failed to create secrets dir: {}", e))?;
+    let tmp = path.with_extension("json.tmp");
+    let _ = std::fs::remove_file(&tmp);
+    {
+        use std::io::Write as _;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt as _;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(&tmp)
+            .map_err(|e| format!("failed to create secrets file: {}", e))?;
+        let json = serde_json::to_string(&secrets).map_err(|e| e.to_string())?;
+        file.write_all(json.as_bytes())
+            .map_err(|e| format!("failed to write secrets file: {}", e))?;
+    }
+    std::fs::rename(&tmp, &path).map_err(|e| format!("failed to move secrets file: {}", e))?;
+    Ok(())
 }
 
 /// All job specs registered for a host (unreadable files skipped with a log line).
@@ -128,3 +418,138 @@ pub fn list(dirs: &AppDirs, host_id: &str) -> Vec<JobSpec> {
     }
     specs
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remotes_cover_batch_inputs_and_skip_local_paths() {
+        let batch = RcRequest {
+            endpoint: "/job/batch".into(),
+            body: serde_json::json!({
+                "inputs": [
+                    { "srcFs": "/home/me/docs", "dstFs": "gdrive,chunk_size=\"8M\":backup/docs" },
+                    { "srcFs": "C:\\Users\\me", "dstFs": ":s3,provider=AWS:bucket" },
+                ],
+                "_async": true,
+            }),
+        };
+        assert_eq!(batch.remotes(), vec!["gdrive".to_string()]);
+
+        let bisync = RcRequest {
+            endpoint: "/sync/bisync".into(),
+            body: serde_json::json!({ "path1": "onedrive:Work", "path2": "b2:archive" }),
+        };
+        assert_eq!(
+            bisync.remotes(),
+            vec!["onedrive".to_string(), "b2".to_string()]
+        );
+    }
+
+    fn spec_with_overrides(env: &[(&str, &str)], secret_env: &[&str], flags: &[&str]) -> JobSpec {
+        serde_json::from_value(serde_json::json!({
+            "schemaVersion": JOB_SCHEMA_VERSION,
+            "taskId": "t1",
+            "hostId": "local",
+            "name": "Backup",
+            "operation": "sync",
+            "cron": "0 3 * * *",
+            "configId": "default",
+            "binary": "app-default",
+            "env": env.iter().copied().collect::<BTreeMap<_, _>>(),
+            "secretEnv": secret_env,
+            "daemonFlags": flags,
+            "requests": [],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn overrides_are_validated_and_redacted() {
+        let spec = spec_with_overrides(
+            &[
+                ("RCLONE_TRANSFERS", "8"),
+                ("RCLONE_S3_ACCESS_KEY_ID", "AKIA123"),
+            ],
+            &["RCLONE_S3_SECRET_ACCESS_KEY"],
+            &["--fast-list", "--s3-session-token=abc", "--checkers=16"],
+        );
+        assert_eq!(validate_overrides(&spec), Ok(()));
+        assert_eq!(
+            describe_env(&spec),
+            "RCLONE_S3_ACCESS_KEY_ID=<redacted>, RCLONE_TRANSFERS=8, RCLONE_S3_SECRET_ACCESS_KEY=<redacted>"
+        );
+        assert_eq!(
+            describe_flags(&spec),
+            "--fast-list --s3-session-token=<redacted> --checkers=16"
+        );
+
+        for bad in [
+            spec_with_overrides(&[("RCLONE_CONFIG", "/tmp/x.conf")], &[], &[]),
+            spec_with_overrides(&[("RCLONE_RC_ADDR", ":5572")], &[], &[]),
+            spec_with_overrides(&[("rclone transfers", "8")], &[], &[]),
+            spec_with_overrides(&[("RCLONE_X", "a\nb")], &[], &[]),
+            spec_with_overrides(&[("RCLONE_X", "1")], &["RCLONE_X"], &[]),
+            spec_with_overrides(&[], &[], &["--rc-addr=:5572"]),
+            spec_with_overrides(&[], &[], &["--config=/etc/rclone.conf"]),
+            spec_with_overrides(&[], &[], &["-v"]),
+            spec_with_overrides(&[], &[], &["--transfers", "8"]),
+        ] {
+            assert!(validate_overrides(&bad).is_err(), "accepted {:?}", bad);
+        }
+    }
+
+    #[test]
+    fn secrets_keep_stored_values_and_drop_undeclared_ones() {
+        let root =
+            std::env::temp_dir().join(format!("rcloneui-secrets-test-{}", std::process::id()));
+        let dirs = AppDirs {
+            app_data: root.clone(),
+            app_local_data: root.clone(),
+        };
+        let provided = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let spec = spec_with_overrides(&[], &["RCLONE_S3_SECRET_ACCESS_KEY", "RCLONE_B2_KEY"], &[]);
+        assert!(save_secrets(&dirs, &spec, &provided(&[("RCLONE_B2_KEY", "b2")])).is_err());
+        save_secrets(
+            &dirs,
+            &spec,
+            &provided(&[
+                ("RCLONE_S3_SECRET_ACCESS_KEY", "s3"),
+                ("RCLONE_B2_KEY", "b2"),
+            ]),
+        )
+        .unwrap();
+
+        // Re-registration without values keeps them; a dropped name loses its value.
+        let spec = spec_with_overrides(&[], &["RCLONE_S3_SECRET_ACCESS_KEY"], &[]);
+        save_secrets(&dirs, &spec, &HashMap::new()).unwrap();
+        let stored = load_secrets(&dirs, "local", "t1");
+        assert_eq!(
+            stored
+                .get("RCLONE_S3_SECRET_ACCESS_KEY")
+                .map(String::as_str),
+            Some("s3")
+        );
+        assert!(!stored.contains_key("RCLONE_B2_KEY"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt as _;
+            let mode = std::fs::metadata(secrets_path(&dirs, "local", "t1"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        remove(&dirs, "local", "t1");
+        assert!(load_secrets(&dirs, "local", "t1").is_empty());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
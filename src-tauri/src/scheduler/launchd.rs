@@ -18,7 +18,7 @@ use std::process::{Command, Stdio};
 
 use super::cronconv::{self, LaunchdCalendar};
 use super::storeread::AppDirs;
-use super::{InstallState, RenderedSchedule, SchedulerBackend};
+use super::{unescape_xml, ArtifactView, InstallState, RenderedSchedule, SchedulerBackend};
 
 const LABEL_PREFIX: &str = "com.rclone.ui.task.";
 
@@ -195,6 +195,27 @@ impl LaunchdBackend {
     }
 }
 
+/// A plist we wrote as an ArtifactView: ProgramArguments, and the StartCalendarInterval array
+/// with its whitespace removed as the schedule.
+fn plist_view(plist: &str, enabled: bool) -> Option<ArtifactView> {
+    let array = |key: &str| {
+        let rest = &plist[plist.find(&format!("<key>{}</key>", key))?..];
+        let start = rest.find("<array>")? + "<array>".len();
+        Some(&rest[start..rest.find("</array>")?])
+    };
+    let mut words = array("ProgramArguments")?
+        .split("<string>")
+        .skip(1)
+        .filter_map(|item| item.split_once("</string>"))
+        .map(|(value, _)| unescape_xml(value));
+    Some(ArtifactView {
+        enabled,
+        schedule: array("StartCalendarInterval")?.split_whitespace().collect(),
+        program: words.next()?,
+        args: words.collect(),
+    })
+}
+
 fn render_calendar(cal: &LaunchdCalendar) -> String {
     let mut body = String::new();
     let mut push = |key: &str, value: Option<u16>| {
@@ -214,6 +235,10 @@ fn render_calendar(cal: &LaunchdCalendar) -> String {
 }
 
 impl SchedulerBackend for LaunchdBackend {
+    fn kind(&self) -> &'static str {
+        "launchd"
+    }
+
     fn install(&self, task_id: &str, rendered: &RenderedSchedule) -> Result<(), String> {
         let plist = self.build_plist(task_id, rendered)?;
         let active = self.active_path(task_id);
@@ -342,6 +367,30 @@ impl SchedulerBackend for LaunchdBackend {
         }
     }
 
+    fn expected(&self, task_id: &str, rendered: &RenderedSchedule) -> Result<ArtifactView, String> {
+        let plist = self.build_plist(task_id, rendered)?;
+        plist_view(&plist, rendered.enabled)
+            .ok_or_else(|| "malformed LaunchAgent plist".to_string())
+    }
+
+    fn inspect(&self, task_id: &str) -> Result<Option<ArtifactView>, String> {
+        for (path, enabled) in [
+            (self.active_path(task_id), true),
+            (self.parked_path(task_id), false),
+        ] {
+            match std::fs::read_to_string(&path) {
+                Ok(plist) => {
+                    return plist_view(&plist, enabled)
+                        .map(Some)
+                        .ok_or_else(|| format!("{} is not a plist this app wrote", path.display()))
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("failed to read {}: {}", path.display(), e)),
+            }
+        }
+        Ok(None)
+    }
+
     fn health_warning(&self, task_id: &str) -> Option<String> {
         // An active plist that launchd does NOT have loaded while we (a GUI process in the same
         // login session) are running means something outside the app unloaded it — since macOS
@@ -446,6 +495,22 @@ mod tests {
         assert!(plist.contains("<key>RunAtLoad</key>\n  <false/>"));
     }
 
+    #[test]
+    fn plist_reads_back_as_its_rendering() {
+        let backend = LaunchdBackend::new(&dirs());
+        let mut rendered = rendered("0 3 * * 1");
+        rendered.args.push("/Users/x/A & B".into());
+        let view = backend.expected("abc", &rendered).unwrap();
+        assert_eq!(
+            view.program,
+            "/Applications/Rclone UI.app/Contents/MacOS/Rclone UI"
+        );
+        assert_eq!(view.args, rendered.args);
+        assert!(view
+            .schedule
+            .contains("<key>Weekday</key><integer>1</integer>"));
+    }
+
     #[test]
     fn plist_every_minute_is_empty_calendar_dict() {
         let backend = LaunchdBackend::new(&dirs());
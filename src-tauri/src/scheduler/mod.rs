@@ -10,7 +10,15 @@
 //! (`--talk-name=org.freedesktop.Flatpak`): the crontab commands run on the host via
 //! `flatpak-spawn --host`, and the cron entry re-launches the app with `flatpak run … run-task`.
 
+pub mod adopt;
+pub mod artifact;
+pub mod bisync;
+pub mod bundle;
+pub mod cli;
 pub mod cronconv;
+pub mod daemonlog;
+pub mod doctor;
+pub mod drift;
 pub mod history;
 pub mod jobfile;
 pub mod runner;
@@ -25,7 +33,8 @@ mod schtasks;
 #[cfg(target_os = "windows")]
 mod winjob;
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use serde::Serialize;
 use tauri::AppHandle;
@@ -75,7 +84,32 @@ pub struct RenderedSchedule {
     pub max_run_seconds: u64,
 }
 
+/// An OS artifact reduced to when and how it runs the task — the form drift checks compare
+/// (drift.rs), for both the installed artifact and what `install` would write today.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactView {
+    pub enabled: bool,
+    /// The schedule in the backend's own encoding (crontab fields, launchd calendar dicts, Task
+    /// Scheduler triggers) — only ever compared with the same backend's rendering.
+    pub schedule: String,
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+/// Reads back a value the XML backends (launchd plists, Task Scheduler definitions) escaped.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn unescape_xml(escaped: &str) -> String {
+    escaped
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
 pub trait SchedulerBackend: Send + Sync {
+    /// The scheduler's name in user-facing messages ("crontab", "launchd", "Task Scheduler").
+    fn kind(&self) -> &'static str;
     /// Create or overwrite the OS artifact in `rendered.enabled`'s state. Idempotent.
     fn install(&self, task_id: &str, rendered: &RenderedSchedule) -> Result<(), String>;
     /// Remove the OS artifact. Idempotent (missing artifacts are not an error).
@@ -83,6 +117,10 @@ pub trait SchedulerBackend: Send + Sync {
     fn set_enabled(&self, task_id: &str, enabled: bool) -> Result<(), String>;
     fn run_now(&self, task_id: &str) -> Result<(), String>;
     fn is_installed(&self, task_id: &str) -> Result<InstallState, String>;
+    /// What `install` would write for `rendered`, as an `ArtifactView`.
+    fn expected(&self, task_id: &str, rendered: &RenderedSchedule) -> Result<ArtifactView, String>;
+    /// The task's installed artifact read back; None when there is none.
+    fn inspect(&self, task_id: &str) -> Result<Option<ArtifactView>, String>;
     /// A user-visible reason the task won't fire even though it is installed and enabled —
     /// state the backend's own enabled model cannot see (macOS: the background item toggled off
     /// in System Settings unloads the agent while the plist stays in LaunchAgents). None = healthy.
@@ -337,22 +375,28 @@ pub struct CronValidation {
     /// cron on the dom/dow star flag, so predicting fires anywhere else risks showing runs the
     /// native schedule will never perform. Empty when invalid (or nothing fires within 5 years).
     pub next_runs: Vec<String>,
+    /// When the schedule fires, in words (see cronconv::describe). None when invalid.
+    pub description: Option<String>,
 }
 
+/// `language` is the UI's BCP 47 tag; descriptions fall back to English.
 #[tauri::command]
-pub fn scheduler_validate_cron(cron: String) -> CronValidation {
-    match cronconv::validate_for_current_platform(&cron) {
-        Ok(()) => CronValidation {
+pub fn scheduler_validate_cron(cron: String, language: Option<String>) -> CronValidation {
+    match cronconv::validate_for_current_platform(&cron).and_then(|()| cronconv::parse(&cron)) {
+        Ok(spec) => CronValidation {
             valid: true,
             error: None,
-            next_runs: cronconv::parse(&cron)
-                .map(|spec| cronconv::next_fires(&spec, chrono::Local::now(), 10))
-                .unwrap_or_default(),
+            next_runs: cronconv::next_fires(&spec, chrono::Local::now(), 10),
+            description: Some(cronconv::describe(
+                &spec,
+                language.as_deref().unwrap_or("en"),
+            )),
         },
         Err(error) => CronValidation {
             valid: false,
             error: Some(error),
             next_runs: Vec::new(),
+            description: None,
         },
     }
 }
@@ -360,67 +404,104 @@ pub fn scheduler_validate_cron(cron: String) -> CronValidation {
 /// UPSERT: write the job file and (re)install the OS artifact in the given enabled state (one
 /// operation — no separate set_enabled step to half-fail). The backend depends on the run mode
 /// (macOS user → launchd, else crontab/schtasks); a mode flip first uninstalls the old artifact
-/// from the other backend so the task never fires twice.
+/// from the other backend so the task never fires twice. `secrets` carries new values for
+/// `spec.secret_env` (names left out keep their stored value).
 #[tauri::command]
-pub async fn scheduler_register(app: AppHandle, spec: JobSpec, enabled: bool) -> Result<(), String> {
+pub async fn scheduler_register(
+    app: AppHandle,
+    spec: JobSpec,
+    enabled: bool,
+    secrets: Option<HashMap<String, String>>,
+) -> Result<(), String> {
     tauri::async_runtime::spawn_blocking(move || {
         let dirs = storeread::app_dirs_from(&app)?;
+        register(&dirs, &spec, enabled, &secrets.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-        sanitize_id(&spec.task_id)?;
-        sanitize_id(&spec.host_id)?;
-        if spec.schema_version != jobfile::JOB_SCHEMA_VERSION {
-            return Err(format!(
-                "unsupported job schema version {}",
-                spec.schema_version
-            ));
-        }
-        if spec.host_id != "local" {
-            return Err("Scheduling is only supported for the local host".to_string());
-        }
-        if spec.requests.is_empty() {
-            return Err("The task produced no rclone requests".to_string());
-        }
+/// The registration path behind `scheduler_register`, shared with `scheduler_import`.
+pub fn register(
+    dirs: &AppDirs,
+    spec: &JobSpec,
+    enabled: bool,
+    secrets: &HashMap<String, String>,
+) -> Result<(), String> {
+    sanitize_id(&spec.task_id)?;
+    sanitize_id(&spec.host_id)?;
+    if spec.schema_version != jobfile::JOB_SCHEMA_VERSION {
+        return Err(format!(
+            "unsupported job schema version {}",
+            spec.schema_version
+        ));
+    }
+    if spec.host_id != "local" {
+        return Err("Scheduling is only supported for the local host".to_string());
+    }
+    if spec.requests.is_empty() {
+        return Err("The task produced no rclone requests".to_string());
+    }
+    if !(1..=jobfile::MAX_PARALLEL_LIMIT).contains(&spec.max_parallel) {
+        return Err(format!(
+            "maxParallel must be between 1 and {}",
+            jobfile::MAX_PARALLEL_LIMIT
+        ));
+    }
+    if spec.log_retention > history::MAX_LOG_RETENTION {
+        return Err(format!(
+            "logRetention must be at most {}",
+            history::MAX_LOG_RETENTION
+        ));
+    }
+    jobfile::validate_overrides(spec)?;
 
-        // Linux 'User' mode is gated at fire time on logind session state — a system without
-        // systemd-logind/elogind can never pass that gate, so every fire would silently skip.
-        // Registration happens from the GUI, i.e. while the user IS logged in: failing the gate
-        // right now proves it can never pass, and the error can name the fix.
-        #[cfg(target_os = "linux")]
-        {
-            if spec.is_user_mode() && !runner::user_has_login_session() {
-                return Err(
-                    "This system does not report login sessions (systemd-logind or elogind is required for the 'User' run mode to know when you are logged in). Switch this schedule's run mode to 'System', which runs regardless of login state."
-                        .to_string(),
-                );
-            }
+    // Linux 'User' mode is gated at fire time on logind session state — a system without
+    // systemd-logind/elogind can never pass that gate, so every fire would silently skip.
+    // Registration happens from the GUI, i.e. while the user IS logged in: failing the gate
+    // right now proves it can never pass, and the error can name the fix.
+    #[cfg(target_os = "linux")]
+    {
+        if spec.is_user_mode() && !runner::user_has_login_session() {
+            return Err(
+                "This system does not report login sessions (systemd-logind or elogind is required for the 'User' run mode to know when you are logged in). Switch this schedule's run mode to 'System', which runs regardless of login state."
+                    .to_string(),
+            );
         }
+    }
 
-        let _guard = mutation_guard();
-        let user_mode = spec.is_user_mode();
-        let backend = backend_for(&dirs, user_mode)?;
-        let rendered = render(&dirs, &spec, enabled)?;
-        // Remove any artifact left in the other backend (a user↔system flip on macOS) BEFORE the
-        // job file changes. Order matters: if this cleanup fails after the job file already says
-        // the NEW mode, the old backend's still-firing trigger would run under the new mode's
-        // contract — on macOS a cron fire would be trusted as launchd-in-session and skip every
-        // gate. Failing here leaves old trigger + old job file: consistent old behavior.
-        for other in other_backends(&dirs, user_mode)? {
-            other.uninstall(&spec.task_id).map_err(|e| {
-                format!("failed to remove the task's previous registration: {}", e)
-            })?;
-        }
-        jobfile::save(&dirs, &spec)?;
-        if let Err(e) = backend.install(&spec.task_id, &rendered) {
-            // Keep the reported state truthful: "not registered" must mean nothing fires. The
-            // old artifact would otherwise keep firing the OLD schedule against the NEW job
-            // file. The job file stays for the startup reconcile to retry.
-            let _ = backend.uninstall(&spec.task_id);
-            return Err(e);
-        }
-        Ok(())
-    })
-    .await
-    .map_err(|e| e.to_string())?
+    let _guard = mutation_guard();
+    let user_mode = spec.is_user_mode();
+    let backend = backend_for(dirs, user_mode)?;
+    let rendered = render(dirs, spec, enabled)?;
+    // Before anything changes: a job file declaring a secret with no stored value would
+    // fail every run.
+    jobfile::save_secrets(dirs, spec, secrets)?;
+    // Remove any artifact left in the other backend (a user↔system flip on macOS) BEFORE the
+    // job file changes. Order matters: if this cleanup fails after the job file already says
+    // the NEW mode, the old backend's still-firing trigger would run under the new mode's
+    // contract — on macOS a cron fire would be trusted as launchd-in-session and skip every
+    // gate. Failing here leaves old trigger + old job file: consistent old behavior.
+    for other in other_backends(dirs, user_mode)? {
+        other
+            .uninstall(&spec.task_id)
+            .map_err(|e| format!("failed to remove the task's previous registration: {}", e))?;
+    }
+    jobfile::save(
+        dirs,
+        &JobSpec {
+            enabled: Some(enabled),
+            ..spec.clone()
+        },
+    )?;
+    if let Err(e) = backend.install(&spec.task_id, &rendered) {
+        // Keep the reported state truthful: "not registered" must mean nothing fires. The
+        // old artifact would otherwise keep firing the OLD schedule against the NEW job
+        // file. The job file stays for the startup reconcile to retry.
+        let _ = backend.uninstall(&spec.task_id);
+        return Err(e);
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -460,60 +541,86 @@ pub async fn scheduler_set_enabled(
 ) -> Result<(), String> {
     tauri::async_runtime::spawn_blocking(move || {
         let dirs = storeread::app_dirs_from(&app)?;
-        let task_id = sanitize_id(&task_id)?;
-        let _guard = mutation_guard();
-        // Load the spec to pick the backend the task is actually registered in (macOS user vs
-        // system live in different backends).
-        let user_mode = jobfile::load(&dirs, "local", &task_id)
-            .map(|spec| spec.is_user_mode())
-            .unwrap_or(true);
-        let result = backend_for(&dirs, user_mode)?.set_enabled(&task_id, enabled);
-
-        // Disabling must reach whatever artifact actually exists. After a failed registration
-        // or mode flip the artifact can live in the OTHER backend (or nowhere): try every
-        // backend, treat "no artifact anywhere" as success (nothing armed IS disabled), but
-        // never swallow a real failure — that would leave the task firing while the UI says
-        // paused. Enabling keeps the strict single-backend error: it must not guess.
-        if !enabled {
-            let mut real_error = match result {
+        set_enabled(&dirs, &task_id, enabled)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Shared by `scheduler_set_enabled` and the headless `enable`/`disable` subcommands. Once the
+/// trigger is flipped the choice is kept in the job file, where the GUI picks it up.
+pub fn set_enabled(dirs: &AppDirs, task_id: &str, enabled: bool) -> Result<(), String> {
+    let task_id = sanitize_id(task_id)?;
+    let _guard = mutation_guard();
+    flip_trigger(dirs, &task_id, enabled)?;
+    match jobfile::load(dirs, "local", &task_id) {
+        Ok(spec) => jobfile::save(
+            dirs,
+            &JobSpec {
+                enabled: Some(enabled),
+                ..spec
+            },
+        ),
+        // No job file: nothing left to fire, and nothing for the GUI to adopt.
+        Err(_) => Ok(()),
+    }
+}
+
+fn flip_trigger(dirs: &AppDirs, task_id: &str, enabled: bool) -> Result<(), String> {
+    // Load the spec to pick the backend the task is actually registered in (macOS user vs
+    // system live in different backends).
+    let user_mode = jobfile::load(dirs, "local", task_id)
+        .map(|spec| spec.is_user_mode())
+        .unwrap_or(true);
+    let result = backend_for(dirs, user_mode)?.set_enabled(task_id, enabled);
+
+    // Disabling must reach whatever artifact actually exists. After a failed registration
+    // or mode flip the artifact can live in the OTHER backend (or nowhere): try every
+    // backend, treat "no artifact anywhere" as success (nothing armed IS disabled), but
+    // never swallow a real failure — that would leave the task firing while the UI says
+    // paused. Enabling keeps the strict single-backend error: it must not guess.
+    if !enabled {
+        let mut real_error = match result {
+            Ok(()) => return Ok(()),
+            Err(e) if e == NOT_REGISTERED => None,
+            Err(e) => Some(e),
+        };
+        for backend in all_backends(dirs) {
+            match backend.set_enabled(task_id, false) {
                 Ok(()) => return Ok(()),
-                Err(e) if e == NOT_REGISTERED => None,
-                Err(e) => Some(e),
-            };
-            for backend in all_backends(&dirs) {
-                match backend.set_enabled(&task_id, false) {
-                    Ok(()) => return Ok(()),
-                    Err(e) if e == NOT_REGISTERED => {}
-                    Err(e) => {
-                        real_error.get_or_insert(e);
-                    }
+                Err(e) if e == NOT_REGISTERED => {}
+                Err(e) => {
+                    real_error.get_or_insert(e);
                 }
             }
-            return match real_error {
-                Some(e) => Err(e),
-                None => Ok(()),
-            };
         }
-        result
-    })
-    .await
-    .map_err(|e| e.to_string())?
+        return match real_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        };
+    }
+    result
 }
 
 #[tauri::command]
 pub async fn scheduler_run_now(app: AppHandle, task_id: String) -> Result<(), String> {
     tauri::async_runtime::spawn_blocking(move || {
         let dirs = storeread::app_dirs_from(&app)?;
-        let task_id = sanitize_id(&task_id)?;
-        let user_mode = jobfile::load(&dirs, "local", &task_id)
-            .map(|spec| spec.is_user_mode())
-            .unwrap_or(true);
-        backend_for(&dirs, user_mode)?.run_now(&task_id)
+        run_now(&dirs, &task_id)
     })
     .await
     .map_err(|e| e.to_string())?
 }
 
+/// Fires the task through its OS scheduler (detached — the run is the scheduler's child).
+pub fn run_now(dirs: &AppDirs, task_id: &str) -> Result<(), String> {
+    let task_id = sanitize_id(task_id)?;
+    let user_mode = jobfile::load(dirs, "local", &task_id)
+        .map(|spec| spec.is_user_mode())
+        .unwrap_or(true);
+    backend_for(dirs, user_mode)?.run_now(&task_id)
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskStatus {
@@ -521,6 +628,10 @@ pub struct TaskStatus {
     pub installed: bool,
     pub enabled: bool,
     pub running: bool,
+    /// The job file's `enabled`: the state last chosen in the GUI or with the headless
+    /// `enable`/`disable`, which the GUI adopts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intended_enabled: Option<bool>,
     pub last_finished: Option<serde_json::Value>,
     /// Backend health warning (see `SchedulerBackend::health_warning`).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -534,69 +645,80 @@ pub async fn scheduler_status(app: AppHandle, host_id: String) -> Result<Vec<Tas
         let dirs = storeread::app_dirs_from(&app)?;
         let host_id = sanitize_id(&host_id)?;
 
-        let mut statuses = Vec::new();
-        for spec in jobfile::list(&dirs, &host_id) {
-            // Per-task backend: a macOS user-mode task's state lives in launchd, a system-mode
-            // task's in crontab.
-            let backend = backend_for(&dirs, spec.is_user_mode());
-            let install_state = backend
-                .as_ref()
-                .ok()
-                .map(|backend| backend.is_installed(&spec.task_id))
-                .and_then(Result::ok)
-                .unwrap_or(InstallState::NotInstalled);
-            let (installed, enabled) = match install_state {
-                InstallState::NotInstalled => (false, false),
-                InstallState::Installed { enabled } => (true, enabled),
-            };
-            let warning = backend
-                .as_ref()
-                .ok()
-                .and_then(|backend| backend.health_warning(&spec.task_id));
-
-            let running = history::is_running(&dirs, &spec.task_id);
-            let lines = history::read(&dirs, &spec.task_id, 20);
-            let event_of = |line: &serde_json::Value| {
-                line.get("event").and_then(|e| e.as_str()).map(str::to_owned)
-            };
-            // Newest-first: the latest started/finished event is the latest ATTEMPT. A started
-            // with no finished and no live lock is a run that died without writing its terminal
-            // record (crash, SIGKILL, power loss, Task Scheduler hard timeout) — surfacing the
-            // older success (or "Never") instead would hide the interruption.
-            let newest_attempt = lines.iter().find(|line| {
-                matches!(event_of(line).as_deref(), Some("started") | Some("finished"))
-            });
-            let last_finished = match newest_attempt {
-                Some(line) if event_of(line).as_deref() == Some("started") && !running => {
-                    Some(serde_json::json!({
-                        "runId": line.get("runId").cloned().unwrap_or_default(),
-                        "ts": line.get("ts").cloned().unwrap_or_default(),
-                        "success": false,
-                        "error": "The run was interrupted before it could finish (crash, forced shutdown, or power loss).",
-                        "durationMs": 0,
-                        "interrupted": true,
-                    }))
-                }
-                _ => lines
-                    .iter()
-                    .find(|line| event_of(line).as_deref() == Some("finished"))
-                    .cloned(),
-            };
-            statuses.push(TaskStatus {
-                task_id: spec.task_id.clone(),
-                installed,
-                enabled,
-                running,
-                last_finished,
-                warning,
-            });
-        }
-        Ok(statuses)
+        Ok(jobfile::list(&dirs, &host_id)
+            .iter()
+            .map(|spec| task_status(&dirs, spec))
+            .collect())
     })
     .await
     .map_err(|e| e.to_string())?
 }
 
+/// Installed/enabled/running state plus the latest attempt's outcome — shared by
+/// `scheduler_status` and the headless `list-tasks`/`task-status` subcommands.
+pub fn task_status(dirs: &AppDirs, spec: &JobSpec) -> TaskStatus {
+    // Per-task backend: a macOS user-mode task's state lives in launchd, a system-mode
+    // task's in crontab.
+    let backend = backend_for(dirs, spec.is_user_mode());
+    let install_state = backend
+        .as_ref()
+        .ok()
+        .map(|backend| backend.is_installed(&spec.task_id))
+        .and_then(Result::ok)
+        .unwrap_or(InstallState::NotInstalled);
+    let (installed, enabled) = match install_state {
+        InstallState::NotInstalled => (false, false),
+        InstallState::Installed { enabled } => (true, enabled),
+    };
+    let warning = backend
+        .as_ref()
+        .ok()
+        .and_then(|backend| backend.health_warning(&spec.task_id));
+
+    let running = history::is_running(dirs, &spec.task_id);
+    let lines = history::read(dirs, &spec.task_id, 20);
+    let event_of = |line: &serde_json::Value| {
+        line.get("event")
+            .and_then(|e| e.as_str())
+            .map(str::to_owned)
+    };
+    // Newest-first: the latest started/finished event is the latest ATTEMPT. A started
+    // with no finished and no live lock is a run that died without writing its terminal
+    // record (crash, SIGKILL, power loss, Task Scheduler hard timeout) — surfacing the
+    // older success (or "Never") instead would hide the interruption.
+    let newest_attempt = lines.iter().find(|line| {
+        matches!(
+            event_of(line).as_deref(),
+            Some("started") | Some("finished")
+        )
+    });
+    let last_finished = match newest_attempt {
+        Some(line) if event_of(line).as_deref() == Some("started") && !running => {
+            Some(serde_json::json!({
+                "runId": line.get("runId").cloned().unwrap_or_default(),
+                "ts": line.get("ts").cloned().unwrap_or_default(),
+                "success": false,
+                "error": "The run was interrupted before it could finish (crash, forced shutdown, or power loss).",
+                "durationMs": 0,
+                "interrupted": true,
+            }))
+        }
+        _ => lines
+            .iter()
+            .find(|line| event_of(line).as_deref() == Some("finished"))
+            .cloned(),
+    };
+    TaskStatus {
+        task_id: spec.task_id.clone(),
+        installed,
+        enabled,
+        running,
+        intended_enabled: spec.enabled,
+        last_finished,
+        warning,
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LogContent {
@@ -604,8 +726,8 @@ pub struct LogContent {
     pub truncated: bool,
 }
 
-/// Tail of a task's log for the in-app viewer. `which`: "runner" (our runner's lines) or
-/// "daemon" (the transient rclone daemon's stderr).
+/// Tail of a task's log for the in-app viewer. `which`: "runner" (our runner's lines, rendered
+/// as text) or "daemon" (the transient rclone daemon's stderr).
 #[tauri::command]
 pub fn scheduler_read_log(
     app: AppHandle,
@@ -618,7 +740,7 @@ pub fn scheduler_read_log(
     let task_id = sanitize_id(&task_id)?;
     let path = match which.as_str() {
         "runner" => history::log_path(&dirs, &task_id),
-        "daemon" => history::log_path(&dirs, &task_id).with_extension("daemon.log"),
+        "daemon" => history::daemon_log_path(&dirs, &task_id),
         other => return Err(format!("unknown log '{}'", other)),
     };
 
@@ -643,10 +765,41 @@ pub fn scheduler_read_log(
         &bytes[..]
     };
 
-    Ok(LogContent {
-        content: String::from_utf8_lossy(tail).into_owned(),
-        truncated,
+    let content = String::from_utf8_lossy(tail).into_owned();
+    let content = if which == "runner" {
+        content
+            .lines()
+            .map(|line| {
+                history::LogRecord::parse(line)
+                    .map(|record| record.render())
+                    .unwrap_or_else(|| line.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        content
+    };
+    Ok(LogContent { content, truncated })
+}
+
+/// The runner log lines of one run (the `runId` of its history records), archives included.
+#[tauri::command]
+pub async fn scheduler_read_run_log(
+    app: AppHandle,
+    task_id: String,
+    run_id: String,
+) -> Result<Vec<history::LogRecord>, String> {
+    // spawn_blocking: may decompress every archive of the log.
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        let task_id = sanitize_id(&task_id)?;
+        Ok(history::read_run(
+            &history::log_path(&dirs, &task_id),
+            &run_id,
+        ))
     })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
@@ -664,6 +817,129 @@ pub async fn scheduler_read_history(
     .map_err(|e| e.to_string())?
 }
 
+/// Writes the tasks behind `definitions` (the GUI's local host store entries) to a portable
+/// bundle at `path` (see bundle.rs).
+#[tauri::command]
+pub async fn scheduler_export(
+    app: AppHandle,
+    path: String,
+    definitions: Vec<serde_json::Value>,
+) -> Result<bundle::ExportReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        let (bundle, skipped) = bundle::build(&dirs, definitions);
+        bundle::write(Path::new(&path), &bundle)?;
+        Ok(bundle::ExportReport {
+            tasks: bundle.tasks.len() as u32,
+            notification_targets: bundle.notification_targets.len() as u32,
+            skipped,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Registers the tasks of the bundle at `path`, adapted to this machine. `fallback_config_id`
+/// is the config for tasks whose own can't be matched here; `known_task_ids` are the ids in the
+/// GUI's store. The GUI adds each imported task's returned definition to its store.
+#[tauri::command]
+pub async fn scheduler_import(
+    app: AppHandle,
+    path: String,
+    fallback_config_id: Option<String>,
+    known_task_ids: Vec<String>,
+) -> Result<bundle::ImportReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        let bundle = bundle::read(Path::new(&path))?;
+        let host = storeread::read_host(&dirs, "local")?;
+        let context = bundle::ImportContext {
+            configs: host
+                .config_files
+                .into_iter()
+                .filter_map(|config| Some((config.id?, config.label)))
+                .collect(),
+            fallback_config: fallback_config_id,
+            taken_ids: known_task_ids.into_iter().collect(),
+        };
+        Ok(bundle::import(&dirs, bundle, context))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Writes a standalone equivalent of the task into `dir` — kind "script" (a shell script) or
+/// "systemd" (service, timer and env file); see artifact.rs. Returns the written paths.
+#[tauri::command]
+pub async fn scheduler_export_artifact(
+    app: AppHandle,
+    task_id: String,
+    kind: String,
+    dir: String,
+) -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        let task_id = sanitize_id(&task_id)?;
+        let spec = jobfile::load(&dirs, "local", &task_id)?;
+        let host = storeread::read_host(&dirs, &spec.host_id)?;
+        let config = storeread::find_config(&host, &spec.config_id);
+        let config = artifact::ConfigInfo {
+            path: storeread::resolve_config_path(&dirs, &host, &spec.config_id)
+                .to_string_lossy()
+                .into_owned(),
+            encrypted: config.is_some_and(|c| c.is_encrypted),
+            pass_command: config
+                .and_then(|c| c.pass_command.clone())
+                .filter(|command| !command.is_empty()),
+        };
+        artifact::write(Path::new(&dir), &spec, &kind, &config)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// rclone lines in the user's crontab that could become managed tasks (see adopt.rs).
+#[tauri::command]
+pub async fn scheduler_scan_crontab() -> Result<adopt::Scan, String> {
+    #[cfg(unix)]
+    {
+        // spawn_blocking: shells out to `crontab -l`.
+        tauri::async_runtime::spawn_blocking(|| {
+            let content = crontab::read_user_crontab()?;
+            Ok(adopt::scan(&content, |path| Path::new(path).is_file()))
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+    #[cfg(not(unix))]
+    {
+        Err("There is no crontab on this system".to_string())
+    }
+}
+
+/// Comments out crontab lines whose tasks were adopted. Returns how many were found.
+#[tauri::command]
+pub async fn scheduler_retire_crontab_lines(
+    app: AppHandle,
+    lines: Vec<String>,
+) -> Result<u32, String> {
+    #[cfg(unix)]
+    {
+        tauri::async_runtime::spawn_blocking(move || {
+            let dirs = storeread::app_dirs_from(&app)?;
+            let _guard = mutation_guard();
+            crontab::retire_lines(&dirs, &lines)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (app, lines);
+        Err("There is no crontab on this system".to_string())
+    }
+}
+
 /// Remove every registration this app ever made (Settings escape hatch / pre-uninstall cleanup).
 /// Sweeps both job files and orphaned OS artifacts by prefix.
 #[tauri::command]
@@ -697,6 +973,50 @@ pub async fn scheduler_unregister_all(app: AppHandle) -> Result<u32, String> {
     .map_err(|e| e.to_string())?
 }
 
+/// Job files whose OS artifact is missing or differs from what registering the task would write
+/// today (see drift.rs). `enabled` is the GUI's intended state per task id.
+#[tauri::command]
+pub async fn scheduler_reconcile_report(
+    app: AppHandle,
+    enabled: HashMap<String, bool>,
+) -> Result<drift::DriftReport, String> {
+    // spawn_blocking: reads crontab/schtasks once per task.
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        Ok(drift::report(&dirs, &enabled))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Re-registers every task `scheduler_reconcile_report` would list.
+#[tauri::command]
+pub async fn scheduler_reconcile_apply(
+    app: AppHandle,
+    enabled: HashMap<String, bool>,
+) -> Result<drift::RepairReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        let _guard = mutation_guard();
+        Ok(drift::repair(&dirs, &enabled))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Dry-runs every task's setup — binary, config, secrets, session, daemon, remotes — and the
+/// notification targets its runs report to (see doctor.rs). Transfers nothing.
+#[tauri::command]
+pub async fn scheduler_doctor(app: AppHandle) -> Result<doctor::DoctorReport, String> {
+    // spawn_blocking: starts one transient daemon per task, one after another.
+    tauri::async_runtime::spawn_blocking(move || {
+        let dirs = storeread::app_dirs_from(&app)?;
+        doctor::run(&dirs)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 /// Task ids that still have a job file — by FILENAME, deliberately not by parse: an unreadable
 /// or newer-schema job file is an environment problem, and sweeping its artifact would destroy
 /// a valid registration (same conservatism as the runner's self-heal).
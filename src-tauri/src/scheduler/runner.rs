@@ -1,24 +1,29 @@
 //! Headless `run-task` engine: executes one scheduled task end-to-end without any GUI.
 //!
-//! Spawns a transient, private rclone daemon (task's binary + config, ephemeral localhost port,
-//! random credentials), POSTs the pre-serialized RC requests from the job file, polls to
-//! terminal state, records history, and dispatches the schedule.* webhooks.
+//! Spawns a transient, private rclone daemon (task's binary + config, a Unix socket in a private
+//! per-run directory — an ephemeral localhost port on Windows — and random credentials), POSTs
+//! the pre-serialized RC requests from the job file, polls to terminal state (recovering
+//! bisync's resync and stale-lock failures, see bisync.rs), records history (with a failure
+//! summary parsed from the daemon log), and dispatches the schedule.* webhooks.
 //!
 //! Exit codes: 0 success · 1 run failed · 2 setup error · 3 skipped (already running).
 
+use std::collections::{HashMap, VecDeque};
 use std::process::{Child, Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use serde_json::{json, Value};
 
-use super::history::{self, HistoryLine, RunLog};
+use super::history::{self, HistoryLine, Level, Phase, RunLog};
 use super::jobfile::{self, JobSpec};
 use super::storeread::{self, AppDirs};
-use crate::notifications::{os, webhooks};
+use super::{bisync, daemonlog};
+use crate::notifications::{digest, os, outbox, settings, webhooks};
 
 const READINESS_TIMEOUT: Duration = Duration::from_secs(15);
 const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const RC_TIMEOUT: Duration = Duration::from_secs(15);
 
 static TERMINATED: AtomicBool = AtomicBool::new(false);
 
@@ -71,8 +76,18 @@ pub fn run(
         },
     };
 
+    // Tags every log line of this invocation — history records carry the same id.
+    let run_id = format!(
+        "{}-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis(),
+        std::process::id()
+    );
+    let loaded = jobfile::load(&dirs, &host_id, &task_id);
     let mut log = RunLog::open(&dirs, &task_id);
-    log.line(&format!("run-task {} (host {})", task_id, host_id));
+    log.set_run_id(&run_id);
 
     // No Flatpak guard here: when running under Flatpak the runner is a fresh sandboxed instance
     // launched by host cron via `flatpak run … run-task`; it drives rclone in-sandbox and never
@@ -86,18 +101,21 @@ pub fn run(
     // data root (unmounted systemd-homed home, wrong XDG-derived path from an old trigger) or a
     // malformed/newer-schema job file is an ENVIRONMENT problem — uninstalling there would
     // destroy a valid registration.
-    let spec = match jobfile::load(&dirs, &host_id, &task_id) {
+    let spec = match loaded {
         Ok(spec) => spec,
         Err(e) => {
             let job_path = jobfile::job_path(&dirs, &host_id, &task_id);
             let jobs_dir_present = job_path.parent().map(|p| p.is_dir()).unwrap_or(false);
             if jobs_dir_present && !job_path.exists() {
-                log.line(&format!("job file missing: {} — removing orphan trigger", e));
+                log.warn(&format!(
+                    "job file missing: {} — removing orphan trigger",
+                    e
+                ));
                 for backend in super::all_backends(&dirs) {
                     let _ = backend.uninstall(&task_id);
                 }
             } else {
-                log.line(&format!(
+                log.error(&format!(
                     "job file unusable: {} — leaving the trigger in place (environment problem, not an orphan)",
                     e
                 ));
@@ -106,7 +124,7 @@ pub fn run(
         }
     };
     if spec.host_id != "local" {
-        log.line("remote-host tasks are not supported by the scheduler");
+        log.error("remote-host tasks are not supported by the scheduler");
         return 2;
     }
 
@@ -145,9 +163,6 @@ pub fn run(
                 );
                 return 3;
             }
-            if let Some(runtime_dir) = session_runtime_dir() {
-                borrow_session_env(&runtime_dir, &mut log);
-            }
         }
     }
 
@@ -167,24 +182,29 @@ pub fn run(
             return 3;
         }
         Err(e) => {
-            log.line(&format!("lock error: {}", e));
+            log.error(&format!("lock error: {}", e));
             return 2;
         }
     };
+    // Only the lock holder rotates: a run skipped as already-running must not rename the log
+    // out from under the live one. Lines before this point only ever end a skipped invocation.
+    log.rotate(spec.log_retention);
+    log.line(&format!("run-task {} (host {})", task_id, host_id));
+    #[cfg(target_os = "linux")]
+    {
+        if spec.is_user_mode() {
+            if let Some(runtime_dir) = session_runtime_dir() {
+                borrow_session_env(&runtime_dir, &mut log);
+            }
+        }
+    }
 
     install_sigterm_handler();
 
-    let run_id = format!(
-        "{}-{}",
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis(),
-        std::process::id()
-    );
     let started_at = Instant::now();
-    // The user-facing "max run time" covers the WHOLE run — started-webhook delivery (up to
-    // ~17s per target, sequential) and daemon readiness included — not just the rclone jobs.
+    // The user-facing "max run time" covers the WHOLE run — started-webhook delivery (up to the
+    // notification time budget, all targets at once) and daemon readiness included — not just
+    // the rclone jobs.
     let deadline = started_at + Duration::from_secs(spec.max_run_seconds);
     history::append(
         &dirs,
@@ -198,6 +218,7 @@ pub fn run(
     );
 
     let client = webhooks::http_client();
+    let budget = settings::load(&dirs).dispatch_budget();
     let root = storeread::read_root(&dirs).unwrap_or_default();
     let task_label = if spec.name.is_empty() {
         spec.operation.clone()
@@ -211,15 +232,80 @@ pub fn run(
         "schedule.started",
         "Scheduled task started",
         &format!("{} started", task_label),
-        json!({ "scheduleId": task_id, "operation": spec.operation, "cron": spec.cron }),
+        json!({
+            "scheduleId": task_id,
+            "operation": spec.operation,
+            "cron": spec.cron,
+            "remotes": spec.remotes(),
+        }),
+        // Never more than the run has left, short of the floor — whatever is still sending then
+        // finishes in the background or is retried via the outbox.
+        budget
+            .min(deadline.saturating_duration_since(Instant::now()))
+            .max(Duration::from_secs(settings::MIN_DISPATCH_BUDGET_SECONDS)),
     ) {
-        log.line(&line);
+        log.record(Phase::Webhook, Level::Warn, &line);
     }
 
-    let mut outcome = execute(&dirs, &spec, &task_id, &root, &client, deadline, &mut log);
+    // Verbose (INFO) logging grows fast — rotate the daemon log independently of the runner log.
+    // Rotated here, before the run, so this run's segment starts at a known offset.
+    let daemon_log_path = history::daemon_log_path(&dirs, &task_id);
+    history::rotate_file(&daemon_log_path, 4 * 1024 * 1024, spec.log_retention);
+    let daemon_log_start = std::fs::metadata(&daemon_log_path)
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let mut outcome = execute(
+        &dirs,
+        &spec,
+        &task_id,
+        &root,
+        deadline,
+        (&daemon_log_path, daemon_log_start),
+        &mut log,
+    );
+    log.set_phase(Phase::Request);
+    if spec.requests.iter().any(|r| r.endpoint == bisync::ENDPOINT) {
+        let conflicts = bisync::count_conflicts(&daemon_log_path, daemon_log_start);
+        if conflicts > 0 {
+            log.line(&format!(
+                "bisync resolved {} conflict{} (both versions kept)",
+                conflicts,
+                if conflicts == 1 { "" } else { "s" }
+            ));
+        }
+        if let Some(stats) = outcome
+            .stats
+            .get_or_insert_with(|| json!({}))
+            .as_object_mut()
+        {
+            stats.insert("conflicts".to_string(), json!(conflicts));
+        }
+    }
     if let Some(error) = outcome.error.take() {
         outcome.error = Some(annotate_session_failure(error, &spec));
     }
+    let failure_summary = outcome
+        .error
+        .as_ref()
+        .and_then(|_| daemonlog::summarize(&daemon_log_path, daemon_log_start));
+    if let Some(summary) = &failure_summary {
+        log.error(&format!("daemon log: {}", summary.describe()));
+    }
+    let reauth = outcome.error.as_deref().and_then(|error| {
+        detect_reauth(
+            error,
+            failure_summary.as_ref(),
+            &spec,
+            &oauth_remotes(&dirs, &spec),
+        )
+    });
+    if let Some(reauth) = &reauth {
+        log.error(&format!(
+            "remote sign-in expired or revoked ({}) — reconnect it in Rclone UI",
+            reauth.remote.as_deref().unwrap_or("remote unknown")
+        ));
+    }
 
     let duration_ms = started_at.elapsed().as_millis() as u64;
     history::append(
@@ -237,12 +323,15 @@ pub fn run(
                 Some(outcome.jobids.clone())
             },
             stats: outcome.stats.clone(),
+            failure_summary: failure_summary.clone(),
+            needs_reauth: reauth.is_some(),
+            reauth_remote: reauth.as_ref().and_then(|r| r.remote.clone()),
         },
     );
 
     // Release BEFORE the completion webhooks: the run's work is done, and holding the lock
-    // through up-to-minutes of sequential webhook delivery would make the next scheduled fire
-    // skip as "already-running".
+    // through webhook delivery (up to the notification time budget per event) would make the next
+    // scheduled fire skip as "already-running".
     run_lock.release();
 
     let (event, title, body) = match &outcome.error {
@@ -257,25 +346,89 @@ pub fn run(
             format!("{} failed: {}", task_label, error),
         ),
     };
-    let mut data = json!({ "scheduleId": task_id, "operation": spec.operation, "durationMs": duration_ms });
+    let mut data = json!({
+        "scheduleId": task_id,
+        "task": spec.name,
+        "operation": spec.operation,
+        "remotes": spec.remotes(),
+        "durationMs": duration_ms,
+    });
+    if let Some(bytes) = outcome.stats.as_ref().and_then(|stats| stats.get("bytes")) {
+        data["bytes"] = bytes.clone();
+    }
     if let Some(error) = &outcome.error {
         data["error"] = Value::String(error.clone());
     }
-    for line in webhooks::dispatch(&dirs, &client, event, title, &body, data) {
-        log.line(&line);
+    if let Some(summary) = &failure_summary {
+        data["failureSummary"] = serde_json::to_value(summary).unwrap_or(Value::Null);
+    }
+    if let Some(reauth) = &reauth {
+        data["needsReauth"] = Value::Bool(true);
+        if let Some(remote) = &reauth.remote {
+            data["remote"] = Value::String(remote.clone());
+        }
+    }
+    for line in webhooks::dispatch(&dirs, &client, event, title, &body, data.clone(), budget) {
+        log.record(Phase::Webhook, Level::Warn, &line);
+    }
+    // Sent IN ADDITION to schedule.failed: targets subscribed only to failures still hear about
+    // it, and the dedicated event lets a target route "someone must sign in" differently.
+    if let Some(reauth) = &reauth {
+        let reauth_body = match &reauth.remote {
+            Some(remote) => format!(
+                "{} needs you to reconnect the remote \"{}\" — its sign-in expired or was revoked",
+                task_label, remote
+            ),
+            None => format!(
+                "{} needs a remote to be reconnected — its sign-in expired or was revoked",
+                task_label
+            ),
+        };
+        for line in webhooks::dispatch(
+            &dirs,
+            &client,
+            "schedule.reauth-required",
+            "Scheduled task needs sign-in",
+            &reauth_body,
+            data,
+            budget,
+        ) {
+            log.record(Phase::Webhook, Level::Warn, &line);
+        }
     }
 
     // OS toast for the terminal state — hardcoded to completed/failed (started would be noise).
     // Scheduled runs happen with the GUI possibly closed, so the runner must post it itself.
     if let Err(e) = os::notify_headless(title, &body) {
-        log.line(&format!("os notification failed: {}", e));
+        log.record(
+            Phase::Webhook,
+            Level::Warn,
+            &format!("os notification failed: {}", e),
+        );
     }
 
-    log.line(&format!(
-        "finished: {} ({} ms)",
-        outcome.error.as_deref().unwrap_or("success"),
-        duration_ms
-    ));
+    // Release due digests and follow-ups into the outbox, then send whatever is due there —
+    // this run's failed notifications included, once due — so they go out even when the GUI is
+    // never opened.
+    let mut flushed = digest::flush(&dirs);
+    flushed.extend(outbox::flush(&dirs, &client, budget));
+    for line in flushed {
+        log.record(Phase::Webhook, Level::Warn, &line);
+    }
+
+    log.record(
+        Phase::Setup,
+        if outcome.error.is_some() {
+            Level::Error
+        } else {
+            Level::Info
+        },
+        &format!(
+            "finished: {} ({} ms)",
+            outcome.error.as_deref().unwrap_or("success"),
+            duration_ms
+        ),
+    );
 
     if outcome.setup_failure {
         2
@@ -443,6 +596,71 @@ fn annotate_session_failure(error: String, spec: &JobSpec) -> String {
     )
 }
 
+/// A failed run caused by an expired or revoked OAuth token.
+struct ReauthNeeded {
+    remote: Option<String>,
+}
+
+/// Recognizes token/auth failures from the RC error (or, when the job error is just a count, the
+/// daemon log's classified lines) and names the remote to reconnect. The remote comes from
+/// rclone's own hint (`rclone config reconnect gdrive:`), then the fs named in a
+/// "Failed to create file system for" error, then — when the task touches exactly one
+/// configured remote — that remote. A bare 401 only counts when that remote is one of
+/// `oauth_remotes`: on S3 or WebDAV it means wrong keys, which reconnecting can't fix.
+fn detect_reauth(
+    error: &str,
+    summary: Option<&daemonlog::FailureSummary>,
+    spec: &JobSpec,
+    oauth_remotes: &[String],
+) -> Option<ReauthNeeded> {
+    let is_auth = |kind: &str| kind == "authExpired" || kind == "unauthorized";
+    let from_summary = |kind: &str| {
+        summary
+            .map(|s| s.categories.iter().any(|c| c.kind == kind))
+            .unwrap_or(false)
+    };
+    let error_kind = daemonlog::classify(error);
+    let expired = error_kind == "authExpired" || from_summary("authExpired");
+    if !expired && error_kind != "unauthorized" && !from_summary("unauthorized") {
+        return None;
+    }
+    // Examples from the summary carry the same hints as the top-level error.
+    let mut texts = vec![error.to_string()];
+    if let Some(summary) = summary {
+        for category in summary.categories.iter().filter(|c| is_auth(&c.kind)) {
+            texts.extend(category.examples.iter().cloned());
+        }
+    }
+    let hinted = texts.iter().find_map(|text| {
+        let hint = text.split("config reconnect ").nth(1)?;
+        jobfile::remote_name(hint.trim_start_matches(['"', '\'']))
+    });
+    let named = || {
+        texts.iter().find_map(|text| {
+            let fs = text.split("file system for \"").nth(1)?.split('"').next()?;
+            jobfile::remote_name(fs)
+        })
+    };
+    let only = || {
+        let mut remotes = spec.remotes();
+        (remotes.len() == 1).then(|| remotes.remove(0))
+    };
+    let remote = hinted.or_else(named).or_else(only);
+    if !expired && !remote.as_ref().is_some_and(|r| oauth_remotes.contains(r)) {
+        return None;
+    }
+    Some(ReauthNeeded { remote })
+}
+
+/// The remotes in the task's config that sign in with OAuth; empty when it can't be read.
+fn oauth_remotes(dirs: &AppDirs, spec: &JobSpec) -> Vec<String> {
+    storeread::read_host(dirs, &spec.host_id)
+        .map(|host| storeread::resolve_config_path(dirs, &host, &spec.config_id))
+        .and_then(|path| std::fs::read_to_string(path).map_err(|e| e.to_string()))
+        .map(|config| storeread::oauth_remotes(&config))
+        .unwrap_or_default()
+}
+
 struct RunOutcome {
     error: Option<String>,
     setup_failure: bool,
@@ -461,105 +679,192 @@ impl RunOutcome {
     }
 }
 
+/// `daemon_log` is the daemon log and where this run's segment of it starts, as run() recorded.
 fn execute(
     dirs: &AppDirs,
     spec: &JobSpec,
     task_id: &str,
     root: &storeread::RootState,
-    client: &reqwest::Client,
     deadline: Instant,
+    daemon_log: (&std::path::Path, u64),
     log: &mut RunLog,
 ) -> RunOutcome {
-    // Binary resolution.
+    let binary = match resolve_binary(spec, root) {
+        Ok(b) => b,
+        Err(e) => return RunOutcome::setup(e),
+    };
+    let mut env = match config_env(dirs, spec) {
+        Ok(env) => env,
+        Err(e) => return RunOutcome::setup(e),
+    };
+    if let Err(e) = add_secrets(dirs, spec, &mut env) {
+        return RunOutcome::setup(e);
+    }
+    if !spec.env.is_empty() || !spec.secret_env.is_empty() {
+        log.line(&format!(
+            "task environment: {}",
+            jobfile::describe_env(spec)
+        ));
+    }
+    if !spec.daemon_flags.is_empty() {
+        log.line(&format!(
+            "extra daemon flags: {}",
+            jobfile::describe_flags(spec)
+        ));
+    }
+
+    // Appended to, never truncated: the segment's start is the same offset bisync recovery
+    // reads its verdict from.
+    let daemon_log_file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(daemon_log.0)
+        .ok();
+    let stderr = match daemon_log_file {
+        Some(file) => Stdio::from(file),
+        None => Stdio::null(),
+    };
+
+    log.set_phase(Phase::Daemon);
+    let mut daemon = match spawn_daemon(&binary, &env, spec, stderr, log) {
+        Ok(d) => d,
+        Err(e) => return RunOutcome::setup(e),
+    };
+    history::record_daemon_pid(dirs, task_id, daemon.child.id());
+    if let Err(e) = await_ready(&mut daemon, " — see the task's daemon log") {
+        return RunOutcome::setup(e);
+    }
+    let rc = daemon.rc.clone();
+    log.set_phase(Phase::Request);
+
+    // The daemon guard drops (quit, then kill) on return, whatever the outcome.
+    run_requests(
+        dirs,
+        spec,
+        &rc,
+        &mut daemon.child,
+        deadline,
+        daemon_log,
+        log,
+    )
+}
+
+/// The rclone binary a run starts: the app's configured one for "app-default".
+pub(super) fn resolve_binary(
+    spec: &JobSpec,
+    root: &storeread::RootState,
+) -> Result<String, String> {
     let binary = if spec.binary == "app-default" {
         match root.rclone_path.as_deref().filter(|p| !p.is_empty()) {
             Some(p) => p.to_string(),
-            None => return RunOutcome::setup("no rclone binary configured — open Rclone UI to set one up".to_string()),
+            None => {
+                return Err("no rclone binary configured — open Rclone UI to set one up".to_string())
+            }
         }
     } else {
         spec.binary.clone()
     };
     if !std::path::Path::new(&binary).is_file() {
-        return RunOutcome::setup(format!(
+        return Err(format!(
             "rclone binary not found at {} — open Rclone UI to repair the schedule",
             binary
         ));
     }
+    Ok(binary)
+}
 
-    // Config + env.
-    let host = match storeread::read_host(dirs, &spec.host_id) {
-        Ok(h) => h,
-        Err(e) => return RunOutcome::setup(e),
-    };
+/// The daemon's environment: the config file and how to decrypt it, then the task's own
+/// overrides (validated at registration, so they never shadow the former). Secrets come after.
+pub(super) fn config_env(
+    dirs: &AppDirs,
+    spec: &JobSpec,
+) -> Result<HashMap<String, String>, String> {
+    let host = storeread::read_host(dirs, &spec.host_id)?;
     let config_path = storeread::resolve_config_path(dirs, &host, &spec.config_id);
     if !config_path.is_file() {
-        return RunOutcome::setup(format!(
+        return Err(format!(
             "config file not found at {} — open Rclone UI to repair the schedule",
             config_path.display()
         ));
     }
     let config_entry = storeread::find_config(&host, &spec.config_id);
-    let env = match storeread::build_run_env(&host, config_entry, &config_path) {
-        Ok(env) => env,
-        Err(e) => return RunOutcome::setup(e),
-    };
+    let mut env = storeread::build_run_env(&host, config_entry, &config_path)?;
+    env.extend(spec.env.clone());
+    Ok(env)
+}
 
-    // Transient daemon.
-    let port = match pick_port() {
-        Ok(p) => p,
-        Err(e) => return RunOutcome::setup(e),
-    };
-    let user = random_token("user");
-    let pass = random_token("pass");
-    let base = format!("http://127.0.0.1:{}", port);
+/// Adds the stored value of every secret the task declares.
+pub(super) fn add_secrets(
+    dirs: &AppDirs,
+    spec: &JobSpec,
+    env: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    let secrets = jobfile::load_secrets(dirs, &spec.host_id, &spec.task_id);
+    for name in &spec.secret_env {
+        // Empty is an imported task's placeholder, not a value.
+        match secrets.get(name).filter(|value| !value.is_empty()) {
+            Some(value) => {
+                env.insert(name.clone(), value.clone());
+            }
+            None => {
+                return Err(format!(
+                    "no value is stored for the secret {} — open Rclone UI and enter it in the schedule's settings",
+                    name
+                ))
+            }
+        }
+    }
+    Ok(())
+}
 
-    log.line(&format!("starting transient daemon: {} (port {})", binary, port));
+/// Starts the transient `rclone rcd` on a fresh private listener, its stderr going to `stderr`.
+/// Not yet ready to take calls — see `await_ready`.
+pub(super) fn spawn_daemon(
+    binary: &str,
+    env: &HashMap<String, String>,
+    spec: &JobSpec,
+    stderr: Stdio,
+    log: &mut RunLog,
+) -> Result<DaemonGuard, String> {
+    let listener = rc_listener()?;
+    let rc = listener.target.clone();
 
-    let daemon_log_path = history::log_path(dirs, task_id).with_extension("daemon.log");
-    // Verbose (INFO) logging grows fast — rotate the daemon log independently of the runner log.
-    history::rotate_file(&daemon_log_path, 4 * 1024 * 1024);
-    let daemon_log = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&daemon_log_path)
-        .ok();
+    log.line(&format!(
+        "starting transient daemon: {} ({})",
+        binary, listener.description
+    ));
 
-    let rc_addr = format!("127.0.0.1:{}", port);
     let mut daemon_args = vec![
         "rcd",
         "--rc-addr",
-        &rc_addr,
+        &listener.addr,
         "--rc-user",
-        &user,
+        &rc.user,
         "--rc-pass",
-        &pass,
+        &rc.pass,
     ];
     if spec.verbose_logging {
         daemon_args.extend(["--log-level", "INFO"]);
     }
+    daemon_args.extend(spec.daemon_flags.iter().map(String::as_str));
 
-    let mut cmd = Command::new(&binary);
+    let mut cmd = Command::new(binary);
     cmd.args(&daemon_args);
-    for (k, v) in &env {
+    for (k, v) in env {
         cmd.env(k, v);
     }
     cmd.stdin(Stdio::null());
     cmd.stdout(Stdio::null());
-    cmd.stderr(match daemon_log {
-        Some(file) => Stdio::from(file),
-        None => Stdio::null(),
-    });
+    cmd.stderr(stderr);
     #[cfg(windows)]
     {
         use std::os::windows::process::CommandExt;
         cmd.creation_flags(0x0800_0000); // CREATE_NO_WINDOW
     }
 
-    let child = match cmd.spawn() {
-        Ok(c) => c,
-        Err(e) => return RunOutcome::setup(format!("failed to start rclone: {}", e)),
-    };
-    history::record_daemon_pid(dirs, task_id, child.id());
+    let child = cmd
+        .spawn()
+        .map_err(|e| format!("failed to start rclone: {}", e))?;
 
     // Tie the daemon's lifetime to this process: Task Scheduler's hard kill (TerminateProcess)
     // runs no destructors, so without the job object a hung, hard-killed runner orphans it.
@@ -567,7 +872,7 @@ fn execute(
     let job = match super::winjob::KillOnCloseJob::assign(&child) {
         Ok(job) => Some(job),
         Err(e) => {
-            log.line(&format!(
+            log.warn(&format!(
                 "job object unavailable ({}) — a hard-killed runner would orphan the daemon until the next run's cleanup",
                 e
             ));
@@ -575,96 +880,133 @@ fn execute(
         }
     };
 
-    let mut daemon = DaemonGuard {
+    Ok(DaemonGuard {
         child,
-        client: client.clone(),
-        quit_url: format!("{}/core/quit", base),
-        user: user.clone(),
-        pass: pass.clone(),
+        rc,
         cleaned: false,
+        _run_dir: listener.run_dir,
         #[cfg(windows)]
         _job: job,
-    };
+    })
+}
 
-    // Readiness.
+/// Waits for the daemon's API to answer; errors when it exits (`see_log` says where its stderr
+/// went) or stays silent past the timeout.
+pub(super) fn await_ready(daemon: &mut DaemonGuard, see_log: &str) -> Result<(), String> {
     let ready_deadline = Instant::now() + READINESS_TIMEOUT;
     loop {
         if let Ok(Some(status)) = daemon.child.try_wait() {
-            return RunOutcome::setup(format!(
-                "rclone daemon exited during startup (code {:?}) — see the task's daemon log",
-                status.code()
+            #[cfg(unix)]
+            let hint = " (rclone versions older than 1.63 can't serve their API on a Unix socket)";
+            #[cfg(not(unix))]
+            let hint = "";
+            return Err(format!(
+                "rclone daemon exited during startup (code {:?}){}{}",
+                status.code(),
+                see_log,
+                hint
             ));
         }
-        let ready = rc_call(client, &base, &user, &pass, "/rc/noop", &json!({})).is_ok();
-        if ready {
-            break;
+        if rc_call(&daemon.rc, "/rc/noop", &json!({})).is_ok() {
+            return Ok(());
         }
         if Instant::now() >= ready_deadline {
-            return RunOutcome::setup("rclone daemon did not become ready within 15s".to_string());
+            return Err("rclone daemon did not become ready within 15s".to_string());
         }
         std::thread::sleep(Duration::from_millis(250));
     }
+}
 
-    // Execute the stored requests sequentially (deadline covers the whole run — set in run()).
+/// Executes the stored requests on the ready daemon, up to `max_parallel` at a time (deadline
+/// covers the whole run — set in run()). Stats are summed across jobs; errors from several
+/// requests are combined into one.
+fn run_requests(
+    dirs: &AppDirs,
+    spec: &JobSpec,
+    rc: &RcTarget,
+    daemon: &mut Child,
+    deadline: Instant,
+    daemon_log: (&std::path::Path, u64),
+    log: &mut RunLog,
+) -> RunOutcome {
+    let max_parallel = spec.max_parallel.max(1) as usize;
+    let mut pending: VecDeque<Attempt> = spec
+        .requests
+        .iter()
+        .enumerate()
+        .map(|(index, request)| Attempt {
+            index,
+            body: request.body.clone(),
+            lock_cleared: false,
+            resynced: false,
+        })
+        .collect();
+    let mut running: Vec<(Attempt, i64)> = Vec::new();
     let mut jobids: Vec<i64> = Vec::new();
     let mut stats: Option<Value> = None;
+    let mut errors: Vec<String> = Vec::new();
+    // Set by the first failure unless the task continues on error: nothing new is submitted and
+    // the jobs still running are stopped.
+    let mut stopping = false;
 
-    for request in &spec.requests {
-        let submitted = match rc_call(client, &base, &user, &pass, &request.endpoint, &request.body)
-        {
-            Ok(v) => v,
-            Err(e) => {
-                return RunOutcome {
-                    error: Some(format!("failed to submit {}: {}", request.endpoint, e)),
-                    setup_failure: false,
-                    jobids,
-                    stats,
+    loop {
+        while !stopping && running.len() < max_parallel {
+            let Some(attempt) = pending.pop_front() else {
+                break;
+            };
+            let endpoint = &spec.requests[attempt.index].endpoint;
+            match submit(rc, endpoint, &attempt.body) {
+                Ok(jobid) => {
+                    jobids.push(jobid);
+                    log.line(&format!("submitted {} as job {}", endpoint, jobid));
+                    running.push((attempt, jobid));
+                }
+                Err(e) => {
+                    errors.push(e);
+                    stopping = !spec.continue_on_error;
                 }
             }
-        };
-        let Some(jobid) = submitted.get("jobid").and_then(|j| j.as_i64()) else {
+        }
+        if stopping {
+            stop_jobs(rc, &running, "another request failed", log);
+            running.clear();
+        }
+        if running.is_empty() {
+            break;
+        }
+
+        if TERMINATED.load(Ordering::SeqCst) {
+            stop_jobs(rc, &running, "terminated", log);
             return RunOutcome {
-                error: Some(format!(
-                    "{} returned no jobid: {}",
-                    request.endpoint, submitted
-                )),
+                error: Some("terminated by the system".to_string()),
                 setup_failure: false,
                 jobids,
                 stats,
             };
-        };
-        jobids.push(jobid);
-        log.line(&format!("submitted {} as job {}", request.endpoint, jobid));
-
-        // Poll to terminal state.
-        let job_status: Value = loop {
-            if TERMINATED.load(Ordering::SeqCst) {
-                let _ = rc_call(client, &base, &user, &pass, "/job/stop", &json!({ "jobid": jobid }));
-                return RunOutcome {
-                    error: Some("terminated by the system".to_string()),
-                    setup_failure: false,
-                    jobids,
-                    stats,
-                };
-            }
-            if Instant::now() >= deadline {
-                let _ = rc_call(client, &base, &user, &pass, "/job/stop", &json!({ "jobid": jobid }));
-                return RunOutcome {
-                    error: Some(format!("timed out after {} seconds", spec.max_run_seconds)),
-                    setup_failure: false,
-                    jobids,
-                    stats,
-                };
-            }
-            match rc_call(client, &base, &user, &pass, "/job/status", &json!({ "jobid": jobid })) {
-                Ok(status) => {
-                    if status.get("finished").and_then(|f| f.as_bool()) == Some(true) {
-                        break status;
-                    }
+        }
+        if Instant::now() >= deadline {
+            stop_jobs(rc, &running, "timed out", log);
+            return RunOutcome {
+                error: Some(format!("timed out after {} seconds", spec.max_run_seconds)),
+                setup_failure: false,
+                jobids,
+                stats,
+            };
+        }
+
+        let mut still_running = Vec::with_capacity(running.len());
+        for (attempt, jobid) in running.drain(..) {
+            let job_status = match rc_call(rc, "/job/status", &json!({ "jobid": jobid })) {
+                Ok(status) if status.get("finished").and_then(|f| f.as_bool()) == Some(true) => {
+                    status
+                }
+                Ok(_) => {
+                    still_running.push((attempt, jobid));
+                    continue;
                 }
                 Err(e) => {
                     // Daemon died mid-run (crash, or the GUI's "stop all rclone processes").
-                    if let Ok(Some(code)) = daemon.child.try_wait() {
+                    if let Ok(Some(code)) = daemon.try_wait() {
                         return RunOutcome {
                             error: Some(format!(
                                 "rclone daemon exited unexpectedly (code {:?}): {}",
@@ -676,47 +1018,167 @@ fn execute(
                             stats,
                         };
                     }
+                    still_running.push((attempt, jobid));
+                    continue;
                 }
+            };
+
+            // Best-effort stats before evaluating the outcome.
+            if let Ok(job_stats) = rc_call(
+                rc,
+                "/core/stats",
+                &json!({ "group": format!("job/{}", jobid) }),
+            ) {
+                add_stats(&mut stats, &job_stats);
             }
-            std::thread::sleep(POLL_INTERVAL);
-        };
 
-        // Best-effort stats before evaluating the outcome.
-        if let Ok(job_stats) = rc_call(
-            client,
-            &base,
-            &user,
-            &pass,
-            "/core/stats",
-            &json!({ "group": format!("job/{}", jobid) }),
-        ) {
-            stats = Some(json!({
-                "bytes": job_stats.get("bytes"),
-                "transfers": job_stats.get("transfers"),
-                "errors": job_stats.get("errors"),
-            }));
+            let Some(error) = evaluate_job_failure(&job_status) else {
+                log.line(&format!("job {} completed successfully", jobid));
+                continue;
+            };
+            let error = if spec.requests[attempt.index].endpoint == bisync::ENDPOINT {
+                match recover_bisync(dirs, spec, attempt, error, daemon_log, log) {
+                    Recovery::Retry(attempt) => {
+                        pending.push_front(attempt);
+                        continue;
+                    }
+                    Recovery::Fail(error) => error,
+                }
+            } else {
+                error
+            };
+            log.error(&format!("job {} failed: {}", jobid, error));
+            errors.push(error);
+            stopping = !spec.continue_on_error;
         }
+        running = still_running;
 
-        if let Some(error) = evaluate_job_failure(&job_status) {
-            return RunOutcome {
-                error: Some(error),
-                setup_failure: false,
-                jobids,
-                stats,
-            };
+        if !running.is_empty() && !stopping {
+            std::thread::sleep(POLL_INTERVAL);
         }
-        log.line(&format!("job {} completed successfully", jobid));
     }
 
-    daemon.shutdown();
     RunOutcome {
-        error: None,
+        error: combine_errors(errors, spec.requests.len()),
         setup_failure: false,
         jobids,
         stats,
     }
 }
 
+/// One submission of a stored request, and the bisync recovery already spent on it.
+struct Attempt {
+    /// Into `spec.requests`.
+    index: usize,
+    body: Value,
+    lock_cleared: bool,
+    resynced: bool,
+}
+
+enum Recovery {
+    Retry(Attempt),
+    Fail(String),
+}
+
+fn submit(rc: &RcTarget, endpoint: &str, body: &Value) -> Result<i64, String> {
+    let submitted =
+        rc_call(rc, endpoint, body).map_err(|e| format!("failed to submit {}: {}", endpoint, e))?;
+    submitted
+        .get("jobid")
+        .and_then(|j| j.as_i64())
+        .ok_or_else(|| format!("{} returned no jobid: {}", endpoint, submitted))
+}
+
+fn stop_jobs(rc: &RcTarget, running: &[(Attempt, i64)], reason: &str, log: &mut RunLog) {
+    for (_, jobid) in running {
+        let _ = rc_call(rc, "/job/stop", &json!({ "jobid": jobid }));
+        log.warn(&format!("stopped job {} ({})", jobid, reason));
+    }
+}
+
+/// Bisync recovery for a failed attempt: retry it once after removing a stale lock, once with
+/// resync when the task's policy allows — at most once per kind (see bisync.rs).
+fn recover_bisync(
+    dirs: &AppDirs,
+    spec: &JobSpec,
+    mut attempt: Attempt,
+    error: String,
+    daemon_log: (&std::path::Path, u64),
+    log: &mut RunLog,
+) -> Recovery {
+    // We hold the task's run lock, so a lock file naming this pair is only live if its
+    // recorded process still is (a GUI bisync of the same paths).
+    if let Some(lock) = bisync::lock_file(&error).filter(|_| !attempt.lock_cleared) {
+        attempt.lock_cleared = true;
+        if bisync::lock_is_stale(&lock) && std::fs::remove_file(&lock).is_ok() {
+            log.line(&format!(
+                "removed stale bisync lock {} left by an interrupted run — retrying",
+                lock.display()
+            ));
+            return Recovery::Retry(attempt);
+        }
+        log.line(&format!("bisync lock {} is still in use", lock.display()));
+    }
+    let log_lines: Vec<String> = daemonlog::segment_lines(daemon_log.0, daemon_log.1)
+        .map(|lines| lines.collect())
+        .unwrap_or_default();
+    if !attempt.resynced
+        && bisync::resync_allowed(
+            bisync::ResyncPolicy::parse(&spec.bisync_resync),
+            &error,
+            &log_lines,
+            !has_succeeded_before(dirs, &spec.task_id),
+        )
+    {
+        attempt.resynced = true;
+        log.line(&format!(
+            "bisync needs a resync ({}) — retrying with resync (policy: {})",
+            error, spec.bisync_resync
+        ));
+        attempt.body["resync"] = Value::Bool(true);
+        return Recovery::Retry(attempt);
+    }
+    if bisync::needs_resync(&error, &log_lines) {
+        return Recovery::Fail(format!(
+            "{} — bisync must be resynced: run it once with resync, or allow automatic resync in the schedule's settings",
+            error
+        ));
+    }
+    Recovery::Fail(error)
+}
+
+/// Adds one job's `core/stats` counters to the run's totals.
+fn add_stats(stats: &mut Option<Value>, job_stats: &Value) {
+    let totals = stats.get_or_insert_with(|| json!({ "bytes": 0, "transfers": 0, "errors": 0 }));
+    for key in ["bytes", "transfers", "errors"] {
+        let add = job_stats.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+        let sum = totals.get(key).and_then(|v| v.as_u64()).unwrap_or(0) + add;
+        totals[key] = json!(sum);
+    }
+}
+
+/// The run's error from every failed request: a lone failure keeps its own message.
+fn combine_errors(mut errors: Vec<String>, requests: usize) -> Option<String> {
+    match errors.len() {
+        0 => None,
+        1 => errors.pop(),
+        n => Some(format!(
+            "{} of {} requests failed — {}",
+            n,
+            requests,
+            errors.join("; ")
+        )),
+    }
+}
+
+/// Whether any earlier run of the task finished successfully — bisync's "first run" test.
+fn has_succeeded_before(dirs: &AppDirs, task_id: &str) -> bool {
+    history::read(dirs, task_id, usize::MAX).iter().any(|line| {
+        line.get("event").and_then(|e| e.as_str()) == Some("finished")
+            && line.get("success").and_then(|s| s.as_bool()) == Some(true)
+    })
+}
+
 /// Failure detection mirroring the app: the job-level error, plus per-result errors from batch
 /// jobs. Deliberately stricter than the app's launch check (which only fails when ALL batch
 /// items fail): a scheduled run with partial failures must not report success.
@@ -757,18 +1219,87 @@ fn evaluate_job_failure(job_status: &Value) -> Option<String> {
     }
 }
 
-fn rc_call(
-    client: &reqwest::Client,
-    base: &str,
-    user: &str,
-    pass: &str,
-    endpoint: &str,
-    body: &Value,
-) -> Result<Value, String> {
+/// How the runner reaches its transient daemon's RC API. Credentials are required on every
+/// transport — the socket directory's permissions are the boundary, the token defence in depth.
+#[derive(Clone)]
+struct RcTarget {
+    client: reqwest::Client,
+    base: String,
+    user: String,
+    pass: String,
+}
+
+/// The daemon's `--rc-addr` plus how to reach it.
+struct RcListener {
+    addr: String,
+    target: RcTarget,
+    /// For the runner log: "unix socket /tmp/…/rc.sock" or "port 49152".
+    description: String,
+    run_dir: Option<RunDir>,
+}
+
+/// Unix: a Unix socket inside a fresh 0700 per-run directory. No port to race for (the old
+/// bind-0-then-release dance could hand the port to someone else before rclone bound it) and no
+/// listener other local users can even connect to.
+#[cfg(unix)]
+fn rc_listener() -> Result<RcListener, String> {
+    use std::os::unix::fs::DirBuilderExt;
+    // Short on purpose: macOS caps socket paths at 104 bytes and its temp dir is already long.
+    let run_dir = std::env::temp_dir().join(format!("rcloneui-run-{}", &random_token()[..16]));
+    // create(), not create_all(): fails on an existing path, so nobody can pre-plant the
+    // directory (or a symlink in its place) with looser permissions.
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&run_dir)
+        .map_err(|e| format!("failed to create the run directory: {}", e))?;
+    let run_dir = RunDir(run_dir);
+    let socket = run_dir.0.join("rc.sock");
+    let client = reqwest::Client::builder()
+        .unix_socket(socket.clone())
+        .timeout(RC_TIMEOUT)
+        .build()
+        .map_err(|e| format!("failed to create the RC client: {}", e))?;
+    Ok(RcListener {
+        addr: format!("unix://{}", socket.display()),
+        target: RcTarget {
+            client,
+            // The host is ignored on a Unix socket; the scheme and path still matter.
+            base: "http://localhost".to_string(),
+            user: random_token(),
+            pass: random_token(),
+        },
+        description: format!("unix socket {}", socket.display()),
+        run_dir: Some(run_dir),
+    })
+}
+
+/// Elsewhere: an ephemeral loopback port.
+#[cfg(not(unix))]
+fn rc_listener() -> Result<RcListener, String> {
+    let port = pick_port()?;
+    let client = reqwest::Client::builder()
+        .timeout(RC_TIMEOUT)
+        .build()
+        .map_err(|e| format!("failed to create the RC client: {}", e))?;
+    Ok(RcListener {
+        addr: format!("127.0.0.1:{}", port),
+        target: RcTarget {
+            client,
+            base: format!("http://127.0.0.1:{}", port),
+            user: random_token(),
+            pass: random_token(),
+        },
+        description: format!("port {}", port),
+        run_dir: None,
+    })
+}
+
+fn rc_call(rc: &RcTarget, endpoint: &str, body: &Value) -> Result<Value, String> {
     tauri::async_runtime::block_on(async {
-        let response = client
-            .post(format!("{}{}", base, endpoint))
-            .basic_auth(user, Some(pass))
+        let response = rc
+            .client
+            .post(format!("{}{}", rc.base, endpoint))
+            .basic_auth(&rc.user, Some(&rc.pass))
             .json(body)
             .send()
             .await
@@ -787,6 +1318,7 @@ fn rc_call(
     })
 }
 
+#[cfg(not(unix))]
 fn pick_port() -> Result<u16, String> {
     for _ in 0..10 {
         let listener = std::net::TcpListener::bind(("127.0.0.1", 0))
@@ -804,34 +1336,30 @@ fn pick_port() -> Result<u16, String> {
     Err("could not allocate a local port".to_string())
 }
 
-fn random_token(salt: &str) -> String {
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    hasher.update(format!(
-        "{:?}-{}-{}",
-        SystemTime::now(),
-        std::process::id(),
-        salt
-    ));
-    hasher
-        .finalize()
-        .iter()
-        .take(12)
-        .map(|b| format!("{:02x}", b))
-        .collect()
+/// 128 bits from the OS CSPRNG (uuid's v4 generator draws from getrandom), as 32 hex chars.
+fn random_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// The per-run socket directory, removed (socket and all) when dropped.
+struct RunDir(std::path::PathBuf);
+
+impl Drop for RunDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
 }
 
 /// Guarantees the transient daemon dies with the run — graceful /core/quit, then kill. The Drop
 /// impl covers panics and every early-return path; the next run's stale-lock daemonPid cleanup
 /// (and Task Scheduler's ExecutionTimeLimit on Windows) are the nets behind this net — cron
 /// itself does not supervise or kill job process trees.
-struct DaemonGuard {
+pub(super) struct DaemonGuard {
     child: Child,
-    client: reqwest::Client,
-    quit_url: String,
-    user: String,
-    pass: String,
+    rc: RcTarget,
     cleaned: bool,
+    /// Dropped after the daemon is gone (fields drop after Drop::drop), taking its socket along.
+    _run_dir: Option<RunDir>,
     /// Kill-on-close job object holding the daemon (see winjob.rs). Dropped after the graceful
     /// shutdown; the kernel drops it on ANY runner death, including TerminateProcess.
     #[cfg(windows)]
@@ -839,20 +1367,18 @@ struct DaemonGuard {
 }
 
 impl DaemonGuard {
+    /// One call on the daemon's RC API.
+    pub(super) fn call(&self, endpoint: &str, body: &Value) -> Result<Value, String> {
+        rc_call(&self.rc, endpoint, body)
+    }
+
     fn shutdown(&mut self) {
         if self.cleaned {
             return;
         }
         self.cleaned = true;
 
-        let _ = tauri::async_runtime::block_on(async {
-            self.client
-                .post(&self.quit_url)
-                .basic_auth(&self.user, Some(&self.pass))
-                .json(&json!({}))
-                .send()
-                .await
-        });
+        let _ = rc_call(&self.rc, "/core/quit", &json!({}));
 
         let grace_deadline = Instant::now() + Duration::from_secs(2);
         loop {
@@ -874,3 +1400,282 @@ impl Drop for DaemonGuard {
         self.shutdown();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec_with(bodies: Vec<Value>) -> JobSpec {
+        JobSpec {
+            schema_version: jobfile::JOB_SCHEMA_VERSION,
+            task_id: "t1".into(),
+            host_id: "local".into(),
+            name: "Backup".into(),
+            operation: "sync".into(),
+            cron: "0 3 * * *".into(),
+            config_id: "default".into(),
+            binary: "app-default".into(),
+            max_run_seconds: jobfile::DEFAULT_MAX_RUN_SECONDS,
+            verbose_logging: false,
+            run_mode: "user".into(),
+            bisync_resync: "never".into(),
+            env: Default::default(),
+            secret_env: Vec::new(),
+            daemon_flags: Vec::new(),
+            max_parallel: 1,
+            continue_on_error: false,
+            log_retention: history::DEFAULT_LOG_RETENTION,
+            enabled: None,
+            requests: bodies
+                .into_iter()
+                .map(|body| jobfile::RcRequest {
+                    endpoint: "/sync/sync".into(),
+                    body,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reauth_names_the_remote_from_rclones_hint_or_the_task() {
+        let spec = spec_with(vec![json!({ "srcFs": "/data", "dstFs": "gdrive:backup" })]);
+
+        let hinted = detect_reauth(
+            "couldn't fetch token: invalid_grant: maybe token expired? - try refreshing with \"rclone config reconnect work-drive:\"",
+            None,
+            &spec,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(hinted.remote.as_deref(), Some("work-drive"));
+
+        // No hint in the error: the task's only configured remote is the one to reconnect.
+        let only = detect_reauth(
+            "oauth2: token expired and refresh token is not set",
+            None,
+            &spec,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(only.remote.as_deref(), Some("gdrive"));
+
+        assert!(detect_reauth("directory not found", None, &spec, &[]).is_none());
+
+        // A bare 401 is a sign-in problem only on a remote that signs in with OAuth.
+        let unauthorized = "HTTP error 401 (401 Unauthorized)";
+        let oauth = vec!["gdrive".to_string()];
+        assert!(detect_reauth(unauthorized, None, &spec, &oauth).is_some());
+        assert!(detect_reauth(unauthorized, None, &spec, &[]).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rc_calls_go_over_the_private_socket() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::os::unix::fs::PermissionsExt;
+
+        let listener = rc_listener().unwrap();
+        let dir = listener.run_dir.as_ref().unwrap().0.clone();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        assert_eq!(listener.target.pass.len(), 32);
+        assert_ne!(listener.target.user, listener.target.pass);
+
+        // Stand-in for rcd: answers one request on the socket named in --rc-addr.
+        let socket = listener.addr.strip_prefix("unix://").unwrap().to_string();
+        let server = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = server.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                head.push(line);
+            }
+            let length: usize = head
+                .iter()
+                .find_map(|h| {
+                    let value = h.to_lowercase();
+                    Some(
+                        value
+                            .strip_prefix("content-length:")?
+                            .trim()
+                            .parse()
+                            .unwrap(),
+                    )
+                })
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let reply = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                         Content-Length: 11\r\nConnection: close\r\n\r\n{\"jobid\":7}";
+            reader.get_mut().write_all(reply.as_bytes()).unwrap();
+            (head, String::from_utf8(body).unwrap())
+        });
+
+        let reply = rc_call(&listener.target, "/sync/sync", &json!({ "srcFs": "/a" })).unwrap();
+        assert_eq!(reply, json!({ "jobid": 7 }));
+        let (head, body) = handle.join().unwrap();
+        assert!(head[0].starts_with("POST /sync/sync "));
+        assert!(head
+            .iter()
+            .any(|h| h.to_lowercase().starts_with("authorization: basic ")));
+        assert_eq!(body, r#"{"srcFs":"/a"}"#);
+
+        drop(listener);
+        assert!(!dir.exists(), "the run directory goes with the listener");
+    }
+
+    /// Minimal rcd stand-in: every submitted job takes ~1s (the body's "seconds" when present),
+    /// fails with the body's "fail" message when present, and reports 10 transferred bytes.
+    /// Records the peak number of unfinished jobs.
+    #[cfg(unix)]
+    #[derive(Default)]
+    struct FakeRcd {
+        jobs: Vec<(std::time::Instant, Duration, Option<String>, bool)>,
+        peak_running: usize,
+        stopped: Vec<i64>,
+    }
+
+    #[cfg(unix)]
+    fn serve_fake_rcd(socket: &str) -> std::sync::Arc<std::sync::Mutex<FakeRcd>> {
+        use std::io::{BufRead, BufReader, Read, Write};
+        let state = std::sync::Arc::new(std::sync::Mutex::new(FakeRcd::default()));
+        let server = std::os::unix::net::UnixListener::bind(socket).unwrap();
+        let shared = state.clone();
+        std::thread::spawn(move || {
+            for stream in server.incoming().flatten() {
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                let mut length = 0usize;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if request_line.is_empty() {
+                        request_line = line.clone();
+                    }
+                    if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                let _ = reader.read_exact(&mut body);
+                let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+                let path = request_line
+                    .split(' ')
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+
+                let mut state = shared.lock().unwrap();
+                let reply = match path.as_str() {
+                    "/sync/sync" => {
+                        let fail = body.get("fail").and_then(|f| f.as_str()).map(String::from);
+                        let seconds = body.get("seconds").and_then(|s| s.as_u64()).unwrap_or(1);
+                        let started = std::time::Instant::now();
+                        let duration = Duration::from_secs(seconds);
+                        state.jobs.push((started, duration, fail, false));
+                        let running = state.jobs.iter().filter(|j| !j.3).count();
+                        state.peak_running = state.peak_running.max(running);
+                        json!({ "jobid": state.jobs.len() })
+                    }
+                    "/job/status" => {
+                        let id = body["jobid"].as_u64().unwrap() as usize;
+                        let job = &mut state.jobs[id - 1];
+                        let finished = job.0.elapsed() >= job.1;
+                        job.3 = finished;
+                        json!({ "finished": finished, "error": job.2.clone().unwrap_or_default() })
+                    }
+                    "/core/stats" => json!({ "bytes": 10, "transfers": 1, "errors": 0 }),
+                    "/job/stop" => {
+                        let id = body["jobid"].as_i64().unwrap();
+                        state.stopped.push(id);
+                        state.jobs[id as usize - 1].3 = true;
+                        json!({})
+                    }
+                    _ => json!({}),
+                };
+                drop(state);
+                let reply = reply.to_string();
+                let _ = reader.get_mut().write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        reply.len(),
+                        reply
+                    )
+                    .as_bytes(),
+                );
+            }
+        });
+        state
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn requests_run_in_a_bounded_pool_and_aggregate() {
+        let root = std::env::temp_dir().join(format!("rcloneui-pool-test-{}", std::process::id()));
+        let dirs = AppDirs {
+            app_data: root.clone(),
+            app_local_data: root.clone(),
+        };
+        let mut log = RunLog::open(&dirs, "t1");
+        let mut daemon = Command::new("sleep").arg("60").spawn().unwrap();
+        let run = |bodies: Vec<Value>,
+                   max_parallel: u32,
+                   continue_on_error: bool,
+                   log: &mut RunLog,
+                   daemon: &mut Child| {
+            let listener = rc_listener().unwrap();
+            let fake = serve_fake_rcd(listener.addr.strip_prefix("unix://").unwrap());
+            let mut spec = spec_with(bodies);
+            spec.max_parallel = max_parallel;
+            spec.continue_on_error = continue_on_error;
+            let deadline = Instant::now() + Duration::from_secs(60);
+            let target = &listener.target;
+            let outcome = run_requests(&dirs, &spec, target, daemon, deadline, (&root, 0), log);
+            let fake = std::mem::take(&mut *fake.lock().unwrap());
+            (outcome, fake)
+        };
+
+        let (outcome, fake) = run(vec![json!({}); 5], 2, false, &mut log, &mut daemon);
+        assert_eq!(outcome.error, None);
+        assert_eq!(outcome.jobids.len(), 5);
+        assert_eq!(fake.peak_running, 2);
+        assert_eq!(outcome.stats.unwrap()["bytes"], json!(50));
+
+        // Stop on failure: the job running alongside the failed one is stopped, the rest never
+        // start.
+        let bodies = vec![
+            json!({ "fail": "quota exceeded" }),
+            json!({ "seconds": 5 }),
+            json!({}),
+            json!({}),
+        ];
+        let (outcome, fake) = run(bodies.clone(), 2, false, &mut log, &mut daemon);
+        assert_eq!(outcome.error.as_deref(), Some("quota exceeded"));
+        assert_eq!(fake.jobs.len(), 2);
+        assert_eq!(fake.stopped, vec![2]);
+
+        // Continue on error: every request runs, failures are combined.
+        let mut bodies = bodies;
+        bodies[1] = json!({});
+        bodies[3] = json!({ "fail": "not found" });
+        let (outcome, fake) = run(bodies, 4, true, &mut log, &mut daemon);
+        assert_eq!(
+            outcome.error.as_deref(),
+            Some("2 of 4 requests failed — quota exceeded; not found")
+        );
+        assert_eq!(fake.jobs.len(), 4);
+        assert!(fake.stopped.is_empty());
+
+        let _ = daemon.kill();
+        let _ = daemon.wait();
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
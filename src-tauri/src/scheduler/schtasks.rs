@@ -17,7 +17,7 @@ use std::process::Command;
 
 use super::cronconv::{DayShape, SchtasksTrigger};
 use super::storeread::AppDirs;
-use super::{InstallState, RenderedSchedule, SchedulerBackend};
+use super::{unescape_xml, ArtifactView, InstallState, RenderedSchedule, SchedulerBackend};
 
 const TASK_FOLDER: &str = "RcloneUI";
 const TASK_PREFIX: &str = "task-";
@@ -250,7 +250,34 @@ fn month_elements(months: &BTreeSet<u16>) -> String {
         .collect()
 }
 
+/// The text of the first `<tag>…</tag>` element in `xml`.
+fn element<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{}>", tag))?;
+    Some(&xml[start..end])
+}
+
+/// A task definition as an ArtifactView. Task Scheduler re-serializes what it was given, so
+/// the schedule is the Triggers element with whitespace and trigger-level `<Enabled>` dropped
+/// (both vary with the Windows version, not with the schedule).
+fn xml_view(xml: &str) -> Option<ArtifactView> {
+    let program = unescape_xml(element(xml, "Command")?.trim());
+    Some(ArtifactView {
+        enabled: !xml.to_lowercase().contains("<enabled>false</enabled>"),
+        schedule: element(xml, "Triggers")?
+            .split_whitespace()
+            .collect::<String>()
+            .replace("<Enabled>true</Enabled>", ""),
+        program: program.trim_matches('"').to_string(),
+        args: split_windows_args(&unescape_xml(element(xml, "Arguments").unwrap_or(""))),
+    })
+}
+
 impl SchedulerBackend for SchtasksBackend {
+    fn kind(&self) -> &'static str {
+        "Task Scheduler"
+    }
+
     fn install(&self, task_id: &str, rendered: &RenderedSchedule) -> Result<(), String> {
         let xml = Self::build_xml(task_id, rendered)?;
         std::fs::create_dir_all(&self.artifacts_dir)
@@ -365,6 +392,30 @@ impl SchedulerBackend for SchtasksBackend {
         }
         Ok(InstallState::NotInstalled)
     }
+
+    fn expected(&self, task_id: &str, rendered: &RenderedSchedule) -> Result<ArtifactView, String> {
+        let xml = Self::build_xml(task_id, rendered)?;
+        xml_view(&xml).ok_or_else(|| "malformed task XML".to_string())
+    }
+
+    fn inspect(&self, task_id: &str) -> Result<Option<ArtifactView>, String> {
+        let output = Self::schtasks(&["/Query", "/TN", &Self::task_name(task_id), "/XML"])?;
+        if !output.status.success() {
+            // Same disambiguation as is_installed: a failed query alone doesn't prove absence.
+            return match self.is_installed(task_id)? {
+                InstallState::NotInstalled => Ok(None),
+                InstallState::Installed { .. } => Err(format!(
+                    "schtasks /Query failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )),
+            };
+        }
+        let mut bytes = output.stdout;
+        bytes.retain(|&b| b != 0);
+        xml_view(&String::from_utf8_lossy(&bytes))
+            .map(Some)
+            .ok_or_else(|| "its task definition has no program to run".to_string())
+    }
 }
 
 /// Whether the task's definition references THIS profile's data dir. Task Scheduler's namespace
@@ -467,6 +518,50 @@ fn quote_windows_arg(arg: &str) -> String {
     out
 }
 
+/// The inverse of `quote_windows_arg` (CommandLineToArgvW's rules for arguments).
+fn split_windows_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut in_arg = false;
+    let mut quoted = false;
+    let mut backslashes = 0;
+    for c in line.chars() {
+        match c {
+            '\\' => {
+                backslashes += 1;
+                in_arg = true;
+            }
+            '"' => {
+                arg.push_str(&"\\".repeat(backslashes / 2));
+                if backslashes % 2 == 1 {
+                    arg.push('"');
+                } else {
+                    quoted = !quoted;
+                }
+                backslashes = 0;
+                in_arg = true;
+            }
+            c => {
+                arg.push_str(&"\\".repeat(backslashes));
+                backslashes = 0;
+                if (c == ' ' || c == '\t') && !quoted {
+                    if std::mem::take(&mut in_arg) {
+                        args.push(std::mem::take(&mut arg));
+                    }
+                } else {
+                    arg.push(c);
+                    in_arg = true;
+                }
+            }
+        }
+    }
+    arg.push_str(&"\\".repeat(backslashes));
+    if in_arg {
+        args.push(arg);
+    }
+    args
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -520,5 +615,9 @@ mod tests {
         // Embedded quote: preceding backslashes double, quote gets its own escape.
         assert_eq!(quote_windows_arg(r#"a\"b"#), r#""a\\\"b""#);
         assert_eq!(quote_windows_arg(""), "\"\"");
+
+        let args = [r"C:\a dir\", r#"a\"b"#, "", "run-task", r"C:\x\y"];
+        let line = args.map(quote_windows_arg).join(" ");
+        assert_eq!(split_windows_args(&line), args);
     }
 }
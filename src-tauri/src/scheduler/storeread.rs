@@ -19,6 +19,7 @@ use serde::Deserialize;
 /// Must match tauri.conf.json `identifier`.
 const APP_IDENTIFIER: &str = "com.rclone.ui";
 
+#[derive(Clone)]
 pub struct AppDirs {
     /// Store files (tauri-plugin-store resolves against BaseDirectory::AppData — Roaming on
     /// Windows).
@@ -137,6 +138,23 @@ pub fn find_config<'a>(host: &'a HostState, config_id: &str) -> Option<&'a Confi
         .find(|c| c.id.as_deref() == Some(config_id))
 }
 
+/// The remotes in an rclone config's text that sign in with OAuth — their section holds a
+/// `token`. Empty for an encrypted config, whose sections can't be read.
+pub fn oauth_remotes(config: &str) -> Vec<String> {
+    let mut remotes: Vec<String> = Vec::new();
+    let mut section: Option<&str> = None;
+    for line in config.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = Some(name);
+        } else if let (Some(name), Some((key, _))) = (section, line.split_once('=')) {
+            if key.trim() == "token" && !remotes.iter().any(|r| r == name) {
+                remotes.push(name.to_string());
+            }
+        }
+    }
+    remotes
+}
+
 /// Mirrors lib/rclone/cli.ts buildRcloneEnv: proxy vars, config pinning, and encrypted-config
 /// credentials. Errors when the config is encrypted with nothing stored — the headless runner
 /// has no UI to prompt with.
@@ -213,6 +231,14 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn oauth_remotes_are_the_ones_holding_a_token() {
+        let config = "[gdrive]\ntype = drive\ntoken = {\"access_token\":\"x\"}\n\n[s3]\ntype = s3\naccess_key_id = AKIA\n\n[box]\ntype = box\ntoken={}\n";
+        assert_eq!(oauth_remotes(config), vec!["gdrive", "box"]);
+        let encrypted = "# Encrypted rclone configuration File\n\nRCLONE_ENCRYPT_V0:\nabc";
+        assert!(oauth_remotes(encrypted).is_empty());
+    }
+
     #[test]
     fn encrypted_config_without_pass_errors() {
         let host = HostState::default();
@@ -9,6 +9,8 @@ interface CronEditorProps {
     onChange: (newExpression: string | null) => void
     /** Platform-specific validation error from scheduler_validate_cron. */
     error?: string | null
+    /** Localized description from scheduler_validate_cron, preferred over cronstrue's. */
+    description?: string | null
 }
 
 interface CronFieldProps {
@@ -20,7 +22,12 @@ interface CronFieldProps {
 
 const DEFAULT_OPTIONS = ['*', '*/5', '*/10', '*/15', '*/30']
 
-export default function CronEditor({ expression, onChange, error }: CronEditorProps) {
+export default function CronEditor({
+    expression,
+    onChange,
+    error,
+    description: localizedDescription,
+}: CronEditorProps) {
     const [cronExpression, setCronExpression] = useState(expression)
 
     const [minute, hour, dayOfMonth, month, dayOfWeek] = useMemo(
@@ -28,18 +35,16 @@ export default function CronEditor({ expression, onChange, error }: CronEditorPr
         [cronExpression]
     )
 
+    // The schedule sentence alone: the closed-app note below is UI text, never appended to a
+    // possibly localized sentence.
     const readableDescription = useMemo(() => {
-        if (!cronExpression)
-            return 'Enter a cron expression to have this task run at regular intervals'
-        let description: string
+        if (!cronExpression) return null
         try {
-            description = cronstrue.toString(cronExpression)
-            description += '. Runs even when the app is closed.'
+            return localizedDescription ?? cronstrue.toString(cronExpression)
         } catch {
-            description = 'Invalid cron expression'
+            return null
         }
-        return description
-    }, [cronExpression])
+    }, [cronExpression, localizedDescription])
 
     const handleFieldChange = useCallback(
         (field: string, value: string) => {
@@ -127,7 +132,14 @@ export default function CronEditor({ expression, onChange, error }: CronEditorPr
                 />
             </div>
 
-            <div className="text-sm text-neutral-500">{readableDescription}</div>
+            <div className="text-sm text-neutral-500">
+                {!cronExpression &&
+                    'Enter a cron expression to have this task run at regular intervals'}
+                {!!cronExpression && (readableDescription ?? 'Invalid cron expression')}
+            </div>
+            {!!readableDescription && (
+                <div className="text-sm text-neutral-500">Runs even when the app is closed.</div>
+            )}
             {!!error && <div className="text-sm text-danger-500">{error}</div>}
         </div>
     )
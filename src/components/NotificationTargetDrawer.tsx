@@ -9,23 +9,45 @@ import {
     DrawerFooter,
     DrawerHeader,
     Input,
+    Select,
+    SelectItem,
     Switch,
+    Textarea,
     cn,
 } from '@heroui/react'
 import { useMutation, useQueryClient } from '@tanstack/react-query'
 import { message } from '@tauri-apps/plugin-dialog'
 import { openUrl } from '@tauri-apps/plugin-opener'
 import { platform } from '@tauri-apps/plugin-os'
-import { ExternalLinkIcon } from 'lucide-react'
+import { CheckIcon, ExternalLinkIcon, MinusIcon, PlusIcon, Trash2Icon } from 'lucide-react'
 import { useMemo, useState } from 'react'
 import {
+    DEFAULT_PAYLOAD_TEMPLATE,
+    EMPTY_EMAIL_SETTINGS,
+    type EmailSecurity,
+    type EmailSettings,
     NOTIFICATION_PROVIDERS,
+    type PushProvider,
     TELEGRAM_CHAT_ID_HELP,
     addNotificationTarget,
+    buildEmailUrl,
+    buildMatrixUrl,
     buildTelegramUrl,
+    buildTokenUrl,
+    emailAuth,
+    generateSigningSecret,
+    previewPayloadTemplate,
+    previewTargetFilters,
     sendTestNotification,
+    splitEmailUrl,
+    splitMatrixUrl,
     splitTelegramUrl,
+    splitTokenUrl,
     updateNotificationTarget,
+    validateEmailSettings,
+    validateMatrixRoomId,
+    validatePushServerUrl,
+    validatePushToken,
     validateTelegramBotUrl,
     validateTelegramChatId,
     validateWebhookUrl,
@@ -34,10 +56,95 @@ import type {
     NotificationCatalog,
     NotificationEventId,
     NotificationProvider,
+    NotificationSeverity,
     NotificationTarget,
+    PayloadTemplate,
+    TargetAuth,
+    TargetAuthMode,
+    TargetDelivery,
+    TargetFilters,
+    TargetHeader,
 } from '../../types/notifications'
+import { useHostStore } from '../../store/host'
 import ProviderIcon from './icons/ProviderIcon'
 
+const EMAIL_SECURITY_OPTIONS: { key: EmailSecurity; label: string }[] = [
+    { key: 'starttls', label: 'STARTTLS (port 587)' },
+    { key: 'tls', label: 'TLS (port 465)' },
+    { key: 'none', label: 'None — local relays only (port 25)' },
+]
+
+const PAYLOAD_METHODS: { key: PayloadTemplate['method'] }[] = [
+    { key: 'POST' },
+    { key: 'PUT' },
+    { key: 'PATCH' },
+]
+
+const PAYLOAD_TEMPLATE_HELP =
+    'Placeholders: {{id}}, {{label}}, {{severity}}, {{title}}, {{body}}, {{timestamp}}, {{data}} and fields like {{data.durationMs}}. In JSON they are escaped inside strings and become values outside them.'
+
+const AUTH_OPTIONS: { key: TargetAuthMode | 'none'; label: string }[] = [
+    { key: 'none', label: 'None' },
+    { key: 'bearer', label: 'Bearer token' },
+    { key: 'basic', label: 'Basic auth' },
+    { key: 'header', label: 'Custom header' },
+]
+
+const AUTH_SECRET_LABELS: Record<TargetAuthMode, string> = {
+    bearer: 'Token',
+    basic: 'Password',
+    header: 'Value',
+}
+
+const SIGNING_HELP =
+    'Each request carries X-RcloneUI-Timestamp and X-RcloneUI-Signature: sha256=<HMAC-SHA256 of "timestamp.body">. Copy the secret to your receiver now — it is stored encrypted and never shown again.'
+
+const FILTER_OPERATIONS: { key: string; label: string }[] = [
+    { key: 'sync', label: 'Sync' },
+    { key: 'copy', label: 'Copy' },
+    { key: 'move', label: 'Move' },
+    { key: 'bisync', label: 'Bisync' },
+    { key: 'delete', label: 'Delete' },
+    { key: 'purge', label: 'Purge' },
+    { key: 'batch', label: 'Batch' },
+]
+
+const SEVERITY_OPTIONS: { key: NotificationSeverity | 'any'; label: string }[] = [
+    { key: 'any', label: 'Any' },
+    { key: 'warning', label: 'Warnings and errors' },
+    { key: 'error', label: 'Errors only' },
+]
+
+const DELIVERY_MODES: { key: TargetDelivery['mode']; label: string; description: string }[] = [
+    { key: 'immediate', label: 'Immediately', description: 'Send every event as it happens' },
+    {
+        key: 'deduplicate',
+        label: 'Deduplicate repeats',
+        description: 'Send the first of a repeating event, then one follow-up with the count',
+    },
+    { key: 'digest', label: 'Digest', description: 'Batch events into one summary message' },
+]
+
+const DEDUPE_WINDOWS: { key: string; label: string }[] = [
+    { key: '15', label: '15 minutes' },
+    { key: '30', label: '30 minutes' },
+    { key: '60', label: '1 hour' },
+    { key: '180', label: '3 hours' },
+    { key: '720', label: '12 hours' },
+    { key: '1440', label: '24 hours' },
+]
+
+const DIGEST_INTERVALS: { key: NonNullable<TargetDelivery['interval']>; label: string }[] = [
+    { key: 'hourly', label: 'Hourly, on the hour' },
+    { key: 'daily', label: 'Daily, at midnight' },
+]
+
+const PUSH_TOKEN_PLACEHOLDERS: Record<PushProvider, string> = {
+    ntfy: 'tk_… — leave empty for a public topic',
+    gotify: 'The token of a Gotify application',
+    matrix: 'syt_… — the token of the account that posts',
+}
+
 // Single component for both add and edit — the forms are identical, only the header text,
 // initial values, and the Rust command differ. State seeds from props at mount: the parent
 // remounts this with a key per target/provider, and only renders it once the catalog query
@@ -60,6 +167,11 @@ export default function NotificationTargetDrawer({
     const providerMeta = NOTIFICATION_PROVIDERS[provider]
     const isEditing = !!target
     const isTelegram = provider === 'telegram'
+    const isEmail = provider === 'email'
+    // ntfy, Gotify and Matrix: a server/topic URL plus an access token (and Matrix's room id),
+    // merged like Telegram's chat id.
+    const pushProvider: PushProvider | null =
+        provider === 'ntfy' || provider === 'gotify' || provider === 'matrix' ? provider : null
 
     const queryClient = useQueryClient()
 
@@ -68,13 +180,63 @@ export default function NotificationTargetDrawer({
     const [name, setName] = useState(target?.name ?? '')
     // Telegram stores one merged URL (…/sendMessage?chat_id=…) but the form edits its two
     // halves separately — the user never types query params by hand.
-    const [url, setUrl] = useState(() =>
-        target && isTelegram ? splitTelegramUrl(target.url).baseUrl : (target?.url ?? '')
-    )
+    const [url, setUrl] = useState(() => {
+        if (target && isTelegram) return splitTelegramUrl(target.url).baseUrl
+        if (target && provider === 'matrix') return splitMatrixUrl(target.url).homeserver
+        if (target && pushProvider) return splitTokenUrl(pushProvider, target.url).baseUrl
+        return target?.url ?? ''
+    })
     const [chatId, setChatId] = useState(() =>
         target && isTelegram ? splitTelegramUrl(target.url).chatId : ''
     )
+    const [token, setToken] = useState(() => {
+        if (target && provider === 'matrix') return splitMatrixUrl(target.url).token
+        return target && pushProvider ? splitTokenUrl(pushProvider, target.url).token : ''
+    })
+    const [roomId, setRoomId] = useState(() =>
+        target && provider === 'matrix' ? splitMatrixUrl(target.url).roomId : ''
+    )
+    // Email targets store their SMTP settings merged into the URL the same way, and their login
+    // as basic auth — the stored password stays in Rust.
+    const [email, setEmail] = useState<EmailSettings>(() =>
+        target && isEmail ? splitEmailUrl(target.url, target.auth) : EMPTY_EMAIL_SETTINGS
+    )
+    const hasStoredEmailPassword = isEmail && !!target?.auth?.secret
+    // Generic webhooks can replace the fixed payload with their own template.
+    const [useTemplate, setUseTemplate] = useState(!!target?.template)
+    const [template, setTemplate] = useState<PayloadTemplate>(
+        target?.template ?? DEFAULT_PAYLOAD_TEMPLATE
+    )
+    // The stored signing secret never comes back to the GUI — only whether there is one.
+    const hasStoredSecret = !!target?.signingSecret
+    const [signRequests, setSignRequests] = useState(hasStoredSecret)
+    const [signingSecret, setSigningSecret] = useState('')
+    // Webhook headers and auth; like the signing secret, a stored auth secret stays in Rust.
+    const [customHeaders, setCustomHeaders] = useState<TargetHeader[]>(target?.headers ?? [])
+    const [authMode, setAuthMode] = useState<TargetAuthMode | 'none'>(
+        target?.auth?.mode ?? 'none'
+    )
+    const [authUsername, setAuthUsername] = useState(target?.auth?.username ?? '')
+    const [authHeader, setAuthHeader] = useState(target?.auth?.header ?? '')
+    const [authSecret, setAuthSecret] = useState('')
+    const hasStoredAuthSecret = !!target?.auth?.secret && target.auth.mode === authMode
     const [events, setEvents] = useState<NotificationEventId[]>(target?.events ?? allEventIds)
+    const [scheduleIds, setScheduleIds] = useState<string[]>(target?.filters?.scheduleIds ?? [])
+    const [operations, setOperations] = useState<string[]>(target?.filters?.operations ?? [])
+    const [remotePatterns, setRemotePatterns] = useState(
+        (target?.filters?.remotes ?? []).join(', ')
+    )
+    const [minSeverity, setMinSeverity] = useState<NotificationSeverity | 'any'>(
+        target?.filters?.minSeverity ?? 'any'
+    )
+    const scheduledTasks = useHostStore((state) => state.scheduledTasks)
+    const [deliveryMode, setDeliveryMode] = useState<TargetDelivery['mode']>(
+        target?.delivery?.mode ?? 'immediate'
+    )
+    const [dedupeWindow, setDedupeWindow] = useState(String(target?.delivery?.windowMinutes ?? 60))
+    const [digestInterval, setDigestInterval] = useState<NonNullable<TargetDelivery['interval']>>(
+        target?.delivery?.interval ?? 'hourly'
+    )
     const [isEnabled, setIsEnabled] = useState(target?.isEnabled ?? true)
     const [urlTouched, setUrlTouched] = useState(false)
     const [chatIdTouched, setChatIdTouched] = useState(false)
@@ -84,8 +246,10 @@ export default function NotificationTargetDrawer({
         if (!urlTouched || !url.trim()) {
             return null
         }
-        return isTelegram ? validateTelegramBotUrl(url) : validateWebhookUrl(provider, url)
-    }, [provider, isTelegram, url, urlTouched])
+        if (isTelegram) return validateTelegramBotUrl(url)
+        if (pushProvider) return validatePushServerUrl(pushProvider, url)
+        return validateWebhookUrl(provider, url)
+    }, [provider, isTelegram, pushProvider, url, urlTouched])
 
     const chatIdError = useMemo(
         () =>
@@ -93,8 +257,78 @@ export default function NotificationTargetDrawer({
         [isTelegram, chatId, chatIdTouched]
     )
 
-    // The URL as it will be stored and POSTed — merged for Telegram, as typed otherwise.
+    const tokenError = useMemo(
+        () => (pushProvider && token.trim() ? validatePushToken(pushProvider, token) : null),
+        [pushProvider, token]
+    )
+
+    const roomIdError = useMemo(
+        () => (provider === 'matrix' && roomId.trim() ? validateMatrixRoomId(roomId) : null),
+        [provider, roomId]
+    )
+
+    const pushFieldError = (push: PushProvider) =>
+        validatePushServerUrl(push, url) ||
+        (push === 'matrix' ? validateMatrixRoomId(roomId) : null) ||
+        validatePushToken(push, token)
+
+    const buildPushUrl = (push: PushProvider) =>
+        push === 'matrix' ? buildMatrixUrl(url, roomId, token) : buildTokenUrl(push, url, token)
+
+    const updateEmail = (patch: Partial<EmailSettings>) =>
+        setEmail((current) => ({ ...current, ...patch }))
+
+    const activeTemplate = provider === 'webhook' && useTemplate ? template : undefined
+    const newSigningSecret =
+        provider === 'webhook' && signRequests && signingSecret ? signingSecret : undefined
+    const activeHeaders =
+        provider === 'webhook'
+            ? customHeaders
+                  .map((header) => ({ name: header.name.trim(), value: header.value }))
+                  .filter((header) => header.name)
+            : undefined
+    // No secret entered keeps the stored one (same mode only — Rust enforces it).
+    let activeAuth: TargetAuth | undefined
+    if (isEmail) {
+        activeAuth = emailAuth(email)
+    } else if (provider === 'webhook' && authMode !== 'none') {
+        activeAuth = {
+            mode: authMode,
+            username: authUsername.trim(),
+            header: authHeader.trim(),
+            secret: authSecret || undefined,
+        }
+    }
+
+    const activeFilters: TargetFilters = {
+        scheduleIds,
+        operations,
+        remotes: remotePatterns
+            .split(',')
+            .map((pattern) => pattern.trim())
+            .filter(Boolean),
+        minSeverity: minSeverity === 'any' ? undefined : minSeverity,
+    }
+
+    const activeDelivery: TargetDelivery = {
+        mode: deliveryMode,
+        windowMinutes: Number(dedupeWindow),
+        interval: digestInterval,
+    }
+
+    const updateHeader = (index: number, patch: Partial<TargetHeader>) =>
+        setCustomHeaders((current) =>
+            current.map((header, i) => (i === index ? { ...header, ...patch } : header))
+        )
+
+    // The URL as it will be stored and sent to — merged from the form, except for webhooks.
     const effectiveUrl = useMemo(() => {
+        if (isEmail) {
+            return validateEmailSettings(email) ? '' : buildEmailUrl(email)
+        }
+        if (pushProvider) {
+            return pushFieldError(pushProvider) ? '' : buildPushUrl(pushProvider)
+        }
         if (!isTelegram) {
             return url.trim()
         }
@@ -102,7 +336,7 @@ export default function NotificationTargetDrawer({
             return ''
         }
         return buildTelegramUrl(url, chatId)
-    }, [isTelegram, url, chatId])
+    }, [isTelegram, isEmail, pushProvider, email, url, chatId, token, roomId])
 
     const canSendTest = !!effectiveUrl && !validateWebhookUrl(provider, effectiveUrl)
 
@@ -110,6 +344,12 @@ export default function NotificationTargetDrawer({
 
     const allSelected = events.length === allEventIds.length
 
+    let urlLabel = 'Webhook URL'
+    if (isTelegram) urlLabel = 'Bot URL'
+    else if (provider === 'ntfy') urlLabel = 'Topic URL'
+    else if (provider === 'gotify') urlLabel = 'Server URL'
+    else if (provider === 'matrix') urlLabel = 'Homeserver URL'
+
     const drawerTitle = `${isEditing ? 'Edit' : 'Add'} ${providerMeta.titleLabel}`
 
     const sendTestMutation = useMutation({
@@ -117,6 +357,10 @@ export default function NotificationTargetDrawer({
             await sendTestNotification({
                 provider,
                 url: effectiveUrl,
+                template: activeTemplate,
+                signingSecret: newSigningSecret,
+                headers: activeHeaders,
+                auth: activeAuth,
                 id: target?.id,
                 name: name.trim() || undefined,
             })
@@ -135,10 +379,32 @@ export default function NotificationTargetDrawer({
         onSettled: () => queryClient.invalidateQueries({ queryKey: ['notifications', 'targets'] }),
     })
 
+    const previewMutation = useMutation({
+        mutationFn: () => previewPayloadTemplate(template),
+    })
+
+    const filterPreviewMutation = useMutation({
+        mutationFn: () => previewTargetFilters(events, activeFilters),
+    })
+
+    const updateTemplate = (patch: Partial<PayloadTemplate>) => {
+        setTemplate((current) => ({ ...current, ...patch }))
+        previewMutation.reset()
+    }
+
     const handleSave = async (close: () => void) => {
         const trimmedName = name.trim()
 
-        if (!trimmedName || !url.trim() || (isTelegram && !chatId.trim())) {
+        if (isEmail) {
+            const emailError = trimmedName ? validateEmailSettings(email) : 'A name is required.'
+            if (emailError) {
+                await message(emailError, {
+                    title: 'Invalid email configuration',
+                    kind: 'warning',
+                })
+                return
+            }
+        } else if (!trimmedName || !url.trim() || (isTelegram && !chatId.trim())) {
             await message(
                 isTelegram
                     ? 'Name, bot URL and chat ID are required.'
@@ -162,7 +428,25 @@ export default function NotificationTargetDrawer({
             }
         }
 
-        const mergedUrl = isTelegram ? buildTelegramUrl(url, chatId) : url.trim()
+        if (pushProvider) {
+            const fieldError = pushFieldError(pushProvider)
+            if (fieldError) {
+                await message(fieldError, {
+                    title: `Invalid ${providerMeta.label} configuration`,
+                    kind: 'warning',
+                })
+                return
+            }
+        }
+
+        let mergedUrl = url.trim()
+        if (isEmail) {
+            mergedUrl = buildEmailUrl(email)
+        } else if (isTelegram) {
+            mergedUrl = buildTelegramUrl(url, chatId)
+        } else if (pushProvider) {
+            mergedUrl = buildPushUrl(pushProvider)
+        }
 
         const validationError = validateWebhookUrl(provider, mergedUrl)
         if (validationError) {
@@ -198,6 +482,22 @@ export default function NotificationTargetDrawer({
             return
         }
 
+        if (provider === 'webhook' && signRequests && !signingSecret && !hasStoredSecret) {
+            await message('Enter or generate a signing secret, or turn request signing off.', {
+                title: 'Missing signing secret',
+                kind: 'warning',
+            })
+            return
+        }
+
+        // Left out when unchanged; null turns signing off.
+        let signingSecretPatch: { signingSecret?: string | null } = {}
+        if (newSigningSecret) {
+            signingSecretPatch = { signingSecret: newSigningSecret }
+        } else if (provider === 'webhook' && !signRequests && hasStoredSecret) {
+            signingSecretPatch = { signingSecret: null }
+        }
+
         try {
             if (isEditing) {
                 await updateNotificationTarget(target.id, {
@@ -205,6 +505,17 @@ export default function NotificationTargetDrawer({
                     url: mergedUrl,
                     events,
                     isEnabled,
+                    filters: activeFilters,
+                    delivery: activeDelivery,
+                    // Only webhooks carry one; null removes a template that was switched off.
+                    ...(provider === 'webhook' && {
+                        template: activeTemplate ?? null,
+                        headers: activeHeaders,
+                        auth: activeAuth ?? null,
+                    }),
+                    // An emptied username removes the login.
+                    ...(isEmail && { auth: activeAuth ?? null }),
+                    ...signingSecretPatch,
                 })
             } else {
                 await addNotificationTarget({
@@ -213,6 +524,12 @@ export default function NotificationTargetDrawer({
                     url: mergedUrl,
                     events,
                     isEnabled,
+                    filters: activeFilters,
+                    delivery: activeDelivery,
+                    template: activeTemplate,
+                    signingSecret: newSigningSecret,
+                    headers: activeHeaders,
+                    auth: activeAuth,
                 })
             }
         } catch (error) {
@@ -266,27 +583,171 @@ export default function NotificationTargetDrawer({
                                         autoCorrect="off"
                                         spellCheck="false"
                                     />
-                                    <Input
-                                        label={isTelegram ? 'Bot URL' : 'Webhook URL'}
-                                        labelPlacement="outside"
-                                        placeholder={providerMeta.urlPlaceholder}
-                                        value={url}
-                                        onValueChange={setUrl}
-                                        onBlur={() => setUrlTouched(true)}
-                                        isRequired={true}
-                                        isInvalid={!!urlError}
-                                        errorMessage={urlError}
-                                        description={
-                                            isPlaintextUrl
-                                                ? 'Unencrypted URL — the webhook payload will be sent in plaintext.'
-                                                : undefined
-                                        }
-                                        autoCapitalize="off"
-                                        autoComplete="off"
-                                        autoCorrect="off"
-                                        spellCheck="false"
-                                        type="url"
-                                    />
+                                    {isEmail && (
+                                        <>
+                                            <div className="flex gap-4">
+                                                <Input
+                                                    label="SMTP Server"
+                                                    labelPlacement="outside"
+                                                    placeholder={providerMeta.urlPlaceholder}
+                                                    value={email.host}
+                                                    onValueChange={(host) => updateEmail({ host })}
+                                                    isRequired={true}
+                                                    autoCapitalize="off"
+                                                    autoComplete="off"
+                                                    autoCorrect="off"
+                                                    spellCheck="false"
+                                                />
+                                                <Input
+                                                    label="Port"
+                                                    labelPlacement="outside"
+                                                    placeholder="Default"
+                                                    className="max-w-28"
+                                                    value={email.port}
+                                                    onValueChange={(port) => updateEmail({ port })}
+                                                    inputMode="numeric"
+                                                />
+                                            </div>
+                                            <Select
+                                                label="Security"
+                                                labelPlacement="outside"
+                                                description={
+                                                    email.security === 'none'
+                                                        ? 'The login and the mail travel unencrypted.'
+                                                        : undefined
+                                                }
+                                                selectedKeys={[email.security]}
+                                                onSelectionChange={(keys) => {
+                                                    const security = keys.currentKey
+                                                    if (security) {
+                                                        updateEmail({
+                                                            security: security as EmailSecurity,
+                                                        })
+                                                    }
+                                                }}
+                                                items={EMAIL_SECURITY_OPTIONS}
+                                            >
+                                                {(item) => (
+                                                    <SelectItem key={item.key}>
+                                                        {item.label}
+                                                    </SelectItem>
+                                                )}
+                                            </Select>
+                                            <div className="flex gap-4">
+                                                <Input
+                                                    label="Username"
+                                                    labelPlacement="outside"
+                                                    placeholder="Leave empty if the server needs no login"
+                                                    value={email.username}
+                                                    onValueChange={(username) =>
+                                                        updateEmail({ username })
+                                                    }
+                                                    autoCapitalize="off"
+                                                    autoComplete="off"
+                                                    autoCorrect="off"
+                                                    spellCheck="false"
+                                                />
+                                                <Input
+                                                    label="Password"
+                                                    labelPlacement="outside"
+                                                    placeholder={
+                                                        hasStoredEmailPassword
+                                                            ? 'Stored — enter a new one to replace it'
+                                                            : undefined
+                                                    }
+                                                    type="password"
+                                                    value={email.password}
+                                                    onValueChange={(password) =>
+                                                        updateEmail({ password })
+                                                    }
+                                                    autoComplete="off"
+                                                />
+                                            </div>
+                                            <Input
+                                                label="From"
+                                                labelPlacement="outside"
+                                                placeholder="Rclone UI <alerts@example.com>"
+                                                value={email.from}
+                                                onValueChange={(from) => updateEmail({ from })}
+                                                isRequired={true}
+                                                autoCapitalize="off"
+                                                autoComplete="off"
+                                                autoCorrect="off"
+                                                spellCheck="false"
+                                            />
+                                            <Input
+                                                label="To"
+                                                labelPlacement="outside"
+                                                placeholder="ops@example.com, oncall@example.com"
+                                                description="Separate multiple recipients with commas."
+                                                value={email.to}
+                                                onValueChange={(to) => updateEmail({ to })}
+                                                isRequired={true}
+                                                autoCapitalize="off"
+                                                autoComplete="off"
+                                                autoCorrect="off"
+                                                spellCheck="false"
+                                            />
+                                        </>
+                                    )}
+                                    {!isEmail && (
+                                        <Input
+                                            label={urlLabel}
+                                            labelPlacement="outside"
+                                            placeholder={providerMeta.urlPlaceholder}
+                                            value={url}
+                                            onValueChange={setUrl}
+                                            onBlur={() => setUrlTouched(true)}
+                                            isRequired={true}
+                                            isInvalid={!!urlError}
+                                            errorMessage={urlError}
+                                            description={
+                                                isPlaintextUrl
+                                                    ? 'Unencrypted URL — the webhook payload will be sent in plaintext.'
+                                                    : undefined
+                                            }
+                                            autoCapitalize="off"
+                                            autoComplete="off"
+                                            autoCorrect="off"
+                                            spellCheck="false"
+                                            type="url"
+                                        />
+                                    )}
+                                    {provider === 'matrix' && (
+                                        <Input
+                                            label="Room ID"
+                                            labelPlacement="outside"
+                                            placeholder="!abc123:example.org"
+                                            value={roomId}
+                                            onValueChange={setRoomId}
+                                            isRequired={true}
+                                            isInvalid={!!roomIdError}
+                                            errorMessage={roomIdError}
+                                            description="Room settings → Advanced. The account behind the token must have joined the room."
+                                            autoCapitalize="off"
+                                            autoComplete="off"
+                                            autoCorrect="off"
+                                            spellCheck="false"
+                                        />
+                                    )}
+                                    {!!pushProvider && (
+                                        <Input
+                                            label={
+                                                pushProvider === 'gotify'
+                                                    ? 'App Token'
+                                                    : 'Access Token'
+                                            }
+                                            labelPlacement="outside"
+                                            placeholder={PUSH_TOKEN_PLACEHOLDERS[pushProvider]}
+                                            value={token}
+                                            onValueChange={setToken}
+                                            isRequired={pushProvider !== 'ntfy'}
+                                            isInvalid={!!tokenError}
+                                            errorMessage={tokenError}
+                                            type="password"
+                                            autoComplete="off"
+                                        />
+                                    )}
                                     {isTelegram && (
                                         <Input
                                             label="Chat ID"
@@ -307,6 +768,278 @@ export default function NotificationTargetDrawer({
                                     )}
                                 </section>
 
+                                {provider === 'webhook' && (
+                                    <section className="flex flex-col gap-4">
+                                        <Select
+                                            label="Authentication"
+                                            labelPlacement="outside"
+                                            selectedKeys={[authMode]}
+                                            onSelectionChange={(keys) => {
+                                                const mode = keys.currentKey as
+                                                    | TargetAuthMode
+                                                    | 'none'
+                                                    | undefined
+                                                if (mode) {
+                                                    setAuthMode(mode)
+                                                    setAuthSecret('')
+                                                }
+                                            }}
+                                            items={AUTH_OPTIONS}
+                                        >
+                                            {(item) => (
+                                                <SelectItem key={item.key}>{item.label}</SelectItem>
+                                            )}
+                                        </Select>
+                                        {authMode !== 'none' && (
+                                            <div className="flex gap-4">
+                                                {authMode === 'basic' && (
+                                                    <Input
+                                                        label="Username"
+                                                        labelPlacement="outside"
+                                                        value={authUsername}
+                                                        onValueChange={setAuthUsername}
+                                                        isRequired={true}
+                                                        autoCapitalize="off"
+                                                        autoComplete="off"
+                                                        autoCorrect="off"
+                                                        spellCheck="false"
+                                                    />
+                                                )}
+                                                {authMode === 'header' && (
+                                                    <Input
+                                                        label="Header"
+                                                        labelPlacement="outside"
+                                                        placeholder="X-Api-Key"
+                                                        value={authHeader}
+                                                        onValueChange={setAuthHeader}
+                                                        isRequired={true}
+                                                        autoCapitalize="off"
+                                                        autoComplete="off"
+                                                        autoCorrect="off"
+                                                        spellCheck="false"
+                                                    />
+                                                )}
+                                                <Input
+                                                    label={AUTH_SECRET_LABELS[authMode]}
+                                                    labelPlacement="outside"
+                                                    placeholder={
+                                                        hasStoredAuthSecret
+                                                            ? 'Stored — enter a new one to replace it'
+                                                            : undefined
+                                                    }
+                                                    type="password"
+                                                    value={authSecret}
+                                                    onValueChange={setAuthSecret}
+                                                    isRequired={
+                                                        authMode !== 'basic' && !hasStoredAuthSecret
+                                                    }
+                                                    autoComplete="off"
+                                                />
+                                            </div>
+                                        )}
+                                        <div className="flex items-center justify-between">
+                                            <p className="text-small">Headers</p>
+                                            <Button
+                                                size="sm"
+                                                variant="light"
+                                                startContent={<PlusIcon className="size-4" />}
+                                                onPress={() =>
+                                                    setCustomHeaders((current) => [
+                                                        ...current,
+                                                        { name: '', value: '' },
+                                                    ])
+                                                }
+                                                data-focus-visible="false"
+                                            >
+                                                Add header
+                                            </Button>
+                                        </div>
+                                        {customHeaders.map((header, index) => (
+                                            <div
+                                                key={index}
+                                                className="flex items-center gap-2"
+                                            >
+                                                <Input
+                                                    aria-label="Header name"
+                                                    placeholder="X-Tenant"
+                                                    value={header.name}
+                                                    onValueChange={(name) =>
+                                                        updateHeader(index, { name })
+                                                    }
+                                                    autoCapitalize="off"
+                                                    autoComplete="off"
+                                                    autoCorrect="off"
+                                                    spellCheck="false"
+                                                />
+                                                <Input
+                                                    aria-label="Header value"
+                                                    placeholder="Value"
+                                                    value={header.value}
+                                                    onValueChange={(value) =>
+                                                        updateHeader(index, { value })
+                                                    }
+                                                    autoCapitalize="off"
+                                                    autoComplete="off"
+                                                    autoCorrect="off"
+                                                    spellCheck="false"
+                                                />
+                                                <Button
+                                                    isIconOnly={true}
+                                                    size="sm"
+                                                    variant="light"
+                                                    aria-label="Remove header"
+                                                    onPress={() =>
+                                                        setCustomHeaders((current) =>
+                                                            current.filter((_, i) => i !== index)
+                                                        )
+                                                    }
+                                                    data-focus-visible="false"
+                                                >
+                                                    <Trash2Icon className="size-4" />
+                                                </Button>
+                                            </div>
+                                        ))}
+                                        <Switch
+                                            size="sm"
+                                            color="primary"
+                                            isSelected={signRequests}
+                                            onValueChange={setSignRequests}
+                                            data-focus-visible="false"
+                                        >
+                                            <div className="flex flex-col">
+                                                <span className="text-small">Sign requests</span>
+                                                <span className="text-tiny text-default-400">
+                                                    Let the receiver verify that requests come
+                                                    from this machine (HMAC-SHA256)
+                                                </span>
+                                            </div>
+                                        </Switch>
+                                        {signRequests && (
+                                            <Input
+                                                label="Signing Secret"
+                                                labelPlacement="outside"
+                                                placeholder={
+                                                    hasStoredSecret
+                                                        ? 'Stored — enter a new secret to replace it'
+                                                        : 'At least 16 characters'
+                                                }
+                                                description={SIGNING_HELP}
+                                                value={signingSecret}
+                                                onValueChange={setSigningSecret}
+                                                endContent={
+                                                    <Button
+                                                        size="sm"
+                                                        variant="flat"
+                                                        onPress={() =>
+                                                            setSigningSecret(
+                                                                generateSigningSecret()
+                                                            )
+                                                        }
+                                                        data-focus-visible="false"
+                                                    >
+                                                        Generate
+                                                    </Button>
+                                                }
+                                                classNames={{ input: 'font-mono' }}
+                                                autoCapitalize="off"
+                                                autoComplete="off"
+                                                autoCorrect="off"
+                                                spellCheck="false"
+                                            />
+                                        )}
+                                        <Switch
+                                            size="sm"
+                                            color="primary"
+                                            isSelected={useTemplate}
+                                            onValueChange={setUseTemplate}
+                                            data-focus-visible="false"
+                                        >
+                                            <div className="flex flex-col">
+                                                <span className="text-small">Custom payload</span>
+                                                <span className="text-tiny text-default-400">
+                                                    Send your own body instead of the Rclone UI
+                                                    payload, e.g. for PagerDuty or Opsgenie
+                                                </span>
+                                            </div>
+                                        </Switch>
+                                        {useTemplate && (
+                                            <>
+                                                <div className="flex gap-4">
+                                                    <Select
+                                                        label="Method"
+                                                        labelPlacement="outside"
+                                                        className="max-w-32"
+                                                        selectedKeys={[template.method]}
+                                                        onSelectionChange={(keys) => {
+                                                            const method = keys.currentKey as
+                                                                | PayloadTemplate['method']
+                                                                | undefined
+                                                            if (method) {
+                                                                updateTemplate({ method })
+                                                            }
+                                                        }}
+                                                        items={PAYLOAD_METHODS}
+                                                    >
+                                                        {(item) => (
+                                                            <SelectItem key={item.key}>
+                                                                {item.key}
+                                                            </SelectItem>
+                                                        )}
+                                                    </Select>
+                                                    <Input
+                                                        label="Content Type"
+                                                        labelPlacement="outside"
+                                                        placeholder="application/json"
+                                                        value={template.contentType}
+                                                        onValueChange={(contentType) =>
+                                                            updateTemplate({ contentType })
+                                                        }
+                                                        isRequired={true}
+                                                        autoCapitalize="off"
+                                                        autoComplete="off"
+                                                        autoCorrect="off"
+                                                        spellCheck="false"
+                                                    />
+                                                </div>
+                                                <Textarea
+                                                    label="Body"
+                                                    labelPlacement="outside"
+                                                    description={PAYLOAD_TEMPLATE_HELP}
+                                                    minRows={6}
+                                                    value={template.body}
+                                                    onValueChange={(body) =>
+                                                        updateTemplate({ body })
+                                                    }
+                                                    classNames={{ input: 'font-mono' }}
+                                                    data-focus-visible="false"
+                                                />
+                                                <Button
+                                                    className="self-start"
+                                                    size="sm"
+                                                    variant="flat"
+                                                    isLoading={previewMutation.isPending}
+                                                    onPress={() => previewMutation.mutate()}
+                                                    data-focus-visible="false"
+                                                >
+                                                    Preview
+                                                </Button>
+                                                {previewMutation.isError && (
+                                                    <p className="text-tiny text-danger">
+                                                        {previewMutation.error instanceof Error
+                                                            ? previewMutation.error.message
+                                                            : String(previewMutation.error)}
+                                                    </p>
+                                                )}
+                                                {previewMutation.isSuccess && (
+                                                    <pre className="p-3 font-mono whitespace-pre-wrap break-all text-tiny rounded-medium bg-default-100">
+                                                        {previewMutation.data}
+                                                    </pre>
+                                                )}
+                                            </>
+                                        )}
+                                    </section>
+                                )}
+
                                 {!!providerMeta.helpUrl && (
                                     <Button
                                         fullWidth={true}
@@ -348,9 +1081,10 @@ export default function NotificationTargetDrawer({
                                     </div>
                                     <CheckboxGroup
                                         value={events}
-                                        onValueChange={(value) =>
+                                        onValueChange={(value) => {
                                             setEvents(value as NotificationEventId[])
-                                        }
+                                            filterPreviewMutation.reset()
+                                        }}
                                         aria-label="Events that trigger this webhook"
                                     >
                                         <div className="flex flex-col gap-6">
@@ -388,6 +1122,202 @@ export default function NotificationTargetDrawer({
                                     </CheckboxGroup>
                                 </section>
 
+                                <section className="flex flex-col gap-4">
+                                    <div className="flex flex-col gap-1">
+                                        <p className="text-sm font-semibold uppercase text-default-500">
+                                            Filters
+                                        </p>
+                                        <p className="text-tiny text-default-400">
+                                            Only deliver the events above when they match every
+                                            filter you set. Leave a filter empty to match all.
+                                        </p>
+                                    </div>
+                                    <Select
+                                        label="Scheduled tasks"
+                                        labelPlacement="outside"
+                                        placeholder="All tasks"
+                                        selectionMode="multiple"
+                                        selectedKeys={scheduleIds}
+                                        onSelectionChange={(keys) => {
+                                            setScheduleIds(Array.from(keys, String))
+                                            filterPreviewMutation.reset()
+                                        }}
+                                        items={scheduledTasks}
+                                    >
+                                        {(task) => (
+                                            <SelectItem key={task.id}>
+                                                {task.name || task.operation.toUpperCase()}
+                                            </SelectItem>
+                                        )}
+                                    </Select>
+                                    <div className="flex gap-4">
+                                        <Select
+                                            label="Operations"
+                                            labelPlacement="outside"
+                                            placeholder="All operations"
+                                            selectionMode="multiple"
+                                            selectedKeys={operations}
+                                            onSelectionChange={(keys) => {
+                                                setOperations(Array.from(keys, String))
+                                                filterPreviewMutation.reset()
+                                            }}
+                                            items={FILTER_OPERATIONS}
+                                        >
+                                            {(item) => (
+                                                <SelectItem key={item.key}>{item.label}</SelectItem>
+                                            )}
+                                        </Select>
+                                        <Select
+                                            label="Minimum severity"
+                                            labelPlacement="outside"
+                                            selectedKeys={[minSeverity]}
+                                            onSelectionChange={(keys) => {
+                                                const severity = keys.currentKey as
+                                                    | NotificationSeverity
+                                                    | 'any'
+                                                    | undefined
+                                                if (severity) {
+                                                    setMinSeverity(severity)
+                                                    filterPreviewMutation.reset()
+                                                }
+                                            }}
+                                            items={SEVERITY_OPTIONS}
+                                        >
+                                            {(item) => (
+                                                <SelectItem key={item.key}>{item.label}</SelectItem>
+                                            )}
+                                        </Select>
+                                    </div>
+                                    <Input
+                                        label="Remotes"
+                                        labelPlacement="outside"
+                                        placeholder="All remotes"
+                                        value={remotePatterns}
+                                        onValueChange={(value) => {
+                                            setRemotePatterns(value)
+                                            filterPreviewMutation.reset()
+                                        }}
+                                        description="Comma-separated remote names; * and ? match any characters, e.g. prod-*, gdrive"
+                                        autoCapitalize="off"
+                                        autoComplete="off"
+                                        autoCorrect="off"
+                                        spellCheck="false"
+                                    />
+                                    <Button
+                                        size="sm"
+                                        variant="flat"
+                                        className="self-start"
+                                        isLoading={filterPreviewMutation.isPending}
+                                        onPress={() => filterPreviewMutation.mutate()}
+                                        data-focus-visible="false"
+                                    >
+                                        Preview Recent Events
+                                    </Button>
+                                    {filterPreviewMutation.isError && (
+                                        <p className="text-tiny text-danger">
+                                            {String(filterPreviewMutation.error)}
+                                        </p>
+                                    )}
+                                    {filterPreviewMutation.data?.length === 0 && (
+                                        <p className="text-tiny text-default-400">
+                                            No events have been sent yet.
+                                        </p>
+                                    )}
+                                    {!!filterPreviewMutation.data?.length && (
+                                        <div className="flex flex-col gap-1.5">
+                                            {filterPreviewMutation.data
+                                                .slice(0, 15)
+                                                .map((event) => (
+                                                    <div
+                                                        key={`${event.timestamp}-${event.eventId}`}
+                                                        className={cn(
+                                                            'flex items-center gap-2 text-small',
+                                                            !event.matched && 'opacity-50'
+                                                        )}
+                                                    >
+                                                        {event.matched ? (
+                                                            <CheckIcon className="size-4 shrink-0 text-success" />
+                                                        ) : (
+                                                            <MinusIcon className="size-4 shrink-0" />
+                                                        )}
+                                                        <span className="truncate">
+                                                            {event.body || event.title}
+                                                        </span>
+                                                        <span className="ml-auto shrink-0 text-tiny text-default-400">
+                                                            {new Date(
+                                                                event.timestamp
+                                                            ).toLocaleString()}
+                                                        </span>
+                                                    </div>
+                                                ))}
+                                        </div>
+                                    )}
+                                </section>
+
+                                <section className="flex flex-col gap-4">
+                                    <p className="text-sm font-semibold uppercase text-default-500">
+                                        Delivery
+                                    </p>
+                                    <Select
+                                        label="Send"
+                                        labelPlacement="outside"
+                                        selectedKeys={[deliveryMode]}
+                                        onSelectionChange={(keys) => {
+                                            const mode = keys.currentKey as
+                                                | TargetDelivery['mode']
+                                                | undefined
+                                            if (mode) setDeliveryMode(mode)
+                                        }}
+                                        items={DELIVERY_MODES}
+                                    >
+                                        {(item) => (
+                                            <SelectItem
+                                                key={item.key}
+                                                description={item.description}
+                                            >
+                                                {item.label}
+                                            </SelectItem>
+                                        )}
+                                    </Select>
+                                    {deliveryMode === 'deduplicate' && (
+                                        <Select
+                                            label="Hold back repeats for"
+                                            labelPlacement="outside"
+                                            description="Repeats of the same event for the same task are counted, not sent, and summed up in one follow-up when this window ends."
+                                            selectedKeys={[dedupeWindow]}
+                                            onSelectionChange={(keys) => {
+                                                if (keys.currentKey) {
+                                                    setDedupeWindow(String(keys.currentKey))
+                                                }
+                                            }}
+                                            items={DEDUPE_WINDOWS}
+                                        >
+                                            {(item) => (
+                                                <SelectItem key={item.key}>{item.label}</SelectItem>
+                                            )}
+                                        </Select>
+                                    )}
+                                    {deliveryMode === 'digest' && (
+                                        <Select
+                                            label="Send a summary"
+                                            labelPlacement="outside"
+                                            description="Summaries go out while Rclone UI is open, or with the next scheduled task run."
+                                            selectedKeys={[digestInterval]}
+                                            onSelectionChange={(keys) => {
+                                                const interval = keys.currentKey as
+                                                    | TargetDelivery['interval']
+                                                    | undefined
+                                                if (interval) setDigestInterval(interval)
+                                            }}
+                                            items={DIGEST_INTERVALS}
+                                        >
+                                            {(item) => (
+                                                <SelectItem key={item.key}>{item.label}</SelectItem>
+                                            )}
+                                        </Select>
+                                    )}
+                                </section>
+
                                 <section className="flex flex-col gap-4">
                                     <Switch
                                         size="sm"
@@ -13,6 +13,7 @@ import {
     Switch,
     Tab,
     Tabs,
+    Textarea,
     Tooltip,
     cn,
 } from '@heroui/react'
@@ -26,8 +27,10 @@ import { useNow } from '../../lib/hooks'
 import {
     DEFAULT_MAX_RUN_HOURS,
     MAX_RUN_HOURS_LIMIT,
+    parseEnvLines,
     schedulerReadHistory,
     schedulerReadLog,
+    schedulerReadRunLog,
     updateScheduledTask as schedulerUpdateTask,
     schedulerValidateCron,
 } from '../../lib/scheduler'
@@ -37,6 +40,23 @@ import BinarySelect from './BinarySelect'
 import ConfigSelect, { configPasswordMissing as isConfigPasswordMissing } from './ConfigSelect'
 import CronEditor from './CronEditor'
 
+type BisyncResync = NonNullable<ScheduledTask['bisyncResync']>
+
+function envText(task: ScheduledTask) {
+    return Object.entries(task.env ?? {})
+        .map(([key, value]) => `${key}=${value}`)
+        .join('\n')
+}
+
+/** Stored secrets are listed by name only — an empty value keeps the stored one. */
+function secretEnvText(task: ScheduledTask) {
+    return (task.secretEnvKeys ?? []).map((key) => `${key}=`).join('\n')
+}
+
+function daemonFlagsText(task: ScheduledTask) {
+    return (task.daemonFlags ?? []).join('\n')
+}
+
 export default function ScheduleEditDrawer({
     isOpen,
     onClose,
@@ -56,10 +76,18 @@ export default function ScheduleEditDrawer({
     const [isEnabled, setIsEnabled] = useState(selectedTask.isEnabled)
     const [verboseLogging, setVerboseLogging] = useState(selectedTask.verboseLogging ?? false)
     const [runMode, setRunMode] = useState<'system' | 'user'>(selectedTask.runMode ?? 'user')
+    const [bisyncResync, setBisyncResync] = useState<BisyncResync>(
+        selectedTask.bisyncResync ?? 'never'
+    )
     const [maxRunHours, setMaxRunHours] = useState(
         String(selectedTask.maxRunHours ?? DEFAULT_MAX_RUN_HOURS)
     )
+    const [env, setEnv] = useState(envText(selectedTask))
+    const [secretEnv, setSecretEnv] = useState(secretEnvText(selectedTask))
+    const [daemonFlags, setDaemonFlags] = useState(daemonFlagsText(selectedTask))
     const [logView, setLogView] = useState<'runner' | 'daemon'>('runner')
+    // The run whose runner-log records are shown instead of the log's tail — null for the tail.
+    const [logRunId, setLogRunId] = useState<string | null>(null)
     const [saveError, setSaveError] = useState<string | null>(null)
 
     useEffect(() => {
@@ -71,7 +99,12 @@ export default function ScheduleEditDrawer({
             setIsEnabled(selectedTask.isEnabled)
             setVerboseLogging(selectedTask.verboseLogging ?? false)
             setRunMode(selectedTask.runMode ?? 'user')
+            setBisyncResync(selectedTask.bisyncResync ?? 'never')
             setMaxRunHours(String(selectedTask.maxRunHours ?? DEFAULT_MAX_RUN_HOURS))
+            setEnv(envText(selectedTask))
+            setSecretEnv(secretEnvText(selectedTask))
+            setDaemonFlags(daemonFlagsText(selectedTask))
+            setLogRunId(null)
             setSaveError(null)
         }
     }, [isOpen, selectedTask])
@@ -150,7 +183,11 @@ export default function ScheduleEditDrawer({
             isEnabled !== selectedTask.isEnabled ||
             verboseLogging !== (selectedTask.verboseLogging ?? false) ||
             runMode !== (selectedTask.runMode ?? 'user') ||
-            maxRunHoursNumber !== (selectedTask.maxRunHours ?? DEFAULT_MAX_RUN_HOURS),
+            bisyncResync !== (selectedTask.bisyncResync ?? 'never') ||
+            maxRunHoursNumber !== (selectedTask.maxRunHours ?? DEFAULT_MAX_RUN_HOURS) ||
+            env !== envText(selectedTask) ||
+            secretEnv !== secretEnvText(selectedTask) ||
+            daemonFlags !== daemonFlagsText(selectedTask),
         [
             name,
             cronExpression,
@@ -159,7 +196,11 @@ export default function ScheduleEditDrawer({
             isEnabled,
             verboseLogging,
             runMode,
+            bisyncResync,
             maxRunHoursNumber,
+            env,
+            secretEnv,
+            daemonFlags,
             selectedTask,
         ]
     )
@@ -171,19 +212,45 @@ export default function ScheduleEditDrawer({
         refetchInterval: 5_000,
     })
 
+    const runLogQuery = useQuery({
+        queryKey: ['scheduler', 'runLog', selectedTask.id, logRunId],
+        queryFn: () => schedulerReadRunLog(selectedTask.id, logRunId!),
+        enabled: isOpen && logView === 'runner' && !!logRunId,
+    })
+
+    const runLogContent = runLogQuery.data
+        ?.map(
+            (record) =>
+                `[${record.ts}] ${record.level.toUpperCase()} ${record.phase}: ${record.message}`
+        )
+        .join('\n')
+
     const saveMutation = useMutation({
         mutationFn: async () => {
             setSaveError(null)
-            await schedulerUpdateTask(selectedTask.id, {
-                name: name.trim(),
-                cron: cronExpression,
-                configId,
-                binaryPath,
-                isEnabled,
-                verboseLogging,
-                runMode,
-                maxRunHours: maxRunHoursNumber,
-            })
+            const secrets = parseEnvLines(secretEnv)
+            await schedulerUpdateTask(
+                selectedTask.id,
+                {
+                    name: name.trim(),
+                    cron: cronExpression,
+                    configId,
+                    binaryPath,
+                    isEnabled,
+                    verboseLogging,
+                    runMode,
+                    bisyncResync,
+                    maxRunHours: maxRunHoursNumber,
+                    env: parseEnvLines(env),
+                    secretEnvKeys: Object.keys(secrets),
+                    daemonFlags: daemonFlags
+                        .split('\n')
+                        .map((flag) => flag.trim())
+                        .filter(Boolean),
+                },
+                // Only the values actually typed in — empty ones keep what is stored.
+                Object.fromEntries(Object.entries(secrets).filter(([, value]) => value !== ''))
+            )
         },
         onSuccess: () => {
             queryClient.invalidateQueries({ queryKey: ['scheduler'] })
@@ -461,6 +528,73 @@ export default function ScheduleEditDrawer({
                                                 />
                                             </div>
                                         </div>
+
+                                        {selectedTask.operation === 'bisync' && (
+                                            <div className="flex flex-row justify-center w-full gap-8">
+                                                <div className="flex flex-col items-end flex-1 gap-2">
+                                                    <h4 className="font-medium">Resync</h4>
+                                                </div>
+                                                <div className="flex flex-col w-3/5 gap-1">
+                                                    <Tabs
+                                                        size="sm"
+                                                        selectedKey={bisyncResync}
+                                                        onSelectionChange={(key) =>
+                                                            setBisyncResync(key as BisyncResync)
+                                                        }
+                                                        data-focus-visible="false"
+                                                    >
+                                                        <Tab key="never" title="Never" />
+                                                        <Tab key="first-run" title="First run" />
+                                                        <Tab key="always" title="After errors" />
+                                                    </Tabs>
+                                                    <span className="text-tiny text-default-400">
+                                                        {bisyncResync === 'never'
+                                                            ? 'A run that needs a resync fails, and you resync it by hand.'
+                                                            : bisyncResync === 'first-run'
+                                                              ? 'The first run resyncs automatically. After that, a run that needs a resync fails.'
+                                                              : 'Runs resync automatically whenever bisync requires it, including after a critical error. Files deleted since the last good run may come back.'}
+                                                    </span>
+                                                </div>
+                                            </div>
+                                        )}
+
+                                        <div className="flex flex-row justify-center w-full gap-8">
+                                            <div className="flex flex-col items-end flex-1 gap-2">
+                                                <h4 className="font-medium">Environment</h4>
+                                            </div>
+                                            <div className="flex flex-col w-3/5 gap-3">
+                                                <Textarea
+                                                    label="Variables"
+                                                    placeholder="RCLONE_TRANSFERS=8"
+                                                    description="One KEY=value per line, passed to this task's rclone"
+                                                    minRows={2}
+                                                    value={env}
+                                                    onValueChange={setEnv}
+                                                    classNames={{ input: 'font-mono' }}
+                                                    data-focus-visible="false"
+                                                />
+                                                <Textarea
+                                                    label="Secrets"
+                                                    placeholder="RCLONE_S3_SECRET_ACCESS_KEY=…"
+                                                    description="Stored separately and never shown again. Leave a value empty to keep the stored one"
+                                                    minRows={2}
+                                                    value={secretEnv}
+                                                    onValueChange={setSecretEnv}
+                                                    classNames={{ input: 'font-mono' }}
+                                                    data-focus-visible="false"
+                                                />
+                                                <Textarea
+                                                    label="Flags"
+                                                    placeholder="--fast-list"
+                                                    description="One --flag or --flag=value per line"
+                                                    minRows={2}
+                                                    value={daemonFlags}
+                                                    onValueChange={setDaemonFlags}
+                                                    classNames={{ input: 'font-mono' }}
+                                                    data-focus-visible="false"
+                                                />
+                                            </div>
+                                        </div>
                                     </div>
 
                                     <Divider />
@@ -473,6 +607,7 @@ export default function ScheduleEditDrawer({
                                                 setCronExpression(expr || '* * * * *')
                                             }
                                             error={cronError}
+                                            description={cronValidation.data?.description}
                                         />
                                     </div>
 
@@ -545,11 +680,31 @@ export default function ScheduleEditDrawer({
                                                             <span className="text-foreground-500">
                                                                 {Math.round(run.durationMs / 1000)}s
                                                             </span>
+                                                            {!!run.stats?.conflicts && (
+                                                                <span className="text-warning-500">
+                                                                    {run.stats.conflicts}{' '}
+                                                                    {run.stats.conflicts === 1
+                                                                        ? 'conflict'
+                                                                        : 'conflicts'}
+                                                                </span>
+                                                            )}
                                                             {!!run.error && (
                                                                 <span className="text-danger-500 line-clamp-1">
                                                                     {run.error}
                                                                 </span>
                                                             )}
+                                                            <Button
+                                                                size="sm"
+                                                                variant="light"
+                                                                className="ml-auto shrink-0"
+                                                                onPress={() => {
+                                                                    setLogView('runner')
+                                                                    setLogRunId(run.runId)
+                                                                }}
+                                                                data-focus-visible="false"
+                                                            >
+                                                                Log
+                                                            </Button>
                                                         </div>
                                                     ) : null
                                                 )}
@@ -575,7 +730,10 @@ export default function ScheduleEditDrawer({
                                                     color={
                                                         logView === 'runner' ? 'primary' : 'default'
                                                     }
-                                                    onPress={() => setLogView('runner')}
+                                                    onPress={() => {
+                                                        setLogView('runner')
+                                                        setLogRunId(null)
+                                                    }}
                                                     data-focus-visible="false"
                                                 >
                                                     Runner
@@ -595,16 +753,36 @@ export default function ScheduleEditDrawer({
                                                 </Button>
                                             </div>
                                         </div>
-                                        {logQuery.data?.truncated && (
+                                        {logView === 'runner' && logRunId ? (
                                             <p className="text-tiny text-default-400">
-                                                Showing the last 64 KB — older lines are on disk.
+                                                Showing run {logRunId} only —{' '}
+                                                <button
+                                                    type="button"
+                                                    className="underline"
+                                                    onClick={() => setLogRunId(null)}
+                                                >
+                                                    show the full log
+                                                </button>
+                                                .
                                             </p>
+                                        ) : (
+                                            logQuery.data?.truncated && (
+                                                <p className="text-tiny text-default-400">
+                                                    Showing the last 64 KB — older lines are on
+                                                    disk.
+                                                </p>
+                                            )
                                         )}
                                         <pre className="p-3 overflow-auto font-mono whitespace-pre-wrap rounded-medium bg-content2 text-tiny max-h-64">
-                                            {logQuery.data?.content ||
-                                                (logView === 'runner'
-                                                    ? 'No runner output yet.'
-                                                    : 'No rclone output yet.')}
+                                            {logView === 'runner' && logRunId
+                                                ? runLogContent ||
+                                                  (runLogQuery.isLoading
+                                                      ? 'Loading…'
+                                                      : 'No lines for this run — it may have been rotated out.')
+                                                : logQuery.data?.content ||
+                                                  (logView === 'runner'
+                                                      ? 'No runner output yet.'
+                                                      : 'No rclone output yet.')}
                                         </pre>
                                     </div>
                                 </div>
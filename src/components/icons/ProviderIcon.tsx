@@ -1,4 +1,12 @@
-import { WebhookIcon } from 'lucide-react'
+import {
+    BellRingIcon,
+    HashIcon,
+    MailIcon,
+    MessagesSquareIcon,
+    SmartphoneIcon,
+    UsersIcon,
+    WebhookIcon,
+} from 'lucide-react'
 import type { NotificationProvider } from '../../../types/notifications'
 
 export default function ProviderIcon({
@@ -17,6 +25,24 @@ export default function ProviderIcon({
     if (provider === 'telegram') {
         return <TelegramIcon className={className} />
     }
+    if (provider === 'teams') {
+        return <UsersIcon className={className} />
+    }
+    if (provider === 'googlechat') {
+        return <MessagesSquareIcon className={className} />
+    }
+    if (provider === 'matrix') {
+        return <HashIcon className={className} />
+    }
+    if (provider === 'ntfy') {
+        return <BellRingIcon className={className} />
+    }
+    if (provider === 'gotify') {
+        return <SmartphoneIcon className={className} />
+    }
+    if (provider === 'email') {
+        return <MailIcon className={className} />
+    }
     return <WebhookIcon className={className} />
 }
 
@@ -23,5 +23,12 @@ export default function CronSection({
             ? (validation.data.error ?? 'Invalid cron expression')
             : null
 
-    return <CronEditor expression={expression} onChange={onChange} error={error} />
+    return (
+        <CronEditor
+            expression={expression}
+            onChange={onChange}
+            error={error}
+            description={validation.data?.description}
+        />
+    )
 }
@@ -1,19 +1,56 @@
-import { Alert, Card, CardBody, CardHeader, Tooltip, useDisclosure } from '@heroui/react'
+import {
+    Alert,
+    Card,
+    CardBody,
+    CardHeader,
+    Dropdown,
+    DropdownItem,
+    DropdownMenu,
+    DropdownTrigger,
+    Tooltip,
+    useDisclosure,
+} from '@heroui/react'
 import { Button, Chip } from '@heroui/react'
 import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
-import { ask } from '@tauri-apps/plugin-dialog'
+import { ask, message, open, save } from '@tauri-apps/plugin-dialog'
 import { platform } from '@tauri-apps/plugin-os'
 import cronstrue from 'cronstrue'
 import { formatDistance } from 'date-fns'
-import { AlertCircleIcon, Clock7Icon, PauseIcon, PlayIcon, Trash2Icon, ZapIcon } from 'lucide-react'
+import {
+    AlertCircleIcon,
+    Clock7Icon,
+    DownloadIcon,
+    FileCodeIcon,
+    KeyRoundIcon,
+    ListPlusIcon,
+    PauseIcon,
+    PlayIcon,
+    ShieldCheckIcon,
+    StethoscopeIcon,
+    Trash2Icon,
+    UploadIcon,
+    ZapIcon,
+} from 'lucide-react'
 import { useCallback, useMemo, useState } from 'react'
 import { onErrorDialog } from '../../lib/errors'
 import { buildReadablePath } from '../../lib/format'
 import { useNow } from '../../lib/hooks'
 import { LOCAL_HOST_ID } from '../../lib/hosts'
+import { reconnectRemote } from '../../lib/rclone/api'
 import {
+    type CrontabScan,
+    type SchedulerImportReport,
     type SchedulerTaskStatus,
+    adoptCrontabEntries,
+    adoptIntendedEnabled,
+    exportScheduledTasks,
+    exportTaskArtifact,
+    importScheduledTasks,
     removeScheduledTask as schedulerRemoveTask,
+    scanCrontab,
+    schedulerDoctor,
+    schedulerReconcileApply,
+    schedulerReconcileReport,
     schedulerRunNow,
     schedulerStatus,
     schedulerValidateCron,
@@ -43,7 +80,13 @@ export default function Schedules() {
 
     const statusQuery = useQuery({
         queryKey: ['scheduler', 'status'],
-        queryFn: () => schedulerStatus(LOCAL_HOST_ID),
+        queryFn: async () => {
+            const requestedAt = Date.now()
+            const statuses = await schedulerStatus(LOCAL_HOST_ID)
+            // Picks up a headless enable/disable while the app runs.
+            adoptIntendedEnabled(statuses, requestedAt)
+            return statuses
+        },
         enabled: schedulingAvailable,
         refetchInterval: 5_000,
         refetchOnWindowFocus: true,
@@ -71,6 +114,7 @@ export default function Schedules() {
                         : unavailableReason}
                 </h1>
                 {schedulingAvailable && <CommandsDropdown title="New scheduled task" />}
+                {schedulingAvailable && <ImportExportActions canExport={false} />}
             </div>
         )
     }
@@ -88,6 +132,11 @@ export default function Schedules() {
                     classNames={{ base: 'flex-shrink-0' }}
                 />
             )}
+            {schedulingAvailable && (
+                <div className="flex justify-end flex-shrink-0 px-4 py-2 border-b border-divider">
+                    <ImportExportActions canExport={true} />
+                </div>
+            )}
             {scheduledTasks.map((task) => (
                 <TaskCard
                     key={task.id}
@@ -104,6 +153,247 @@ export default function Schedules() {
     )
 }
 
+function describeImport(report: SchedulerImportReport) {
+    const lines = report.tasks.map((task) =>
+        task.imported
+            ? [`Imported "${task.name}"`, ...task.adaptations].join(' — ')
+            : `Skipped "${task.name}": ${task.error}`
+    )
+    for (const target of report.notificationTargets) {
+        lines.push(
+            target.imported
+                ? `Added the webhook "${target.name}"`
+                : `Skipped the webhook "${target.name}": ${target.error}`
+        )
+    }
+    return lines.length > 0 ? lines.join('\n') : 'The file contains no scheduled tasks.'
+}
+
+function describeCrontabScan(scan: CrontabScan) {
+    const sections: string[] = []
+    if (scan.candidates.length > 0) {
+        sections.push(
+            scan.candidates
+                .map((candidate) =>
+                    [`${candidate.cron}  ${candidate.name}`, ...candidate.notes].join('\n    ')
+                )
+                .join('\n')
+        )
+    }
+    if (scan.rejected.length > 0) {
+        sections.push(
+            `Can't be adopted:\n${scan.rejected
+                .map((rejected) => `${rejected.line}\n    ${rejected.reason}`)
+                .join('\n')}`
+        )
+    }
+    return sections.join('\n\n')
+}
+
+/** Bundle export/import (moving tasks to another machine) and adopting crontab rclone lines. */
+function ImportExportActions({ canExport }: { canExport: boolean }) {
+    const queryClient = useQueryClient()
+
+    const exportMutation = useMutation({
+        mutationFn: async () => {
+            const path = await save({
+                filters: [{ name: 'JSON', extensions: ['json'] }],
+                defaultPath: `schedules-${new Date().toISOString().replace(/[:.]/g, '-')}.json`,
+            })
+            if (!path) {
+                return
+            }
+            const report = await exportScheduledTasks(path)
+            const lines = [
+                `Exported ${report.tasks} task(s) and ${report.notificationTargets} webhook(s).`,
+            ]
+            if (report.skipped.length > 0) {
+                lines.push(`Never registered here, left out: ${report.skipped.join(', ')}`)
+            }
+            lines.push('The file contains your webhook URLs — keep it private.')
+            await message(lines.join('\n\n'), { title: 'Export', kind: 'info' })
+        },
+        onError: onErrorDialog('Export', 'Failed to export the schedules', { capture: false }),
+    })
+
+    const importMutation = useMutation({
+        mutationFn: async () => {
+            const path = await open({
+                filters: [{ name: 'JSON', extensions: ['json'] }],
+                multiple: false,
+                directory: false,
+            })
+            if (!path) {
+                return
+            }
+            const report = await importScheduledTasks(path)
+            await queryClient.invalidateQueries({ queryKey: ['scheduler'] })
+            await message(describeImport(report), { title: 'Import', kind: 'info' })
+        },
+        onError: onErrorDialog('Import', 'Failed to import the schedules', { capture: false }),
+    })
+
+    const adoptMutation = useMutation({
+        mutationFn: async () => {
+            const scan = await scanCrontab()
+            if (scan.candidates.length === 0) {
+                await message(
+                    scan.rejected.length > 0
+                        ? describeCrontabScan(scan)
+                        : 'Your crontab has no rclone lines.',
+                    { title: 'Crontab', kind: 'info' }
+                )
+                return
+            }
+            const confirmed = await ask(
+                `${describeCrontabScan(scan)}\n\nCreate scheduled tasks for these lines? Each line is commented out once its task is registered.`,
+                { title: 'Adopt crontab lines', kind: 'info', okLabel: 'Adopt' }
+            )
+            if (!confirmed) {
+                return
+            }
+            const failed = await adoptCrontabEntries(scan.candidates)
+            await queryClient.invalidateQueries({ queryKey: ['scheduler'] })
+            if (failed.length > 0) {
+                await message(
+                    failed.map((failure) => `${failure.line}\n    ${failure.error}`).join('\n'),
+                    { title: 'Some lines were not adopted', kind: 'warning' }
+                )
+            }
+        },
+        onError: onErrorDialog('Crontab', 'Failed to adopt the crontab lines', { capture: false }),
+    })
+
+    const checkMutation = useMutation({
+        mutationFn: async () => {
+            const report = await schedulerReconcileReport()
+            if (report.drifted.length === 0) {
+                await message(`All ${report.checked} task(s) are registered as defined.`, {
+                    title: 'Check registrations',
+                    kind: 'info',
+                })
+                return
+            }
+            const details = report.drifted
+                .map((task) => [task.name, ...task.problems].join('\n    '))
+                .join('\n')
+            const confirmed = await ask(`${details}\n\nRegister these tasks again?`, {
+                title: 'Check registrations',
+                kind: 'warning',
+                okLabel: 'Repair',
+            })
+            if (!confirmed) {
+                return
+            }
+            const repair = await schedulerReconcileApply()
+            await queryClient.invalidateQueries({ queryKey: ['scheduler'] })
+            if (repair.failed.length > 0) {
+                const lines = repair.failed.map((failure) => `${failure.taskId}: ${failure.error}`)
+                await message(lines.join('\n'), {
+                    title: 'Some tasks were not repaired',
+                    kind: 'warning',
+                })
+            }
+        },
+        onError: onErrorDialog('Check registrations', 'Failed to check the registrations', {
+            capture: false,
+        }),
+    })
+
+    const doctorMutation = useMutation({
+        mutationFn: async () => {
+            const report = await schedulerDoctor()
+            const findings = report.tasks
+                .map((task) => {
+                    const problems = task.checks
+                        .filter((check) => check.status === 'fail' || check.status === 'warn')
+                        .map((check) => {
+                            const mark = check.status === 'fail' ? '✗' : '!'
+                            return `${mark} ${check.label}: ${check.detail}`
+                        })
+                    return problems.length > 0 ? [task.name, ...problems].join('\n    ') : null
+                })
+                .filter((finding) => finding !== null)
+            if (findings.length === 0) {
+                await message(`All ${report.tasks.length} task(s) passed every check.`, {
+                    title: 'Preflight',
+                    kind: 'info',
+                })
+                return
+            }
+            await message(findings.join('\n'), {
+                title: 'Preflight',
+                kind: report.tasks.some((task) =>
+                    task.checks.some((check) => check.status === 'fail')
+                )
+                    ? 'error'
+                    : 'warning',
+            })
+        },
+        onError: onErrorDialog('Preflight', 'Failed to run the preflight checks', {
+            capture: false,
+        }),
+    })
+
+    return (
+        <div className="flex flex-row gap-2">
+            {canExport && (
+                <Button
+                    size="sm"
+                    variant="flat"
+                    startContent={<ShieldCheckIcon className="size-4" />}
+                    isLoading={checkMutation.isPending}
+                    onPress={() => checkMutation.mutate()}
+                >
+                    Check
+                </Button>
+            )}
+            {canExport && (
+                <Button
+                    size="sm"
+                    variant="flat"
+                    startContent={<StethoscopeIcon className="size-4" />}
+                    isLoading={doctorMutation.isPending}
+                    onPress={() => doctorMutation.mutate()}
+                >
+                    Preflight
+                </Button>
+            )}
+            {platform() !== 'windows' && (
+                <Button
+                    size="sm"
+                    variant="flat"
+                    startContent={<ListPlusIcon className="size-4" />}
+                    isLoading={adoptMutation.isPending}
+                    onPress={() => adoptMutation.mutate()}
+                >
+                    From crontab
+                </Button>
+            )}
+            <Button
+                size="sm"
+                variant="flat"
+                startContent={<DownloadIcon className="size-4" />}
+                isLoading={importMutation.isPending}
+                onPress={() => importMutation.mutate()}
+            >
+                Import
+            </Button>
+            {canExport && (
+                <Button
+                    size="sm"
+                    variant="flat"
+                    startContent={<UploadIcon className="size-4" />}
+                    isLoading={exportMutation.isPending}
+                    onPress={() => exportMutation.mutate()}
+                >
+                    Export
+                </Button>
+            )}
+        </div>
+    )
+}
+
 function TaskCard({
     task,
     status,
@@ -203,13 +493,54 @@ function TaskCard({
         onError: onErrorDialog('Schedule', 'Failed to remove the task', { capture: false }),
     })
 
+    const artifactMutation = useMutation({
+        mutationFn: async (kind: 'script' | 'systemd') => {
+            const dir = await open({ directory: true, multiple: false })
+            if (!dir) {
+                return
+            }
+            const paths = await exportTaskArtifact(task.id, kind, dir)
+            const hint =
+                kind === 'systemd'
+                    ? 'Copy the units to /etc/systemd/system, the env file to /etc, fill in any empty values, then run systemctl enable --now on the timer.'
+                    : 'Fill in the variables the script asks for, then add it to a crontab.'
+            await message(`Written:\n${paths.join('\n')}\n\n${hint}`, {
+                title: 'Export',
+                kind: 'info',
+            })
+        },
+        onError: onErrorDialog('Export', 'Failed to export the task', { capture: false }),
+    })
+
+    // An expired/revoked OAuth token: the fix is a reconnect, not a look at the logs. Only
+    // offered while that failure is still the latest run's outcome.
+    const reauthRemote =
+        !isRunning && lastFinished?.needsReauth && !lastFinished.success
+            ? lastFinished.reauthRemote
+            : undefined
+
+    const reconnectMutation = useMutation({
+        mutationFn: async (remoteName: string) => {
+            await reconnectRemote(remoteName)
+            await message(
+                `Remote "${remoteName}" has been reconnected. The next scheduled run will use the new sign-in.`,
+                { title: 'Reconnected', kind: 'info' }
+            )
+        },
+        onError: onErrorDialog('Reconnect', 'Failed to reconnect the remote', { capture: false }),
+    })
+
     const errorLine = task.registrationError
         ? `Not scheduled: ${task.registrationError}`
         : status?.warning
           ? status.warning
-          : !isRunning && lastFinished && !lastFinished.success
-            ? lastFinished.error || 'The last run failed'
-            : null
+          : !isRunning && lastFinished?.needsReauth && !lastFinished.success
+            ? reauthRemote
+                ? `Remote "${reauthRemote}" needs to be reconnected — its sign-in expired or was revoked`
+                : 'A remote needs to be reconnected — its sign-in expired or was revoked'
+            : !isRunning && lastFinished && !lastFinished.success
+              ? lastFinished.error || 'The last run failed'
+              : null
 
     return (
         <Card
@@ -377,6 +708,30 @@ function TaskCard({
                                 <PlayIcon className="w-4 h-4" />
                             )}
                         </Button>
+                        <Dropdown shadow={platform() === 'windows' ? 'none' : undefined}>
+                            <DropdownTrigger>
+                                <Button
+                                    isIconOnly={true}
+                                    variant="flat"
+                                    isDisabled={
+                                        !!task.registrationError || artifactMutation.isPending
+                                    }
+                                    size="sm"
+                                    data-focus-visible="false"
+                                >
+                                    <FileCodeIcon className="w-4 h-4" />
+                                </Button>
+                            </DropdownTrigger>
+                            <DropdownMenu
+                                aria-label="Export the task"
+                                onAction={(key) =>
+                                    artifactMutation.mutate(key as 'script' | 'systemd')
+                                }
+                            >
+                                <DropdownItem key="script">Export as shell script</DropdownItem>
+                                <DropdownItem key="systemd">Export as systemd timer</DropdownItem>
+                            </DropdownMenu>
+                        </Dropdown>
                         <Button
                             isIconOnly={true}
                             color="danger"
@@ -396,6 +751,20 @@ function TaskCard({
                         <>
                             <AlertCircleIcon className="w-4 h-4 text-danger-600" />
                             <p className="text-sm font-bold text-danger-600">{errorLine}</p>
+                            {reauthRemote && (
+                                <Button
+                                    size="sm"
+                                    color="warning"
+                                    variant="flat"
+                                    className="ml-auto shrink-0"
+                                    startContent={<KeyRoundIcon className="w-4 h-4" />}
+                                    isLoading={reconnectMutation.isPending}
+                                    onPress={() => reconnectMutation.mutate(reauthRemote)}
+                                    data-focus-visible="false"
+                                >
+                                    Reconnect remote
+                                </Button>
+                            )}
                         </>
                     ) : (
                         <>
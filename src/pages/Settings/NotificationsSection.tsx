@@ -7,6 +7,7 @@ import {
     DropdownItem,
     DropdownMenu,
     DropdownTrigger,
+    Input,
     Switch,
     Tooltip,
     cn,
@@ -14,29 +15,56 @@ import {
 import { useMutation, useQueryClient } from '@tanstack/react-query'
 import { ask, message } from '@tauri-apps/plugin-dialog'
 import { platform } from '@tauri-apps/plugin-os'
-import { PencilIcon, SendIcon, SettingsIcon, Trash2Icon, TriangleAlertIcon } from 'lucide-react'
+import { formatDistance } from 'date-fns'
+import {
+    ClockIcon,
+    PencilIcon,
+    SendIcon,
+    SettingsIcon,
+    Trash2Icon,
+    TriangleAlertIcon,
+    XIcon,
+} from 'lucide-react'
 import { type ReactNode, useMemo, useState } from 'react'
 import {
     FREE_MAX_TARGETS,
+    MAX_DISPATCH_BUDGET_SECONDS,
+    MIN_DISPATCH_BUDGET_SECONDS,
     NOTIFICATION_PROVIDERS,
     maskWebhookUrl,
+    purgePendingDeliveries,
     removeNotificationTarget,
     sendTestNotification,
+    setNotificationSettings,
     updateNotificationTarget,
+    useNotificationSettings,
     useNotificationTargets,
     useNotificationsCatalog,
+    usePendingDeliveries,
 } from '../../../lib/notifications'
 import { usePersistedStore } from '../../../store/persisted'
 import type {
     NotificationCatalog,
     NotificationProvider,
     NotificationTarget,
+    PendingDelivery,
 } from '../../../types/notifications'
 import NotificationTargetDrawer from '../../components/NotificationTargetDrawer'
 import ProviderIcon, { TelegramIcon, WhatsAppIcon } from '../../components/icons/ProviderIcon'
 import BaseSection from './BaseSection'
 
-const PROVIDER_ORDER: NotificationProvider[] = ['discord', 'slack', 'telegram', 'webhook']
+const PROVIDER_ORDER: NotificationProvider[] = [
+    'discord',
+    'slack',
+    'telegram',
+    'teams',
+    'googlechat',
+    'matrix',
+    'ntfy',
+    'gotify',
+    'webhook',
+    'email',
+]
 
 export default function NotificationsSection() {
     // Targets live in a Rust-owned store (notifications/targets.json) shared with the headless
@@ -131,6 +159,10 @@ export default function NotificationsSection() {
                         </p>
                     )}
                 </section>
+
+                <DeliverySettings />
+
+                <PendingDeliveries catalog={catalogQuery.data} />
             </div>
             {!!drawerProvider && !!catalogQuery.data && (
                 <NotificationTargetDrawer
@@ -394,3 +426,185 @@ function NotificationTargetCard({
         </Card>
     )
 }
+
+// How long a notification waits for its deliveries (notifications/settings.rs). Shared with the
+// headless runner, so the next scheduled run picks a change up.
+function DeliverySettings() {
+    const settingsQuery = useNotificationSettings()
+    const queryClient = useQueryClient()
+    const saved = settingsQuery.data?.dispatchBudgetSeconds
+    const [draft, setDraft] = useState<string | null>(null)
+    const value = draft ?? (saved === undefined ? '' : String(saved))
+    const budget = Number(value)
+    const isInvalid =
+        !Number.isInteger(budget) ||
+        budget < MIN_DISPATCH_BUDGET_SECONDS ||
+        budget > MAX_DISPATCH_BUDGET_SECONDS
+
+    const saveMutation = useMutation({
+        mutationFn: async (dispatchBudgetSeconds: number) => {
+            await setNotificationSettings({ dispatchBudgetSeconds })
+        },
+        onError: async (error) => {
+            await message(error instanceof Error ? error.message : 'Unknown error occurred', {
+                title: 'Update failed',
+                kind: 'error',
+            })
+        },
+        onSettled: async () => {
+            await queryClient.invalidateQueries({ queryKey: ['notifications', 'settings'] })
+            setDraft(null)
+        },
+    })
+
+    return (
+        <section className="flex flex-col gap-2.5">
+            <p className="text-sm font-semibold uppercase text-default-500">Delivery</p>
+            <div className="flex items-center justify-between gap-4 px-4 py-3 rounded-medium bg-content2">
+                <div className="flex flex-col gap-0.5">
+                    <p className="text-small">Time budget</p>
+                    <p className="text-tiny text-default-500">
+                        How long a notification waits for all its webhooks. Slower ones finish in
+                        the background, or are retried later.
+                    </p>
+                </div>
+                <Input
+                    type="number"
+                    aria-label="Time budget in seconds"
+                    endContent={<span className="text-small text-default-400">seconds</span>}
+                    min={MIN_DISPATCH_BUDGET_SECONDS}
+                    max={MAX_DISPATCH_BUDGET_SECONDS}
+                    value={value}
+                    onValueChange={setDraft}
+                    onBlur={() => {
+                        if (draft !== null && !isInvalid && budget !== saved) {
+                            saveMutation.mutate(budget)
+                        }
+                    }}
+                    isInvalid={isInvalid && draft !== null}
+                    errorMessage={`Enter a whole number of seconds between ${MIN_DISPATCH_BUDGET_SECONDS} and ${MAX_DISPATCH_BUDGET_SECONDS}`}
+                    isDisabled={settingsQuery.isLoading || saveMutation.isPending}
+                    className="max-w-36"
+                    data-focus-visible="false"
+                />
+            </div>
+        </section>
+    )
+}
+
+// Failed deliveries the outbox is still retrying (notifications/outbox.rs). Hidden when empty.
+function PendingDeliveries({ catalog }: { catalog: NotificationCatalog | undefined }) {
+    const pendingQuery = usePendingDeliveries()
+    const queryClient = useQueryClient()
+    const pending = pendingQuery.data ?? []
+
+    const purgeMutation = useMutation({
+        mutationFn: async (id?: string) => {
+            await purgePendingDeliveries(id)
+        },
+        onError: async (error) => {
+            await message(error instanceof Error ? error.message : 'Unknown error occurred', {
+                title: 'Discard failed',
+                kind: 'error',
+            })
+        },
+        onSettled: () => queryClient.invalidateQueries({ queryKey: ['notifications', 'outbox'] }),
+    })
+
+    const handleDiscardAll = async () => {
+        const confirmation = await ask(
+            `Discard ${pending.length} pending ${pending.length === 1 ? 'delivery' : 'deliveries'}? They will not be retried.`,
+            {
+                title: 'Discard pending deliveries',
+                kind: 'warning',
+            }
+        )
+        if (confirmation) {
+            purgeMutation.mutate(undefined)
+        }
+    }
+
+    if (pending.length === 0) {
+        return null
+    }
+
+    return (
+        <section className="flex flex-col gap-2.5">
+            <div className="flex items-center justify-between">
+                <p className="text-sm font-semibold uppercase text-default-500">
+                    Pending Retries
+                </p>
+                <Button
+                    size="sm"
+                    variant="light"
+                    color="danger"
+                    onPress={handleDiscardAll}
+                    isDisabled={purgeMutation.isPending}
+                >
+                    Discard All
+                </Button>
+            </div>
+            {pending.map((delivery) => (
+                <PendingDeliveryRow
+                    key={delivery.id}
+                    delivery={delivery}
+                    eventLabel={
+                        catalog?.events.find((e) => e.id === delivery.eventId)?.label ??
+                        delivery.eventId
+                    }
+                    onDiscard={() => purgeMutation.mutate(delivery.id)}
+                />
+            ))}
+        </section>
+    )
+}
+
+function PendingDeliveryRow({
+    delivery,
+    eventLabel,
+    onDiscard,
+}: {
+    delivery: PendingDelivery
+    eventLabel: string
+    onDiscard: () => void
+}) {
+    const now = new Date()
+    const nextAttempt = new Date(delivery.nextAttemptAt)
+    const retryLabel =
+        nextAttempt > now
+            ? `retrying ${formatDistance(nextAttempt, now, { addSuffix: true })}`
+            : 'retrying now'
+    const attemptsLabel = `${delivery.attempts} ${delivery.attempts === 1 ? 'attempt' : 'attempts'}`
+
+    return (
+        <div className="flex items-center justify-between gap-4 px-4 py-3 rounded-medium bg-content2">
+            <div className="flex items-center min-w-0 gap-3">
+                <ClockIcon className="size-5 shrink-0 text-warning" />
+                <div className="flex flex-col min-w-0 gap-0.5">
+                    <p className="truncate text-small">
+                        {eventLabel} → {delivery.targetName}
+                    </p>
+                    <Tooltip content={delivery.lastError} size="lg">
+                        <p className="truncate text-tiny text-default-500">
+                            {delivery.attempts === 0
+                                ? delivery.lastError
+                                : `${attemptsLabel}, ${retryLabel} — ${delivery.lastError}`}
+                        </p>
+                    </Tooltip>
+                </div>
+            </div>
+            <Tooltip content="Discard">
+                <Button
+                    size="sm"
+                    isIconOnly={true}
+                    variant="light"
+                    radius="full"
+                    onPress={onDiscard}
+                    aria-label={`Discard ${eventLabel} to ${delivery.targetName}`}
+                >
+                    <XIcon className="size-4" />
+                </Button>
+            </Tooltip>
+        </div>
+    )
+}
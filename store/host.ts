@@ -83,7 +83,8 @@ interface HostState {
     favoritePaths: { remote: string; path: string; added: number }[]
 
     scheduledTasks: ScheduledTask[]
-    addScheduledTask: (task: Omit<ScheduledTask, 'id'>) => string
+    /** `id`: keep an existing id (an imported task whose job file is already registered). */
+    addScheduledTask: (task: Omit<ScheduledTask, 'id'>, id?: string) => string
     removeScheduledTask: (id: string) => void
     updateScheduledTask: (id: string, task: Partial<ScheduledTask>) => void
 
@@ -139,8 +140,8 @@ export const useHostStore = create<HostState>()(
             favoritePaths: [],
 
             scheduledTasks: [],
-            addScheduledTask: (task: Omit<ScheduledTask, 'id'>) => {
-                const id = crypto.randomUUID()
+            addScheduledTask: (task: Omit<ScheduledTask, 'id'>, existingId?: string) => {
+                const id = existingId ?? crypto.randomUUID()
                 set((state) => ({
                     scheduledTasks: [...state.scheduledTasks, { ...task, id } as ScheduledTask],
                 }))
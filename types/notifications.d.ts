@@ -9,6 +9,7 @@ export type NotificationEventId =
     | 'schedule.started'
     | 'schedule.completed'
     | 'schedule.failed'
+    | 'schedule.reauth-required'
     | 'mount.failed'
     | 'rclone.crashed'
     | 'rclone.update-available'
@@ -17,7 +18,17 @@ export type NotificationEventId =
 export type NotificationSeverity = 'info' | 'success' | 'error'
 export type NotificationCategory = 'transfers' | 'schedules' | 'system'
 
-export type NotificationProvider = 'discord' | 'slack' | 'telegram' | 'webhook'
+export type NotificationProvider =
+    | 'discord'
+    | 'slack'
+    | 'telegram'
+    | 'teams'
+    | 'googlechat'
+    | 'matrix'
+    | 'ntfy'
+    | 'gotify'
+    | 'webhook'
+    | 'email'
 
 export interface NotificationEventMeta {
     id: NotificationEventId
@@ -33,6 +44,71 @@ export interface NotificationCatalog {
     events: NotificationEventMeta[]
 }
 
+/**
+ * Generic webhooks only: a body with `{{variable}}` placeholders (id, label, severity, title,
+ * body, timestamp, data, data.<path>) sent instead of the fixed payload (template.rs).
+ */
+export interface PayloadTemplate {
+    body: string
+    contentType: string
+    method: 'POST' | 'PUT' | 'PATCH'
+}
+
+export interface TargetHeader {
+    name: string
+    value: string
+}
+
+export type TargetAuthMode = 'bearer' | 'basic' | 'header'
+
+/** Generic webhooks, and email targets' SMTP login as basic auth (headers.rs). */
+export interface TargetAuth {
+    mode: TargetAuthMode
+    /** Basic auth only. */
+    username?: string
+    /** Custom-header auth only: the header the secret goes in. */
+    header?: string
+    /**
+     * The token, password or header value. Listings show it redacted; sent in plain text it
+     * replaces the stored one, left out it keeps it.
+     */
+    secret?: string
+}
+
+/**
+ * Narrows a target's subscribed events (filters.rs). Every criterion that is set must match;
+ * within one, any value may. Remote patterns take `*` and `?`.
+ */
+export interface TargetFilters {
+    scheduleIds?: string[]
+    operations?: string[]
+    remotes?: string[]
+    minSeverity?: NotificationSeverity
+}
+
+/**
+ * How a target's events go out (digest.rs): each as it fires, repeats of the same event and task
+ * held back for a window and then summed up in one "(x12)" follow-up, or batched into an hourly
+ * or daily summary.
+ */
+export interface TargetDelivery {
+    mode: 'immediate' | 'deduplicate' | 'digest'
+    /** Deduplicate only; 60 when absent. */
+    windowMinutes?: number
+    /** Digest only. */
+    interval?: 'hourly' | 'daily'
+}
+
+/** A recently dispatched event, and whether the previewed events and filters would take it. */
+export interface FilterPreviewEvent {
+    eventId: NotificationEventId
+    title: string
+    body: string
+    timestamp: string
+    data: unknown
+    matched: boolean
+}
+
 export interface NotificationTarget {
     id: string
     provider: NotificationProvider
@@ -44,4 +120,41 @@ export interface NotificationTarget {
     // Delivery status, written by the dispatcher after each send attempt.
     lastSentAt?: number
     lastError?: string
+    template?: PayloadTemplate
+    /**
+     * Generic webhooks only. Stored encrypted and redacted in listings — the GUI only checks
+     * whether it's set, and sends new secrets in plain text (signing.rs).
+     */
+    signingSecret?: string
+    headers?: TargetHeader[]
+    auth?: TargetAuth
+    filters?: TargetFilters
+    /** Immediate when absent. */
+    delivery?: TargetDelivery
+}
+
+/**
+ * A delivery that failed for a reason that may pass, waiting in the outbox (outbox.rs) for a
+ * retry with backoff. Dropped once delivered, a day old, or its target is deleted or disabled.
+ * One still sending when its time budget ran out is queued with no attempts until it finishes.
+ */
+export interface PendingDelivery {
+    id: string
+    targetId: string
+    targetName: string
+    eventId: NotificationEventId
+    title: string
+    body: string
+    data: unknown
+    timestamp: string
+    createdAt: number
+    attempts: number
+    nextAttemptAt: number
+    lastError: string
+}
+
+/** Notification-wide settings (settings.rs), read by the GUI and the headless runner alike. */
+export interface NotificationSettings {
+    /** How long a dispatch or retry waits for its deliveries, all targets together. */
+    dispatchBudgetSeconds: number
 }
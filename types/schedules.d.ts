@@ -30,6 +30,27 @@ export type ScheduledTask = {
      * grant.
      */
     runMode?: 'system' | 'user'
+    /**
+     * Bisync tasks only: whether a run that needs a resync may retry with resync on. 'never'
+     * (default, also when absent) fails the run; 'first-run' resyncs only before the task has
+     * ever completed; 'always' also recovers from a prior run's critical error.
+     */
+    bisyncResync?: 'never' | 'first-run' | 'always'
+    /** Extra environment for the run's rclone daemon, e.g. RCLONE_TRANSFERS. */
+    env?: Record<string, string>
+    /**
+     * Names of secret env vars. Their values are stored by the scheduler (outside the job file)
+     * and never kept here.
+     */
+    secretEnvKeys?: string[]
+    /** Extra global flags for the run's rclone daemon, one `--name` or `--name=value` each. */
+    daemonFlags?: string[]
+    /** How many of the task's rclone requests run at once (1-16). Default 1 when absent. */
+    maxParallel?: number
+    /** Keep running the remaining requests after one fails. Default false when absent. */
+    continueOnError?: boolean
+    /** How many rotated, gzipped runner/daemon logs to keep (0-50). Default 5 when absent. */
+    logRetention?: number
     /**
      * Set when the last OS-registration attempt failed (cron unrepresentable on this platform,
      * register error). Persisted so a disabled task can explain itself across restarts.